@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// Dameng schema object, stored in system table `SYSOBJECTS` with `TYPE$ = 'SCHOBJ'`
+/// and distinguished by column `SUBTYPE$`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum DmObjectType {
+    #[strum(to_string = "UTAB")]
+    Table,
+    #[strum(to_string = "VIEW")]
+    View,
+    #[strum(to_string = "SEQ")]
+    Sequence,
+}
+
+/// List all schemas. Result columns: `SCHEMA_NAME`, `CRTDATE`
+pub const LIST_SCHEMAS_SQL: &str =
+    r#"SELECT NAME AS SCHEMA_NAME, CRTDATE FROM SYSOBJECTS WHERE TYPE$ = 'SCH' ORDER BY NAME;"#;
+
+/// Get sql listing all objects of `object_type` in one schema, the schema name is bound to `?`.
+/// Result columns: `SCHEMA_NAME`, `NAME`, `COMMENT$`, `CRTDATE`
+///
+/// # Example
+///
+/// ```rust
+/// use dameng_helper::catalog::{list_objects_sql, DmObjectType};
+///
+/// let sql = list_objects_sql(DmObjectType::View);
+/// assert!(sql.contains("A.SUBTYPE$ = 'VIEW'"));
+/// assert!(sql.contains("B.NAME = ?"));
+/// ```
+pub fn list_objects_sql(object_type: DmObjectType) -> String {
+    format!(
+        r#"SELECT B.NAME AS SCHEMA_NAME, A.NAME, C.COMMENT$, A.CRTDATE FROM SYSOBJECTS AS A LEFT JOIN SYSOBJECTS AS B ON A.SCHID = B.ID LEFT JOIN SYSTABLECOMMENTS AS C ON C.SCHNAME = B.NAME AND C.TVNAME = A.NAME WHERE A.TYPE$ = 'SCHOBJ' AND A.SUBTYPE$ = '{}' AND B.TYPE$ = 'SCH' AND B.NAME = ? ORDER BY A.NAME;"#,
+        object_type
+    )
+}
@@ -2,7 +2,7 @@ use crate::error::DmError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DmDateType {
     /// `NUMERIC 数据类型用于存储零、正负定点数。其中:精度是一个无符号整数，
     /// 定义 了总的数字数，精度范围是 1至38，标度定义了小数点右边的数字位数，定义时如省略 精度，则默认是 16。
@@ -80,28 +80,97 @@ pub enum DmDateType {
 
     /// 布尔数据类型:TRUE 和 FALSE。DMSQL 程序的布尔类型和 INT 类型可以相互转化。 如果变量或方法返回的类型是布尔类型，则返回值为 0 或 1。TRUE 和非 0 值的返回值为 1，FALSE 和 0 值返回为 0。
     BOOL,
-    // TODO 时间间隔数据类型
 
-    //未知类型
-    Unknown,
+    /// 年-月间隔类型，描述一个若干年的间隔，引导精度规定了年的取值范围，取值范围为 1~9，缺省为 2。
+    /// INTERVAL YEAR[(引导精度)]
+    INTERVAL_YEAR,
+    /// 描述一个若干年若干月的间隔。INTERVAL YEAR[(引导精度)] TO MONTH
+    INTERVAL_YEAR_TO_MONTH,
+    /// 描述一个若干月的间隔。INTERVAL MONTH[(引导精度)]
+    INTERVAL_MONTH,
+    /// 日-时间隔类型，描述一个若干日的间隔。INTERVAL DAY[(引导精度)]
+    INTERVAL_DAY,
+    /// 描述一个若干日若干小时的间隔。INTERVAL DAY[(引导精度)] TO HOUR
+    INTERVAL_DAY_TO_HOUR,
+    /// 描述一个若干日若干小时若干分钟的间隔。INTERVAL DAY[(引导精度)] TO MINUTE
+    INTERVAL_DAY_TO_MINUTE,
+    /// 描述一个若干日若干小时若干分钟若干秒的间隔，小数秒精度取值范围为 0~6，缺省为 6。
+    /// INTERVAL DAY[(引导精度)] TO SECOND[(小数秒精度)]
+    INTERVAL_DAY_TO_SECOND,
+    /// 描述一个若干小时的间隔。INTERVAL HOUR[(引导精度)]
+    INTERVAL_HOUR,
+    /// 描述一个若干小时若干分钟的间隔。INTERVAL HOUR[(引导精度)] TO MINUTE
+    INTERVAL_HOUR_TO_MINUTE,
+    /// 描述一个若干小时若干分钟若干秒的间隔。INTERVAL HOUR[(引导精度)] TO SECOND[(小数秒精度)]
+    INTERVAL_HOUR_TO_SECOND,
+    /// 描述一个若干分钟的间隔。INTERVAL MINUTE[(引导精度)]
+    INTERVAL_MINUTE,
+    /// 描述一个若干分钟若干秒的间隔。INTERVAL MINUTE[(引导精度)] TO SECOND[(小数秒精度)]
+    INTERVAL_MINUTE_TO_SECOND,
+    /// 描述一个若干秒的间隔。INTERVAL SECOND[(引导精度 [,小数秒精度])]
+    INTERVAL_SECOND,
+
+    /// 伪列 ROWID 的类型，标识数据库中一条记录的物理地址。
+    ROWID,
+    /// 用户自定义的类(CLASS)、对象类型，列类型为类的名称。
+    CLASS,
+    /// DMSQL 程序的记录类型(RECORD)。
+    RECORD,
+
+    //未知类型，保留原始类型名称，如用户自定义类的名称 `SYSDBA.CLS1`，类型未知时为空。
+    Unknown(String),
 }
 
 impl Default for DmDateType {
     fn default() -> Self {
-        Self::Unknown
+        Self::Unknown(String::new())
+    }
+}
+
+impl DmDateType {
+    /// Parse column type name of table metadata, the name of user defined class is kept as
+    /// `Unknown`, E.g: `SYSDBA.CLS1`. Other unrecognised names are error.
+    pub fn from_column_type(s: &str) -> Result<Self, DmError> {
+        s.parse().or_else(|e| {
+            if is_class_name(s) {
+                Ok(Self::Unknown(s.to_string()))
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// Whether the type is a user defined class or object type
+    pub fn is_class(&self) -> bool {
+        match self {
+            Self::CLASS | Self::RECORD => true,
+            Self::Unknown(name) => !name.is_empty(),
+            _ => false,
+        }
     }
 }
 
+/// Class name is an identifier optionally qualified by schema, E.g: `CLS1`, `SYSDBA.CLS1`
+fn is_class_name(s: &str) -> bool {
+    s.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '#'))
+    })
+}
+
 impl FromStr for DmDateType {
     type Err = DmError;
 
+    /// Parse type name ignoring case, type modifiers and extra whitespace,
+    /// E.g: `number(10,2)`, `TIMESTAMP(6) WITH TIME ZONE`, `INTERVAL DAY(2) TO SECOND(6)`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data_type = match &*s.to_uppercase() {
+        let data_type = match &*normalize_type_name(s) {
             "NUMERIC" => Self::NUMERIC,
             "NUMBER" => Self::NUMBER,
             "DECIMAL" | "DEC" => Self::DECIMAL,
             "BIT" => Self::BIT,
-            "INT" | "INTEGER" | "PLS_INTEGER" => Self::INTEGER,
+            "INT" | "INTEGER" | "PLS_INTEGER" | "BINARY_INTEGER" => Self::INTEGER,
             "BIGINT" => Self::BIGINT,
             "TINYINT" => Self::TINYINT,
             "BYTE" => Self::BYTE,
@@ -112,25 +181,67 @@ impl FromStr for DmDateType {
             "FLOAT" => Self::FLOAT,
             "DOUBLE" => Self::DOUBLE,
             "DOUBLE PRECISION" => Self::DOUBLE_PRECISION,
-            "CHAR" => Self::CHAR,
-            "VARCHAR" | "CHARACTER VARYING" => Self::VARCHAR,
-            "TEXT" => Self::TEXT,
-            "IMAGE" => Self::IMAGE,
+            "CHAR" | "CHARACTER" => Self::CHAR,
+            "VARCHAR" | "VARCHAR2" | "CHARACTER VARYING" => Self::VARCHAR,
+            "TEXT" | "LONGVARCHAR" => Self::TEXT,
+            "IMAGE" | "LONGVARBINARY" => Self::IMAGE,
             "BLOB" => Self::BLOB,
             "CLOB" => Self::CLOB,
             "BFILE" => Self::BFILE,
             "DATE" => Self::DATE,
             "TIME" => Self::TIME,
-            "TIMESTAMP" => Self::TIMESTAMP,
+            "TIMESTAMP" | "DATETIME" => Self::TIMESTAMP,
             "TIME WITH TIME ZONE" => Self::TIME_WITH_TIME_ZONE,
-            "DATETIME WITH TIME ZONE" => Self::TIMESTAMP_WITH_TIME_ZONE,
+            "DATETIME WITH TIME ZONE" | "TIMESTAMP WITH TIME ZONE" => {
+                Self::TIMESTAMP_WITH_TIME_ZONE
+            }
             "TIMESTAMP WITH LOCAL TIME ZONE" => Self::TIMESTAMP_WITH_LOCAL_TIME_ZONE,
-            _ => return Err(DmError::DataTypeError(s.to_string())),
+            "BOOL" | "BOOLEAN" => Self::BOOL,
+            "INTERVAL YEAR" => Self::INTERVAL_YEAR,
+            "INTERVAL YEAR TO MONTH" => Self::INTERVAL_YEAR_TO_MONTH,
+            "INTERVAL MONTH" => Self::INTERVAL_MONTH,
+            "INTERVAL DAY" => Self::INTERVAL_DAY,
+            "INTERVAL DAY TO HOUR" => Self::INTERVAL_DAY_TO_HOUR,
+            "INTERVAL DAY TO MINUTE" => Self::INTERVAL_DAY_TO_MINUTE,
+            "INTERVAL DAY TO SECOND" => Self::INTERVAL_DAY_TO_SECOND,
+            "INTERVAL HOUR" => Self::INTERVAL_HOUR,
+            "INTERVAL HOUR TO MINUTE" => Self::INTERVAL_HOUR_TO_MINUTE,
+            "INTERVAL HOUR TO SECOND" => Self::INTERVAL_HOUR_TO_SECOND,
+            "INTERVAL MINUTE" => Self::INTERVAL_MINUTE,
+            "INTERVAL MINUTE TO SECOND" => Self::INTERVAL_MINUTE_TO_SECOND,
+            "INTERVAL SECOND" => Self::INTERVAL_SECOND,
+            "ROWID" => Self::ROWID,
+            "CLASS" | "OBJECT" => Self::CLASS,
+            "RECORD" => Self::RECORD,
+            _ => {
+                return Err(DmError::DataTypeError {
+                    message: s.to_string(),
+                    source: None,
+                })
+            }
         };
         Ok(data_type)
     }
 }
 
+/// Uppercase type name, remove type modifiers in parentheses and collapse whitespace.
+fn normalize_type_name(s: &str) -> String {
+    let mut name = String::with_capacity(s.len());
+    let mut depth = 0;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth == 0 => name.push(c),
+            _ => {}
+        }
+    }
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
 pub trait TryToString {
     type Err;
     fn try_to_string(&self) -> Result<String, Self::Err>;
@@ -171,7 +282,78 @@ impl TryToString for DmDateType {
             DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => {
                 Ok("TIMESTAMP WITH LOCAL TIME ZONE".to_string())
             }
-            _ => Err(DmError::DataTypeError(format!("{:?}", self))),
+            DmDateType::BOOL => Ok("BOOL".to_string()),
+            DmDateType::INTERVAL_YEAR => Ok("INTERVAL YEAR".to_string()),
+            DmDateType::INTERVAL_YEAR_TO_MONTH => Ok("INTERVAL YEAR TO MONTH".to_string()),
+            DmDateType::INTERVAL_MONTH => Ok("INTERVAL MONTH".to_string()),
+            DmDateType::INTERVAL_DAY => Ok("INTERVAL DAY".to_string()),
+            DmDateType::INTERVAL_DAY_TO_HOUR => Ok("INTERVAL DAY TO HOUR".to_string()),
+            DmDateType::INTERVAL_DAY_TO_MINUTE => Ok("INTERVAL DAY TO MINUTE".to_string()),
+            DmDateType::INTERVAL_DAY_TO_SECOND => Ok("INTERVAL DAY TO SECOND".to_string()),
+            DmDateType::INTERVAL_HOUR => Ok("INTERVAL HOUR".to_string()),
+            DmDateType::INTERVAL_HOUR_TO_MINUTE => Ok("INTERVAL HOUR TO MINUTE".to_string()),
+            DmDateType::INTERVAL_HOUR_TO_SECOND => Ok("INTERVAL HOUR TO SECOND".to_string()),
+            DmDateType::INTERVAL_MINUTE => Ok("INTERVAL MINUTE".to_string()),
+            DmDateType::INTERVAL_MINUTE_TO_SECOND => Ok("INTERVAL MINUTE TO SECOND".to_string()),
+            DmDateType::INTERVAL_SECOND => Ok("INTERVAL SECOND".to_string()),
+            DmDateType::ROWID => Ok("ROWID".to_string()),
+            DmDateType::Unknown(name) if !name.is_empty() => Ok(name.clone()),
+            _ => Err(DmError::DataTypeError {
+                message: format!("{:?}", self),
+                source: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dm_data_type_from_str() {
+        let cases = [
+            ("number(10,2)", DmDateType::NUMBER),
+            ("VARCHAR2(100)", DmDateType::VARCHAR),
+            ("longvarchar", DmDateType::TEXT),
+            ("LONGVARBINARY", DmDateType::IMAGE),
+            ("DATETIME(6)", DmDateType::TIMESTAMP),
+            (
+                "TIMESTAMP(3)  WITH TIME ZONE",
+                DmDateType::TIMESTAMP_WITH_TIME_ZONE,
+            ),
+            (
+                "INTERVAL YEAR(4) TO MONTH",
+                DmDateType::INTERVAL_YEAR_TO_MONTH,
+            ),
+            (
+                "interval day(2) to second(6)",
+                DmDateType::INTERVAL_DAY_TO_SECOND,
+            ),
+            ("INTERVAL SECOND(2,6)", DmDateType::INTERVAL_SECOND),
+            ("ROWID", DmDateType::ROWID),
+            ("boolean", DmDateType::BOOL),
+        ];
+        for (name, data_type) in cases {
+            assert_eq!(DmDateType::from_str(name).unwrap(), data_type, "{}", name);
+        }
+        assert!(DmDateType::from_str("SYSDBA.CLS1").is_err());
+    }
+
+    #[test]
+    fn test_dm_data_type_from_column_type() {
+        let data_type = DmDateType::from_column_type("SYSDBA.CLS1").unwrap();
+        assert_eq!(data_type, DmDateType::Unknown("SYSDBA.CLS1".to_string()));
+        assert!(data_type.is_class());
+        assert_eq!(data_type.try_to_string().unwrap(), "SYSDBA.CLS1");
+
+        assert_eq!(
+            DmDateType::from_column_type("varchar(10)").unwrap(),
+            DmDateType::VARCHAR
+        );
+        for name in ["", "INTERVAL WEEK", "GEOMETRY(4326)", "SYSDBA.", "1CLS"] {
+            assert!(DmDateType::from_column_type(name).is_err(), "{}", name);
         }
+        assert!(DmDateType::default().try_to_string().is_err());
     }
 }
@@ -0,0 +1,206 @@
+use crate::error::DmError;
+use crate::table::{DmKeyType, DmTableDesc, DmTableItem, DmTableKey};
+use crate::{DmDateType, TryToString};
+
+/// Quote dameng identifier with double quotes, keep the original case.
+///
+/// # Example
+///
+/// ```rust
+/// use dameng_helper::ddl::quote_ident;
+///
+/// assert_eq!(quote_ident("T1"), r#""T1""#);
+/// assert_eq!(quote_ident(r#"a"b"#), r#""a""b""#);
+/// ```
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_table(schema: Option<&str>, table_name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quote_ident(schema), quote_ident(table_name)),
+        None => quote_ident(table_name),
+    }
+}
+
+fn quote_columns(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|x| quote_ident(x))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl DmTableItem {
+    /// Get column type sql with length, precision and scale. E.g: `VARCHAR(100)`, `NUMERIC(10,2)`
+    pub fn type_sql(&self) -> Result<String, DmError> {
+        let type_name = self.r#type.try_to_string()?;
+        let sql = match self.r#type {
+            // `LENGTH$` is precision, 0 means precision not specified
+            DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL => {
+                match (self.length, self.scale) {
+                    (0, _) => type_name,
+                    (precision, 0) => format!("{}({})", type_name, precision),
+                    (precision, scale) => format!("{}({},{})", type_name, precision, scale),
+                }
+            }
+            DmDateType::CHAR | DmDateType::VARCHAR | DmDateType::BINARY | DmDateType::VARBINARY
+                if self.length > 0 =>
+            {
+                format!("{}({})", type_name, self.length)
+            }
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => {
+                // The low byte of `SCALE` is fractional seconds precision, E.g: `4102` is `0x1006`
+                let precision = self.scale & 0xff;
+                match type_name.split_once(' ') {
+                    Some((base, suffix)) => format!("{}({}) {}", base, precision, suffix),
+                    None => format!("{}({})", type_name, precision),
+                }
+            }
+            _ => type_name,
+        };
+        Ok(sql)
+    }
+
+    /// Get column definition sql. E.g: `"C1" VARCHAR(100) DEFAULT 'x' NOT NULL`
+    pub fn column_sql(&self) -> Result<String, DmError> {
+        let mut sql = format!("{} {}", quote_ident(&self.name), self.type_sql()?);
+        if let Some(default_val) = self.default_val.as_ref().filter(|x| !x.is_empty()) {
+            sql.push_str(" DEFAULT ");
+            sql.push_str(default_val);
+        }
+        if !self.nullable {
+            sql.push_str(" NOT NULL");
+        }
+        Ok(sql)
+    }
+}
+
+impl DmTableKey {
+    /// Get table constraint sql, index return `None`
+    fn constraint_sql(&self) -> Option<String> {
+        let constraint = match self.key_type {
+            DmKeyType::Primary => "PRIMARY KEY",
+            DmKeyType::Unique => "UNIQUE",
+            DmKeyType::UniqueIndex | DmKeyType::Index => return None,
+        };
+        Some(format!(
+            "CONSTRAINT {} {} ({})",
+            quote_ident(&self.name),
+            constraint,
+            quote_columns(&self.columns)
+        ))
+    }
+
+    /// Get `CREATE INDEX` sql, constraint return `None`
+    fn index_sql(&self, table: &str) -> Option<String> {
+        let index = match self.key_type {
+            DmKeyType::UniqueIndex => "UNIQUE INDEX",
+            DmKeyType::Index => "INDEX",
+            DmKeyType::Primary | DmKeyType::Unique => return None,
+        };
+        Some(format!(
+            "CREATE {} {} ON {} ({});",
+            index,
+            quote_ident(&self.name),
+            table,
+            quote_columns(&self.columns)
+        ))
+    }
+}
+
+impl DmTableDesc {
+    /// Generate dameng `CREATE TABLE` sql of all tables, followed by `CREATE INDEX` sql when
+    /// keys are loaded by [`DmTableDesc::with_keys`].
+    pub fn to_create_table_sql(&self, schema: Option<&str>) -> Result<String, DmError> {
+        let mut sqls = vec![];
+        for (table_name, items) in self.data.iter() {
+            let table = quote_table(schema, table_name);
+            let keys = self
+                .keys
+                .get(table_name)
+                .map(|x| x.as_slice())
+                .unwrap_or_default();
+
+            let mut items = items.iter().collect::<Vec<_>>();
+            items.sort_by_key(|x| x.col_index);
+
+            let mut definitions = items
+                .into_iter()
+                .map(|x| x.column_sql())
+                .collect::<Result<Vec<_>, _>>()?;
+            definitions.extend(keys.iter().filter_map(|x| x.constraint_sql()));
+
+            let mut sql = format!(
+                "CREATE TABLE {} (\n    {}\n);",
+                table,
+                definitions.join(",\n    ")
+            );
+            for index in keys.iter().filter_map(|x| x.index_sql(&table)) {
+                sql.push('\n');
+                sql.push_str(&index);
+            }
+            sqls.push(sql);
+        }
+        Ok(sqls.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svec;
+    use crate::tests::mock_table_result;
+
+    #[test]
+    fn test_dameng_create_table_sql() {
+        let result = mock_table_result();
+        let mut dm_table_desc = DmTableDesc::new(result.0, result.1).unwrap();
+        dm_table_desc.data.retain(|k, _| k == "T3" || k == "T4");
+
+        let dm_table_desc = dm_table_desc
+            .with_keys(
+                svec![
+                    "TABLE_NAME",
+                    "KEY_NAME",
+                    "KEY_TYPE",
+                    "COLUMN_NAME",
+                    "POSITION"
+                ],
+                vec![
+                    svec!["T4", "IDX_T4_NAME", "I", "USER_NAME", "1"],
+                    svec!["T4", "IDX_T4_NAME", "I", "ROLE", "2"],
+                    svec!["T4", "PK_T4", "P", "ID", "1"],
+                    svec!["T4", "UK_T4_USER_ID", "U", "USER_ID", "1"],
+                ],
+            )
+            .unwrap();
+
+        let sql = dm_table_desc.to_create_table_sql(Some("SYSDBA")).unwrap();
+        info!("\n{}", sql);
+
+        let expect = r#"CREATE TABLE "SYSDBA"."T3" (
+    "C1" DATETIME(6) WITH TIME ZONE,
+    "CASE_SENSITIVE" TIMESTAMP(6),
+    "C3" VARCHAR(100),
+    "C4" NUMERIC,
+    "NOT_NULL_TEST_LEN" VARCHAR(100) DEFAULT 'default_value_hh' NOT NULL
+);
+
+CREATE TABLE "SYSDBA"."T4" (
+    "ID" INT NOT NULL,
+    "USER_ID" VARCHAR(8188) NOT NULL,
+    "USER_NAME" TEXT NOT NULL,
+    "ROLE" TEXT NOT NULL,
+    "SOURCE" TEXT NOT NULL,
+    CONSTRAINT "PK_T4" PRIMARY KEY ("ID"),
+    CONSTRAINT "UK_T4_USER_ID" UNIQUE ("USER_ID")
+);
+CREATE INDEX "IDX_T4_NAME" ON "SYSDBA"."T4" ("USER_NAME", "ROLE");"#;
+        assert_eq!(sql, expect);
+    }
+}
@@ -1,12 +1,30 @@
 use odbc_api::Error;
 use thiserror::Error as ThisError;
 
+/// Underlying error kept as [`std::error::Error::source`] of [`DmError`]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 #[derive(Debug, ThisError)]
 pub enum DmError {
     #[error("Failed to handle odbc-api error:{0}")]
-    OdbcError(Error),
-    #[error("Failed to parse Dameng DateType with str:{0}")]
-    DataTypeError(String),
-    #[error("Failed to Dameng DateType to_string error:{0}")]
-    ToStringError(String),
+    OdbcError(#[from] Error),
+    #[error("Failed to parse Dameng DateType with str:{message}")]
+    DataTypeError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+    #[error("Failed to Dameng DateType to_string error:{message}")]
+    ToStringError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+    /// Table metadata could not be parsed, `source` is the parse error if any
+    #[error("Failed to parse Dameng table metadata:{message}")]
+    MetadataParseError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
 }
@@ -2,12 +2,15 @@
 #[macro_use]
 extern crate log;
 
+pub mod catalog;
 #[allow(non_camel_case_types)]
 pub mod data_type;
+pub mod ddl;
 pub mod error;
 pub mod table;
 
 pub use data_type::*;
+use error::DmError;
 use odbc_api::buffers::TextRowSet;
 use odbc_api::handles::StatementImpl;
 use odbc_api::{Cursor, CursorImpl, ResultSetMetadata};
@@ -26,16 +29,24 @@ pub trait DmAdapter {
         db_name: &str,
         case_sensitive: bool,
     ) -> TableSqlDescribe;
+    fn get_table_keys_sql(
+        table_names: Vec<String>,
+        db_name: &str,
+        case_sensitive: bool,
+    ) -> TableSqlDescribe;
     fn get_table_desc(
         self,
         describe: TableSqlDescribe,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)>;
+    ) -> Result<(Vec<String>, Vec<Vec<String>>), DmError>;
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TableSqlDescribe {
     pub db_name: String,
+    /// Describe sql with `?` placeholders
     pub describe_sql: String,
+    /// The values bound to `describe_sql` placeholders in order
+    pub values: Vec<String>,
     pub column_name_index: usize,
     pub table_name_index: usize,
     // See detail dameng database case_sensitive rule: <https://github.com/baoyachi/odbc-bridge/discussions/25>
@@ -43,35 +54,80 @@ pub struct TableSqlDescribe {
 }
 
 impl DmAdapter for CursorImpl<StatementImpl<'_>> {
+    /// Get table describe sql, table names and schema name are bound as parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dameng_helper::DmAdapter;
+    /// use odbc_api::handles::StatementImpl;
+    /// use odbc_api::CursorImpl;
+    ///
+    /// let describe = CursorImpl::<StatementImpl>::get_table_sql(
+    ///     vec!["T1".to_string(), "T'2".to_string()],
+    ///     "SYSDBA",
+    ///     false,
+    /// );
+    /// assert!(describe.describe_sql.contains("B.name IN (?,?)"));
+    /// assert!(!describe.describe_sql.contains("T'2"));
+    /// assert_eq!(describe.values, vec!["T1", "T'2", "SYSDBA"]);
+    /// ```
     fn get_table_sql(
         table_names: Vec<String>,
         db_name: &str,
         case_sensitive: bool,
     ) -> TableSqlDescribe {
-        // Use sql: `SELECT A.*, B.NAME AS TABLE_NAME FROM SYSCOLUMNS AS a LEFT JOIN SYSOBJECTS AS B ON A.id = B.id WHERE B.name IN ("X")`;
-        // The X is table name;
-        let tables = table_names
-            .iter()
-            .map(|x| format!("'{}'", x))
-            .collect::<Vec<_>>()
-            .join(",");
+        // Use sql: `SELECT A.*, B.NAME AS TABLE_NAME FROM SYSCOLUMNS AS a LEFT JOIN SYSOBJECTS AS B ON A.id = B.id WHERE B.name IN (?)`;
+        // The ? is table name placeholder;
+        let placeholders = vec!["?"; table_names.len()].join(",");
         let describe_sql = format!(
-            r#"SELECT A.NAME, A.ID, A.COLID, A.TYPE$, A.LENGTH$, A.SCALE, A.NULLABLE$, A.DEFVAL, B.NAME AS TABLE_NAME, B.CRTDATE FROM SYSCOLUMNS AS a LEFT JOIN SYSOBJECTS AS B ON A.id = B.id WHERE B.name IN ({}) AND B.SCHID IN (SELECT ID FROM SYSOBJECTS WHERE name = '{}');"#,
-            tables, db_name
+            r#"SELECT A.NAME, A.ID, A.COLID, A.TYPE$, A.LENGTH$, A.SCALE, A.NULLABLE$, A.DEFVAL, B.NAME AS TABLE_NAME, B.CRTDATE FROM SYSCOLUMNS AS a LEFT JOIN SYSOBJECTS AS B ON A.id = B.id WHERE B.name IN ({}) AND B.SCHID IN (SELECT ID FROM SYSOBJECTS WHERE name = ?);"#,
+            placeholders
         );
+        let mut values = table_names;
+        values.push(db_name.to_string());
         TableSqlDescribe {
             db_name: db_name.to_string(),
             describe_sql,
+            values,
             column_name_index: 0,
             table_name_index: 8,
             case_sensitive,
         }
     }
 
+    /// Get primary keys, unique constraints and normal indexes sql of tables.
+    /// Result columns: `TABLE_NAME`, `KEY_NAME`, `KEY_TYPE`, `COLUMN_NAME`, `POSITION`.
+    /// The indexes created by constraints are excluded.
+    fn get_table_keys_sql(
+        table_names: Vec<String>,
+        db_name: &str,
+        case_sensitive: bool,
+    ) -> TableSqlDescribe {
+        let placeholders = vec!["?"; table_names.len()].join(",");
+        let describe_sql = format!(
+            r#"SELECT C.TABLE_NAME, C.CONSTRAINT_NAME AS KEY_NAME, C.CONSTRAINT_TYPE AS KEY_TYPE, L.COLUMN_NAME, L.POSITION FROM ALL_CONSTRAINTS AS C JOIN ALL_CONS_COLUMNS AS L ON C.OWNER = L.OWNER AND C.CONSTRAINT_NAME = L.CONSTRAINT_NAME WHERE C.CONSTRAINT_TYPE IN ('P', 'U') AND C.TABLE_NAME IN ({0}) AND C.OWNER = ? UNION ALL SELECT I.TABLE_NAME, I.INDEX_NAME AS KEY_NAME, CASE WHEN I.UNIQUENESS = 'UNIQUE' THEN 'UI' ELSE 'I' END AS KEY_TYPE, L.COLUMN_NAME, L.COLUMN_POSITION AS POSITION FROM ALL_INDEXES AS I JOIN ALL_IND_COLUMNS AS L ON I.OWNER = L.INDEX_OWNER AND I.INDEX_NAME = L.INDEX_NAME WHERE I.INDEX_TYPE = 'NORMAL' AND I.TABLE_NAME IN ({0}) AND I.TABLE_OWNER = ? AND I.INDEX_NAME NOT IN (SELECT INDEX_NAME FROM ALL_CONSTRAINTS WHERE OWNER = ? AND INDEX_NAME IS NOT NULL) ORDER BY 1, 2, 5;"#,
+            placeholders
+        );
+        let mut values = table_names.clone();
+        values.push(db_name.to_string());
+        values.extend(table_names);
+        values.push(db_name.to_string());
+        values.push(db_name.to_string());
+        TableSqlDescribe {
+            db_name: db_name.to_string(),
+            describe_sql,
+            values,
+            column_name_index: 3,
+            table_name_index: 0,
+            case_sensitive,
+        }
+    }
+
     fn get_table_desc(
         mut self,
         describe: TableSqlDescribe,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
+    ) -> Result<(Vec<String>, Vec<Vec<String>>), DmError> {
         debug!("describe:{:?}", describe);
         let case_sensitive_fn = |row_index: usize, name: Cow<str>| -> String {
             if !describe.case_sensitive
@@ -107,10 +163,9 @@ impl DmAdapter for CursorImpl<StatementImpl<'_>> {
 
 #[cfg(test)]
 mod tests {
-    const DAMENG_CONNECTION: &str = "Driver={DM8};Server=0.0.0.0;UID=SYSDBA;PWD=SYSDBA001;";
-
     use odbc_api::Environment;
-    use odbc_api_helper::executor::database::{ConnectionTrait, OdbcDbConnection, Options};
+    use odbc_api_helper::executor::connection::ConnectionConfig;
+    use odbc_api_helper::executor::database::{ConnectionTrait, OdbcDbConnection};
     use odbc_api_helper::executor::execute::ExecResult;
     use odbc_api_helper::executor::table::TableDescResult;
     use odbc_api_helper::executor::SupportDatabase;
@@ -152,13 +207,11 @@ mod tests {
     }
 
     fn get_dameng_conn() -> OdbcDbConnection<'static> {
-        let conn = ENV
-            .connect_with_connection_string(DAMENG_CONNECTION)
-            .unwrap();
-
-        let connection =
-            OdbcDbConnection::new(conn, Options::new(SupportDatabase::Dameng)).unwrap();
-        connection
+        let config = ConnectionConfig::new(SupportDatabase::Dameng, "DM8")
+            .with_server("0.0.0.0")
+            .with_user("SYSDBA")
+            .with_password("SYSDBA001");
+        OdbcDbConnection::connect(&ENV, &config).unwrap()
     }
 
     #[test]
@@ -172,6 +225,32 @@ mod tests {
         cursor.print_all_tables().unwrap();
     }
 
+    #[test]
+    fn test_dameng_list_catalog() {
+        let connection = get_dameng_conn();
+
+        let create_table = r#"CREATE TABLE SYSDBA.T_CATALOG (id INT NOT NULL);"#;
+        let _: ExecResult = connection.execute(create_table).unwrap();
+        let create_view = r#"CREATE VIEW SYSDBA.V_CATALOG AS SELECT id FROM SYSDBA.T_CATALOG;"#;
+        let _: ExecResult = connection.execute(create_view).unwrap();
+        let create_sequence = r#"CREATE SEQUENCE SYSDBA.S_CATALOG;"#;
+        let _: ExecResult = connection.execute(create_sequence).unwrap();
+
+        let schemas = connection.list_schemas().unwrap();
+        assert!(schemas.names().contains(&"SYSDBA"));
+
+        let tables = connection.list_tables("SYSDBA").unwrap();
+        assert!(tables.names().contains(&"T_CATALOG"));
+        assert!(!tables.names().contains(&"V_CATALOG"));
+
+        let views = connection.list_views("SYSDBA").unwrap();
+        assert!(views.names().contains(&"V_CATALOG"));
+
+        let sequences = connection.list_sequences("SYSDBA").unwrap();
+        assert!(sequences.names().contains(&"S_CATALOG"));
+        sequences.print_all_tables().unwrap();
+    }
+
     #[test]
     fn test_dameng_table_desc() {
         let connection = get_dameng_conn();
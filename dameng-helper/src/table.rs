@@ -1,5 +1,6 @@
+use crate::error::DmError;
 use crate::DmDateType;
-use odbc_common::{Print, StyledString, Table, TableTheme, TextStyle};
+use odbc_common::{Print, TableData};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::str::FromStr;
@@ -105,46 +106,84 @@ pub enum ColNameEnum {
 pub struct DmTableDesc {
     pub headers: BTreeMap<usize, ColNameEnum>,
     pub data: BTreeMap<String, Vec<DmTableItem>>,
+    /// Table keys and indexes, key is table name. Empty when not loaded.
+    #[serde(default)]
+    pub keys: BTreeMap<String, Vec<DmTableKey>>,
+}
+
+/// dameng table key type, value of `KEY_TYPE` column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display, Serialize, Deserialize)]
+pub enum DmKeyType {
+    #[strum(to_string = "P")]
+    Primary,
+    #[strum(to_string = "U")]
+    Unique,
+    #[strum(to_string = "UI")]
+    UniqueIndex,
+    #[strum(to_string = "I")]
+    Index,
+}
+
+/// dameng table primary key, unique constraint or index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmTableKey {
+    pub name: String,
+    pub key_type: DmKeyType,
+    /// key columns in order
+    pub columns: Vec<String>,
+}
+
+/// table keys describe
+#[derive(Debug, EnumString, Display, Serialize, Deserialize)]
+pub enum KeyColNameEnum {
+    #[strum(to_string = "TABLE_NAME")]
+    TableName,
+    #[strum(to_string = "KEY_NAME")]
+    KeyName,
+    #[strum(to_string = "KEY_TYPE")]
+    KeyType,
+    #[strum(to_string = "COLUMN_NAME")]
+    ColumnName,
+    #[strum(to_string = "POSITION")]
+    Position,
 }
 
 impl Print for DmTableDesc {
-    fn convert_table(self) -> anyhow::Result<Table> {
-        let headers: Vec<StyledString> = self
-            .headers
-            .values()
-            .map(|x| StyledString::new(x.to_string(), TextStyle::default_header()))
-            .collect();
+    fn table_data(self) -> anyhow::Result<TableData> {
+        let headers = self.headers.values().map(|x| x.to_string()).collect();
 
         let items: Vec<DmTableItem> = self.data.into_iter().fold(vec![], |mut vec, (_, mut x)| {
             vec.append(&mut x);
             vec
         });
-        let rows = items
-            .iter()
-            .map(|x| {
-                x.to_vec()
-                    .into_iter()
-                    .map(|x| StyledString::new(x, TextStyle::basic_left()))
-                    .collect()
-            })
-            .collect::<Vec<Vec<StyledString>>>();
+        let rows = items.iter().map(|x| x.to_vec()).collect();
 
-        Ok(Table::new(headers, rows, TableTheme::rounded()))
+        Ok(TableData::from((headers, rows)))
     }
 }
 
 impl DmTableDesc {
-    pub fn new(headers: Vec<String>, data: Vec<Vec<String>>) -> anyhow::Result<Self> {
+    pub fn new(headers: Vec<String>, data: Vec<Vec<String>>) -> Result<Self, DmError> {
         macro_rules! to_type {
             ($val:expr,$t:ident) => {
-                $val.parse::<$t>()
+                $val.parse::<$t>().map_err(|e| DmError::MetadataParseError {
+                    message: format!("invalid {}:{}", stringify!($t), $val),
+                    source: Some(e.into()),
+                })
             };
         }
 
         let headers = headers
             .iter()
+            .map(|x| {
+                ColNameEnum::from_str(x).map_err(|e| DmError::MetadataParseError {
+                    message: format!("table describe header `{}`", x),
+                    source: Some(e.into()),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .enumerate()
-            .map(|(index, x)| (index, ColNameEnum::from_str(x).unwrap()))
             .fold(BTreeMap::default(), |mut m, (index, x)| {
                 m.insert(index, x);
                 m
@@ -161,7 +200,8 @@ impl DmTableDesc {
                     ColNameEnum::Name => item.name = val,
                     ColNameEnum::Id => item.table_id = to_type!(val, usize)?,
                     ColNameEnum::ColId => item.col_index = to_type!(val, usize)?,
-                    ColNameEnum::Type => item.r#type = to_type!(val, DmDateType)?,
+                    // Unrecognised type name is the name of user defined class type
+                    ColNameEnum::Type => item.r#type = DmDateType::from_column_type(&val)?,
                     ColNameEnum::Length => item.length = to_type!(val, usize)?,
                     ColNameEnum::Scale => item.scale = to_type!(val, usize)?,
                     ColNameEnum::Nullable => match val.to_uppercase().as_ref() {
@@ -184,8 +224,63 @@ impl DmTableDesc {
         Ok(DmTableDesc {
             headers,
             data: data_map,
+            keys: Default::default(),
         })
     }
+
+    /// Fill table keys with the result of `DmAdapter::get_table_keys_sql`.
+    /// The rows must be ordered by table name, key name and column position.
+    pub fn with_keys(
+        mut self,
+        headers: Vec<String>,
+        data: Vec<Vec<String>>,
+    ) -> Result<Self, DmError> {
+        let headers = headers
+            .iter()
+            .map(|x| {
+                KeyColNameEnum::from_str(x).map_err(|e| DmError::MetadataParseError {
+                    message: format!("table keys header `{}`", x),
+                    source: Some(e.into()),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for rows in data {
+            assert_eq!(rows.len(), headers.len());
+            let (mut table_name, mut key_name, mut key_type, mut column_name) = (
+                String::new(),
+                String::new(),
+                DmKeyType::Index,
+                String::new(),
+            );
+            for (index, val) in rows.into_iter().enumerate() {
+                match headers[index] {
+                    KeyColNameEnum::TableName => table_name = val,
+                    KeyColNameEnum::KeyName => key_name = val,
+                    KeyColNameEnum::KeyType => {
+                        key_type =
+                            DmKeyType::from_str(&val).map_err(|e| DmError::MetadataParseError {
+                                message: format!("invalid key type:{}", val),
+                                source: Some(e.into()),
+                            })?
+                    }
+                    KeyColNameEnum::ColumnName => column_name = val,
+                    KeyColNameEnum::Position => {}
+                }
+            }
+
+            let keys = self.keys.entry(table_name).or_default();
+            match keys.last_mut() {
+                Some(key) if key.name == key_name => key.columns.push(column_name),
+                _ => keys.push(DmTableKey {
+                    name: key_name,
+                    key_type,
+                    columns: vec![column_name],
+                }),
+            }
+        }
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
@@ -247,4 +342,25 @@ mod tests {
 ╰───────────────────┴──────┴───────┴────────────────────────────────┴────────────┴───────┴───────────┴────────────────────┴────────────┴────────────────────────────╯"#;
         assert_eq!(string, expect);
     }
+
+    #[test]
+    fn test_dameng_table_desc_parse_error() {
+        use std::error::Error;
+
+        let (headers, mut data) = mock_table_result();
+        data[0][1] = "x".to_string();
+        let err = DmTableDesc::new(headers.clone(), data).unwrap_err();
+        assert!(matches!(err, DmError::MetadataParseError { .. }));
+        assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+
+        let mut bad_headers = headers.clone();
+        bad_headers[0] = "FOO".to_string();
+        let err = DmTableDesc::new(bad_headers, vec![]).unwrap_err();
+        assert!(err.source().is_some());
+
+        let (_, mut data) = mock_table_result();
+        data[0][3] = "INTERVAL WEEK".to_string();
+        let err = DmTableDesc::new(headers, data).unwrap_err();
+        assert!(matches!(err, DmError::DataTypeError { .. }));
+    }
 }
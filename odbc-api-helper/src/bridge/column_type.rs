@@ -0,0 +1,436 @@
+use crate::error::OdbcHelperError;
+use crate::{Convert, TryConvert};
+use dameng_helper::table::DmTableItem;
+use dameng_helper::{DmDateType, TryToString};
+use pg_helper::table::PgTableItem;
+use pg_helper::PgType;
+use std::fmt::{Display, Formatter};
+
+/// Dameng max precision of `NUMERIC`, `NUMBER` and `DECIMAL`
+pub const DM_MAX_NUMERIC_PRECISION: usize = 38;
+/// Dameng max length of `CHAR` and `VARCHAR` with default 8K page size
+pub const DM_MAX_VARCHAR_LENGTH: usize = 8188;
+/// Max fractional seconds precision of dameng and postgres time types
+pub const MAX_FRACTIONAL_SECONDS: usize = 6;
+/// Length of uuid text, E.g: `a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11`
+const UUID_TEXT_LENGTH: usize = 36;
+/// Max length of inet and cidr text, IPv6 address with IPv4 suffix and netmask
+const INET_TEXT_LENGTH: usize = 43;
+
+/// Parameterised column type: the base type with its type modifiers.
+/// E.g: `VARCHAR(100)`, `NUMERIC(10,2)`, `TIMESTAMP(6)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnType<T> {
+    pub base: T,
+    /// character or binary length
+    pub length: Option<usize>,
+    /// numeric precision
+    pub precision: Option<usize>,
+    /// numeric scale
+    pub scale: Option<usize>,
+    /// fractional seconds precision of time and timestamp types
+    pub fractional_seconds: Option<usize>,
+}
+
+pub type DmColumnType = ColumnType<DmDateType>;
+pub type PgColumnType = ColumnType<PgType>;
+
+impl<T> ColumnType<T> {
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            length: None,
+            precision: None,
+            scale: None,
+            fractional_seconds: None,
+        }
+    }
+
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    pub fn with_precision(mut self, precision: usize, scale: usize) -> Self {
+        self.precision = Some(precision);
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn with_fractional_seconds(mut self, fractional_seconds: usize) -> Self {
+        self.fractional_seconds = Some(fractional_seconds);
+        self
+    }
+
+    /// Value of table item `length` field: length or numeric precision
+    pub(crate) fn item_length(&self) -> Option<usize> {
+        self.length.or(self.precision)
+    }
+
+    /// Value of table item `scale` field: numeric scale or fractional seconds precision
+    pub(crate) fn item_scale(&self) -> Option<usize> {
+        self.scale.or(self.fractional_seconds)
+    }
+
+    fn modifiers(&self) -> String {
+        match (
+            self.length,
+            self.precision,
+            self.scale,
+            self.fractional_seconds,
+        ) {
+            (Some(length), ..) => format!("({})", length),
+            (_, Some(precision), Some(scale), _) if scale > 0 => {
+                format!("({},{})", precision, scale)
+            }
+            (_, Some(precision), ..) => format!("({})", precision),
+            (.., Some(fractional_seconds)) => format!("({})", fractional_seconds),
+            _ => "".to_string(),
+        }
+    }
+}
+
+impl Display for DmColumnType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = self
+            .base
+            .try_to_string()
+            .unwrap_or_else(|_| format!("{:?}", self.base));
+        write!(f, "{}{}", name, self.modifiers())
+    }
+}
+
+impl Display for PgColumnType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.base.name(), self.modifiers())
+    }
+}
+
+/// Warning of type conversion losing length, precision or semantics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LossyWarning {
+    /// source type, E.g: `numeric(50,4)`
+    pub source: String,
+    /// target type, E.g: `NUMERIC(38,4)`
+    pub target: String,
+    pub reason: String,
+}
+
+impl Display for LossyWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}: {}", self.source, self.target, self.reason)
+    }
+}
+
+/// Converted column type with lossy conversion warnings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeConversion<T> {
+    pub column_type: ColumnType<T>,
+    pub warnings: Vec<LossyWarning>,
+}
+
+impl<T> TypeConversion<T>
+where
+    ColumnType<T>: Display,
+{
+    fn new(column_type: ColumnType<T>) -> Self {
+        Self {
+            column_type,
+            warnings: vec![],
+        }
+    }
+
+    fn warn<S: Display>(&mut self, source: &S, reason: String) {
+        self.warnings.push(LossyWarning {
+            source: source.to_string(),
+            target: self.column_type.to_string(),
+            reason,
+        });
+    }
+}
+
+impl Convert<DmColumnType> for &DmTableItem {
+    fn convert(self) -> DmColumnType {
+        let column_type = DmColumnType::new(self.r#type.clone());
+        match self.r#type {
+            // `LENGTH$` is precision, 0 means precision not specified
+            DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL if self.length > 0 => {
+                column_type.with_precision(self.length, self.scale)
+            }
+            DmDateType::CHAR | DmDateType::VARCHAR | DmDateType::BINARY | DmDateType::VARBINARY
+                if self.length > 0 =>
+            {
+                column_type.with_length(self.length)
+            }
+            // The low byte of `SCALE` is fractional seconds precision, E.g: `4102` is `0x1006`
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => {
+                column_type.with_fractional_seconds(self.scale & 0xff)
+            }
+            _ => column_type,
+        }
+    }
+}
+
+impl Convert<PgColumnType> for &PgTableItem {
+    fn convert(self) -> PgColumnType {
+        let column_type = PgColumnType::new(self.r#type.clone());
+        match self.r#type {
+            PgType::NUMERIC if self.length > 0 => {
+                column_type.with_precision(self.length, self.scale)
+            }
+            PgType::CHAR | PgType::BPCHAR | PgType::VARCHAR | PgType::BIT | PgType::VARBIT
+                if self.length > 0 =>
+            {
+                column_type.with_length(self.length)
+            }
+            PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+                column_type.with_fractional_seconds(self.scale)
+            }
+            _ => column_type,
+        }
+    }
+}
+
+impl TryConvert<TypeConversion<PgType>> for &DmColumnType {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<TypeConversion<PgType>, Self::Error> {
+        let base: PgType = self.base.clone().try_convert()?;
+        let mut result = TypeConversion::new(PgColumnType::new(base));
+        match self.base {
+            DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL => {
+                result.column_type.precision = self.precision;
+                result.column_type.scale = self.scale;
+            }
+            DmDateType::CHAR | DmDateType::VARCHAR => {
+                result.column_type.length = self.length;
+            }
+            DmDateType::BINARY | DmDateType::VARBINARY => {
+                if let Some(length) = self.length {
+                    result.warn(
+                        self,
+                        format!("bytea does not limit binary length to {}", length),
+                    );
+                }
+            }
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => {
+                result.column_type.fractional_seconds = self
+                    .fractional_seconds
+                    .map(|x| x.min(MAX_FRACTIONAL_SECONDS));
+                if self.fractional_seconds > Some(MAX_FRACTIONAL_SECONDS) {
+                    result.warn(
+                        self,
+                        "fractional seconds precision is truncated".to_string(),
+                    );
+                }
+                if self.base == DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE {
+                    result.warn(
+                        self,
+                        "local time zone value is stored with time zone".to_string(),
+                    );
+                }
+            }
+            DmDateType::BFILE => {
+                result.warn(self, "external file is stored as bytea".to_string());
+            }
+            DmDateType::ROWID => {
+                result.warn(self, "physical row address is stored as text".to_string());
+            }
+            ref x if x.is_class() => {
+                result.warn(self, "object value is stored as jsonb".to_string());
+            }
+            DmDateType::Unknown(_) => {
+                result.warn(self, "unknown type has no postgres mapping".to_string());
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+}
+
+impl TryConvert<TypeConversion<DmDateType>> for &PgColumnType {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<TypeConversion<DmDateType>, Self::Error> {
+        let base: DmDateType = (&self.base).try_convert()?;
+        let mut result = TypeConversion::new(DmColumnType::new(base.clone()));
+        match self.base {
+            PgType::UUID => {
+                result.column_type.length = Some(UUID_TEXT_LENGTH);
+                return Ok(result);
+            }
+            PgType::INET | PgType::CIDR => {
+                result.column_type.length = Some(INET_TEXT_LENGTH);
+                return Ok(result);
+            }
+            // money is fixed 2 fractional digits of 64 bits integer
+            PgType::MONEY => {
+                result.column_type.precision = Some(19);
+                result.column_type.scale = Some(2);
+                return Ok(result);
+            }
+            _ => {}
+        }
+        match base {
+            DmDateType::NUMERIC => match self.precision {
+                Some(precision) if precision > DM_MAX_NUMERIC_PRECISION => {
+                    let scale = self.scale.unwrap_or_default();
+                    result.column_type.precision = Some(DM_MAX_NUMERIC_PRECISION);
+                    result.column_type.scale = Some(scale.min(DM_MAX_NUMERIC_PRECISION));
+                    result.warn(
+                        self,
+                        format!(
+                            "precision {} exceeds dameng max precision {}",
+                            precision, DM_MAX_NUMERIC_PRECISION
+                        ),
+                    );
+                }
+                Some(_) => {
+                    result.column_type.precision = self.precision;
+                    result.column_type.scale = self.scale;
+                }
+                None => {
+                    result.warn(
+                        self,
+                        format!(
+                            "unconstrained numeric is limited to precision {}",
+                            DM_MAX_NUMERIC_PRECISION
+                        ),
+                    );
+                }
+            },
+            DmDateType::CHAR | DmDateType::VARCHAR => match self.length {
+                Some(length) if length > DM_MAX_VARCHAR_LENGTH => {
+                    result.column_type.base = DmDateType::TEXT;
+                    result.warn(
+                        self,
+                        format!(
+                            "length {} exceeds dameng max length {}",
+                            length, DM_MAX_VARCHAR_LENGTH
+                        ),
+                    );
+                }
+                Some(length) => result.column_type.length = Some(length),
+                None if base == DmDateType::VARCHAR => {
+                    result.column_type.length = Some(DM_MAX_VARCHAR_LENGTH);
+                    result.warn(
+                        self,
+                        format!(
+                            "unlimited varchar is limited to length {}",
+                            DM_MAX_VARCHAR_LENGTH
+                        ),
+                    );
+                }
+                None => {}
+            },
+            DmDateType::INTERVAL_DAY_TO_SECOND => {
+                result.warn(
+                    self,
+                    "year and month of interval are not supported by dameng day to second interval"
+                        .to_string(),
+                );
+            }
+            DmDateType::VARBINARY => {
+                // bit string length is count of bits
+                result.column_type.length = self.length.map(|x| x.div_ceil(8));
+                result.warn(self, "bit string is stored as binary".to_string());
+            }
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE => {
+                result.column_type.fractional_seconds = self.fractional_seconds;
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dameng_to_pg_column_type() {
+        let convert = |x: DmColumnType| -> TypeConversion<PgType> { (&x).try_convert().unwrap() };
+
+        let result = convert(DmColumnType::new(DmDateType::VARCHAR).with_length(100));
+        assert_eq!(result.column_type.to_string(), "varchar(100)");
+        assert!(result.warnings.is_empty());
+
+        let result = convert(DmColumnType::new(DmDateType::NUMBER).with_precision(10, 2));
+        assert_eq!(result.column_type.to_string(), "numeric(10,2)");
+
+        let result = convert(DmColumnType::new(DmDateType::DOUBLE));
+        assert_eq!(result.column_type.base, PgType::FLOAT8);
+
+        let result = convert(DmColumnType::new(DmDateType::TIMESTAMP).with_fractional_seconds(3));
+        assert_eq!(result.column_type.to_string(), "timestamp(3)");
+
+        let result = convert(DmColumnType::new(DmDateType::INTERVAL_DAY_TO_SECOND));
+        assert_eq!(result.column_type.base, PgType::INTERVAL);
+
+        let result = convert(DmColumnType::new(DmDateType::CLASS));
+        assert_eq!(result.column_type.base, PgType::JSONB);
+        assert_eq!(result.warnings.len(), 1);
+
+        let class = DmDateType::Unknown("SYSDBA.CLS1".to_string());
+        let result = convert(DmColumnType::new(class));
+        assert_eq!(result.column_type.base, PgType::JSONB);
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = convert(DmColumnType::new(DmDateType::VARBINARY).with_length(8188));
+        assert_eq!(result.column_type.to_string(), "bytea");
+        assert_eq!(
+            result.warnings[0].to_string(),
+            "VARBINARY(8188) -> bytea: bytea does not limit binary length to 8188"
+        );
+    }
+
+    #[test]
+    fn test_pg_to_dameng_column_type() {
+        let convert =
+            |x: PgColumnType| -> TypeConversion<DmDateType> { (&x).try_convert().unwrap() };
+
+        let result = convert(PgColumnType::new(PgType::NUMERIC).with_precision(50, 4));
+        assert_eq!(result.column_type.to_string(), "NUMERIC(38,4)");
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = convert(PgColumnType::new(PgType::VARCHAR).with_length(10000));
+        assert_eq!(result.column_type.base, DmDateType::TEXT);
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = convert(PgColumnType::new(PgType::BPCHAR).with_length(10));
+        assert_eq!(result.column_type.to_string(), "CHAR(10)");
+        assert!(result.warnings.is_empty());
+
+        let result = convert(PgColumnType::new(PgType::FLOAT4));
+        assert_eq!(result.column_type.base, DmDateType::REAL);
+
+        let result = convert(PgColumnType::new(PgType::UUID));
+        assert_eq!(result.column_type.to_string(), "CHAR(36)");
+        assert!(result.warnings.is_empty());
+
+        let result = convert(PgColumnType::new(PgType::INET));
+        assert_eq!(result.column_type.to_string(), "VARCHAR(43)");
+        let result = convert(PgColumnType::new(PgType::CIDR));
+        assert_eq!(result.column_type.to_string(), "VARCHAR(43)");
+
+        let result = convert(PgColumnType::new(PgType::MONEY));
+        assert_eq!(result.column_type.to_string(), "DECIMAL(19,2)");
+
+        for pg_type in [PgType::INT4_ARRAY, PgType::TEXT_ARRAY, PgType::UUID_ARRAY] {
+            let result = convert(PgColumnType::new(pg_type));
+            assert_eq!(result.column_type.to_string(), "CLOB");
+        }
+    }
+}
@@ -1,11 +1,14 @@
-use crate::TryConvert;
+use crate::bridge::column_type::{DmColumnType, TypeConversion};
+use crate::bridge::mapping::TypeMapping;
+use crate::error::OdbcHelperError;
+use crate::{Convert, TryConvert};
 use dameng_helper::table::DmTableItem;
 use dameng_helper::DmDateType;
 use pg_helper::table::PgTableItem;
 use pg_helper::PgType;
 
 impl TryConvert<PgType> for DmDateType {
-    type Error = anyhow::Error;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<PgType, Self::Error> {
         match self {
@@ -19,12 +22,12 @@ impl TryConvert<PgType> for DmDateType {
             DmDateType::BYTE => Ok(PgType::INT2),
             DmDateType::SMALLINT => Ok(PgType::INT2),
             DmDateType::BINARY => Ok(PgType::BYTEA),
-            DmDateType::VARBINARY => Ok(PgType::VARBIT),
+            DmDateType::VARBINARY => Ok(PgType::BYTEA),
             DmDateType::REAL => Ok(PgType::FLOAT4),
-            DmDateType::FLOAT => Ok(PgType::FLOAT4),
-            DmDateType::DOUBLE => Ok(PgType::FLOAT4),
+            DmDateType::FLOAT => Ok(PgType::FLOAT8),
+            DmDateType::DOUBLE => Ok(PgType::FLOAT8),
             DmDateType::DOUBLE_PRECISION => Ok(PgType::FLOAT8),
-            DmDateType::CHAR => Ok(PgType::CHAR),
+            DmDateType::CHAR => Ok(PgType::BPCHAR),
             DmDateType::VARCHAR => Ok(PgType::VARCHAR),
             DmDateType::TEXT => Ok(PgType::TEXT),
             DmDateType::IMAGE => Ok(PgType::BYTEA),
@@ -38,27 +41,141 @@ impl TryConvert<PgType> for DmDateType {
             DmDateType::TIMESTAMP_WITH_TIME_ZONE => Ok(PgType::TIMESTAMPTZ),
             DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => Ok(PgType::TIMESTAMPTZ),
             DmDateType::BOOL => Ok(PgType::BOOL),
-            DmDateType::Unknown => Ok(PgType::UNKNOWN),
+            DmDateType::INTERVAL_YEAR
+            | DmDateType::INTERVAL_YEAR_TO_MONTH
+            | DmDateType::INTERVAL_MONTH
+            | DmDateType::INTERVAL_DAY
+            | DmDateType::INTERVAL_DAY_TO_HOUR
+            | DmDateType::INTERVAL_DAY_TO_MINUTE
+            | DmDateType::INTERVAL_DAY_TO_SECOND
+            | DmDateType::INTERVAL_HOUR
+            | DmDateType::INTERVAL_HOUR_TO_MINUTE
+            | DmDateType::INTERVAL_HOUR_TO_SECOND
+            | DmDateType::INTERVAL_MINUTE
+            | DmDateType::INTERVAL_MINUTE_TO_SECOND
+            | DmDateType::INTERVAL_SECOND => Ok(PgType::INTERVAL),
+            DmDateType::ROWID => Ok(PgType::TEXT),
+            DmDateType::CLASS | DmDateType::RECORD => Ok(PgType::JSONB),
+            // Unknown type name is the name of user defined class
+            DmDateType::Unknown(name) if !name.is_empty() => Ok(PgType::JSONB),
+            DmDateType::Unknown(_) => Ok(PgType::UNKNOWN),
         }
     }
 }
 
 impl TryConvert<PgTableItem> for DmTableItem {
-    type Error = anyhow::Error;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<PgTableItem, Self::Error> {
-        let pg_type: PgType = self.r#type.try_convert()?;
+        (self, &TypeMapping::default()).try_convert()
+    }
+}
+
+/// Convert dameng column to postgres column, the column type is overridden by [`TypeMapping`]
+impl TryConvert<PgTableItem> for (DmTableItem, &TypeMapping) {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<PgTableItem, Self::Error> {
+        let (item, mapping) = self;
+        let source: DmColumnType = (&item).convert();
+        let column_type = match mapping.pg_type(&item.table_name, &item.name, &source)? {
+            Some(column_type) => column_type,
+            None => {
+                let TypeConversion {
+                    column_type,
+                    warnings,
+                }: TypeConversion<PgType> = (&source).try_convert()?;
+                for warning in warnings {
+                    warn!("column {}.{}: {}", item.table_name, item.name, warning);
+                }
+                column_type
+            }
+        };
+
+        let default_val = item
+            .default_val
+            .as_deref()
+            .and_then(|x| dm_default_to_pg(x, &column_type.base));
         Ok(PgTableItem {
-            name: self.name.to_string(),
-            table_id: self.table_id,
-            col_index: self.col_index,
-            r#type: pg_type,
-            length: self.length,
-            scale: self.scale,
-            nullable: self.nullable,
-            default_val: self.default_val,
-            table_name: self.table_name,
-            create_time: self.create_time,
+            name: item.name.to_string(),
+            table_id: item.table_id,
+            col_index: item.col_index,
+            length: column_type.item_length().unwrap_or(item.length),
+            scale: column_type.item_scale().unwrap_or(item.scale),
+            r#type: column_type.base,
+            nullable: item.nullable,
+            default_val,
+            table_name: item.table_name,
+            create_time: item.create_time,
         })
     }
 }
+
+/// Translate dameng column default expression to postgres.
+///
+/// # Example
+///
+/// ```rust
+/// use odbc_api_helper::bridge::dameng_to_pg::dm_default_to_pg;
+/// use pg_helper::PgType;
+///
+/// assert_eq!(
+///     dm_default_to_pg("SYSDATE", &PgType::TIMESTAMP).as_deref(),
+///     Some("LOCALTIMESTAMP(0)")
+/// );
+/// assert_eq!(
+///     dm_default_to_pg("'1'", &PgType::BOOL).as_deref(),
+///     Some("true")
+/// );
+/// assert_eq!(dm_default_to_pg("", &PgType::TEXT), None);
+/// ```
+pub fn dm_default_to_pg(default_val: &str, pg_type: &PgType) -> Option<String> {
+    let default_val = default_val.trim();
+    if default_val.is_empty() {
+        return None;
+    }
+
+    let upper = default_val.to_uppercase();
+    let translated = match upper.as_str() {
+        "SYSDATE" => "LOCALTIMESTAMP(0)",
+        "SYSTIMESTAMP" | "NOW()" | "GETDATE()" | "CURRENT_TIMESTAMP" => "CURRENT_TIMESTAMP",
+        "CURDATE()" | "CURRENT_DATE" => "CURRENT_DATE",
+        "CURTIME()" => "LOCALTIME",
+        "GUID()" | "NEWID()" => "gen_random_uuid()::text",
+        "1" | "'1'" if *pg_type == PgType::BOOL => "true",
+        "0" | "'0'" if *pg_type == PgType::BOOL => "false",
+        // `SEQ.NEXTVAL` to `nextval('SEQ')`
+        x if x.ends_with(".NEXTVAL") => {
+            let seq = &default_val[..default_val.len() - ".NEXTVAL".len()];
+            return Some(format!("nextval('{}')", seq.replace('\'', "''")));
+        }
+        _ => default_val,
+    };
+    Some(translated.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dm_default_to_pg() {
+        let cases = [
+            ("SYSDATE", PgType::TIMESTAMP, Some("LOCALTIMESTAMP(0)")),
+            (
+                "systimestamp",
+                PgType::TIMESTAMPTZ,
+                Some("CURRENT_TIMESTAMP"),
+            ),
+            ("CURDATE()", PgType::DATE, Some("CURRENT_DATE")),
+            ("SEQ_T1.NEXTVAL", PgType::INT8, Some("nextval('SEQ_T1')")),
+            ("0", PgType::BOOL, Some("false")),
+            ("0", PgType::INT4, Some("0")),
+            ("'foo'", PgType::VARCHAR, Some("'foo'")),
+            ("  ", PgType::TEXT, None),
+        ];
+        for (dm, pg_type, expect) in cases {
+            assert_eq!(dm_default_to_pg(dm, &pg_type).as_deref(), expect, "{}", dm);
+        }
+    }
+}
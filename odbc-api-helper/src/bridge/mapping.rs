@@ -0,0 +1,459 @@
+use crate::bridge::column_type::{ColumnType, DmColumnType, PgColumnType, MAX_FRACTIONAL_SECONDS};
+use crate::error::OdbcHelperError;
+use dameng_helper::DmDateType;
+use pg_helper::PgType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+/// User defined type mapping, override the builtin mapping of `bridge::dameng_to_pg` and
+/// `bridge::pg_to_dameng`.
+///
+/// Lookup order: column override, table type override, global type override, builtin mapping.
+/// The key of type override is dameng type name, the value is postgres type name with optional
+/// type modifiers. When the value has no modifiers, the applicable modifiers of source column
+/// are kept. E.g:
+/// ```toml
+/// [types]
+/// BIT = "int2"
+/// CLOB = "jsonb"
+///
+/// [tables.T1.types]
+/// VARCHAR = "text"
+///
+/// [tables.T1.columns]
+/// PRICE = "numeric(20,4)"
+/// ```
+///
+/// Postgres to dameng overrides are in the `dameng` section with the same layout and lookup
+/// order, the key of type override is postgres type name, the value is dameng type name. E.g:
+/// ```toml
+/// [dameng.types]
+/// jsonb = "TEXT"
+///
+/// [dameng.tables.T1.columns]
+/// PRICE = "NUMBER(20,4)"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeMapping {
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    /// key is table name
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableMapping>,
+    /// postgres to dameng overrides
+    #[serde(default)]
+    pub dameng: DmTypeMapping,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmTypeMapping {
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    /// key is table name
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableMapping>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableMapping {
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    /// key is column name
+    #[serde(default)]
+    pub columns: BTreeMap<String, String>,
+}
+
+impl TypeMapping {
+    pub fn from_toml(s: &str) -> Result<Self, OdbcHelperError> {
+        toml::from_str(s).map_err(|e| metadata_error!(source = e, "invalid type mapping toml"))
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, OdbcHelperError> {
+        serde_json::from_str(s)
+            .map_err(|e| metadata_error!(source = e, "invalid type mapping json"))
+    }
+
+    /// Load mapping file, file with `.json` extension is parsed as json, otherwise toml.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, OdbcHelperError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            metadata_error!(
+                source = e,
+                "read type mapping file {} error",
+                path.display()
+            )
+        })?;
+        match path.extension().and_then(|x| x.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+            && self.tables.is_empty()
+            && self.dameng.types.is_empty()
+            && self.dameng.tables.is_empty()
+    }
+
+    /// Get the overridden postgres type of dameng column, `None` means use builtin mapping.
+    pub fn pg_type(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        source: &DmColumnType,
+    ) -> Result<Option<PgColumnType>, OdbcHelperError> {
+        let table = find_by_name(&self.tables, table_name);
+        let value = table
+            .and_then(|x| find_by_name(&x.columns, column_name))
+            .map(|x| x.as_str())
+            .or_else(|| table.and_then(|x| find_by_type(&x.types, &source.base)))
+            .or_else(|| find_by_type(&self.types, &source.base));
+
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let mut pg_type = parse_pg_type(value)?;
+        if !pg_type.has_modifiers() {
+            pg_type.inherit_modifiers(source);
+            pg_type.retain_applicable_modifiers();
+        }
+        debug!(
+            "column {}.{} type {} is mapped to {} by override",
+            table_name, column_name, source, pg_type
+        );
+        Ok(Some(pg_type))
+    }
+
+    /// Get the overridden dameng type of postgres column, `None` means use builtin mapping.
+    pub fn dm_type(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        source: &PgColumnType,
+    ) -> Result<Option<DmColumnType>, OdbcHelperError> {
+        let table = find_by_name(&self.dameng.tables, table_name);
+        let value = table
+            .and_then(|x| find_by_name(&x.columns, column_name))
+            .map(|x| x.as_str())
+            .or_else(|| table.and_then(|x| find_by_pg_type(&x.types, &source.base)))
+            .or_else(|| find_by_pg_type(&self.dameng.types, &source.base));
+
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let mut dm_type = parse_dm_type(value)?;
+        if !dm_type.has_modifiers() {
+            dm_type.inherit_modifiers(source);
+            dm_type.retain_applicable_modifiers();
+        }
+        debug!(
+            "column {}.{} type {} is mapped to {} by override",
+            table_name, column_name, source, dm_type
+        );
+        Ok(Some(dm_type))
+    }
+}
+
+/// Find value by table or column name, exact name first, then ignore case.
+fn find_by_name<'a, T>(map: &'a BTreeMap<String, T>, name: &str) -> Option<&'a T> {
+    map.get(name).or_else(|| {
+        map.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v)
+    })
+}
+
+/// Find value by dameng type name, E.g: `INT` and `integer` are both `DmDateType::INTEGER`.
+fn find_by_type<'a>(map: &'a BTreeMap<String, String>, dm_type: &DmDateType) -> Option<&'a str> {
+    map.iter()
+        .find(|(k, _)| {
+            DmDateType::from_str(k)
+                .map(|x| x == *dm_type)
+                .unwrap_or_default()
+        })
+        .map(|(_, v)| v.as_str())
+}
+
+/// Find value by postgres type name or alias, E.g: `int4` and `integer` are both `PgType::INT4`.
+fn find_by_pg_type<'a>(map: &'a BTreeMap<String, String>, pg_type: &PgType) -> Option<&'a str> {
+    map.iter()
+        .find(|(k, _)| pg_helper::type_from_name(k).as_ref() == Some(pg_type))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Parse postgres type name with optional modifiers. E.g: `int4`, `varchar(100)`, `numeric(10,2)`
+///
+/// # Example
+///
+/// ```rust
+/// use odbc_api_helper::bridge::mapping::parse_pg_type;
+///
+/// assert_eq!(parse_pg_type("numeric(10,2)").unwrap().to_string(), "numeric(10,2)");
+/// assert_eq!(parse_pg_type("TIMESTAMP(3)").unwrap().fractional_seconds, Some(3));
+/// assert!(parse_pg_type("foo").is_err());
+/// ```
+pub fn parse_pg_type(value: &str) -> Result<PgColumnType, OdbcHelperError> {
+    let (name, args) = match value.split_once('(') {
+        Some((name, args)) => {
+            let args = args
+                .trim()
+                .strip_suffix(')')
+                .ok_or_else(|| invalid_pg_type(value))?
+                .split(',')
+                .map(|x| x.trim().parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| invalid_pg_type(format!("{}, error:{}", value, e)))?;
+            (name, args)
+        }
+        None => (value, vec![]),
+    };
+    let base = pg_helper::type_from_name(name).ok_or_else(|| invalid_pg_type(name))?;
+
+    let pg_type = PgColumnType::new(base.clone());
+    let pg_type = match (base, args.as_slice()) {
+        (_, []) => pg_type,
+        (PgType::NUMERIC, [precision]) => pg_type.with_precision(*precision, 0),
+        (PgType::NUMERIC, [precision, scale]) => pg_type.with_precision(*precision, *scale),
+        (PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ, [precision]) => {
+            pg_type.with_fractional_seconds(*precision)
+        }
+        (
+            PgType::VARCHAR | PgType::BPCHAR | PgType::CHAR | PgType::BIT | PgType::VARBIT,
+            [length],
+        ) => pg_type.with_length(*length),
+        _ => return Err(invalid_pg_type(value)),
+    };
+    Ok(pg_type)
+}
+
+fn invalid_pg_type<S: Display>(value: S) -> OdbcHelperError {
+    metadata_error!("invalid pg type:{}", value)
+}
+
+/// Parse dameng type name with optional modifiers. E.g: `VARCHAR(100)`, `NUMBER(10,2)`,
+/// `TIMESTAMP(3) WITH TIME ZONE`
+///
+/// # Example
+///
+/// ```rust
+/// use odbc_api_helper::bridge::mapping::parse_dm_type;
+///
+/// assert_eq!(parse_dm_type("number(10,2)").unwrap().to_string(), "NUMBER(10,2)");
+/// assert_eq!(parse_dm_type("TIMESTAMP(3) WITH TIME ZONE").unwrap().fractional_seconds, Some(3));
+/// assert!(parse_dm_type("foo").is_err());
+/// ```
+pub fn parse_dm_type(value: &str) -> Result<DmColumnType, OdbcHelperError> {
+    let base = DmDateType::from_str(value).map_err(|_| invalid_dm_type(value))?;
+    let args = match value.split_once('(') {
+        Some((_, args)) => args
+            .split_once(')')
+            .ok_or_else(|| invalid_dm_type(value))?
+            .0
+            .split(',')
+            .map(|x| x.trim().parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid_dm_type(format!("{}, error:{}", value, e)))?,
+        None => vec![],
+    };
+
+    let dm_type = DmColumnType::new(base.clone());
+    let dm_type = match (base, args.as_slice()) {
+        (_, []) => dm_type,
+        (DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL, [precision]) => {
+            dm_type.with_precision(*precision, 0)
+        }
+        (DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL, [precision, scale]) => {
+            dm_type.with_precision(*precision, *scale)
+        }
+        (
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE,
+            [precision],
+        ) => dm_type.with_fractional_seconds(*precision),
+        (
+            DmDateType::CHAR | DmDateType::VARCHAR | DmDateType::BINARY | DmDateType::VARBINARY,
+            [length],
+        ) => dm_type.with_length(*length),
+        _ => return Err(invalid_dm_type(value)),
+    };
+    Ok(dm_type)
+}
+
+fn invalid_dm_type<S: Display>(value: S) -> OdbcHelperError {
+    metadata_error!("invalid dameng type:{}", value)
+}
+
+impl<T> ColumnType<T> {
+    fn has_modifiers(&self) -> bool {
+        self.length.is_some()
+            || self.precision.is_some()
+            || self.scale.is_some()
+            || self.fractional_seconds.is_some()
+    }
+
+    /// Keep the modifiers of source column when override type has none
+    fn inherit_modifiers<S>(&mut self, source: &ColumnType<S>) {
+        self.length = source.length;
+        self.precision = source.precision;
+        self.scale = source.scale;
+        self.fractional_seconds = source
+            .fractional_seconds
+            .map(|x| x.min(MAX_FRACTIONAL_SECONDS));
+    }
+
+    fn clear_modifiers(&mut self, length: bool, numeric: bool, fractional_seconds: bool) {
+        if !length {
+            self.length = None;
+        }
+        if !numeric {
+            self.precision = None;
+            self.scale = None;
+        }
+        if !fractional_seconds {
+            self.fractional_seconds = None;
+        }
+    }
+}
+
+impl DmColumnType {
+    /// Clear the modifiers not applicable to base type, E.g: length of `TEXT`
+    fn retain_applicable_modifiers(&mut self) {
+        let (length, numeric, fractional_seconds) = match self.base {
+            DmDateType::CHAR | DmDateType::VARCHAR | DmDateType::BINARY | DmDateType::VARBINARY => {
+                (true, false, false)
+            }
+            DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL => (false, true, false),
+            DmDateType::TIME
+            | DmDateType::TIMESTAMP
+            | DmDateType::TIME_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_TIME_ZONE
+            | DmDateType::TIMESTAMP_WITH_LOCAL_TIME_ZONE => (false, false, true),
+            _ => (false, false, false),
+        };
+        self.clear_modifiers(length, numeric, fractional_seconds);
+    }
+}
+
+impl PgColumnType {
+    /// Clear the modifiers not applicable to base type, E.g: length of `text`
+    fn retain_applicable_modifiers(&mut self) {
+        let (length, numeric, fractional_seconds) = match self.base {
+            PgType::VARCHAR | PgType::BPCHAR | PgType::CHAR | PgType::BIT | PgType::VARBIT => {
+                (true, false, false)
+            }
+            PgType::NUMERIC => (false, true, false),
+            PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+                (false, false, true)
+            }
+            _ => (false, false, false),
+        };
+        self.clear_modifiers(length, numeric, fractional_seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_mapping() {
+        let mapping = TypeMapping::from_toml(
+            r#"
+[types]
+bit = "int2"
+CLOB = "jsonb"
+VARCHAR = "text"
+
+[tables.T1.types]
+VARCHAR = "bpchar"
+
+[tables.T1.columns]
+PRICE = "numeric(20,4)"
+"#,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&mapping).unwrap();
+        assert_eq!(TypeMapping::from_json(&json).unwrap(), mapping);
+
+        let pg_type = |table: &str, column: &str, source: DmColumnType| {
+            mapping
+                .pg_type(table, column, &source)
+                .unwrap()
+                .map(|x| x.to_string())
+        };
+        let varchar = DmColumnType::new(DmDateType::VARCHAR).with_length(10);
+
+        assert_eq!(
+            pg_type("T1", "PRICE", DmColumnType::new(DmDateType::NUMBER)).as_deref(),
+            Some("numeric(20,4)")
+        );
+        assert_eq!(
+            pg_type("t1", "NAME", varchar.clone()).as_deref(),
+            Some("bpchar(10)")
+        );
+        assert_eq!(pg_type("T2", "NAME", varchar).as_deref(), Some("text"));
+        assert_eq!(
+            pg_type("T2", "FLAG", DmColumnType::new(DmDateType::BIT)).as_deref(),
+            Some("int2")
+        );
+        assert_eq!(
+            pg_type("T2", "ID", DmColumnType::new(DmDateType::INTEGER)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dm_type_mapping() {
+        let mapping = TypeMapping::from_toml(
+            r#"
+[dameng.types]
+jsonb = "TEXT"
+"character varying" = "VARCHAR2"
+
+[dameng.tables.T1.types]
+varchar = "CHAR"
+
+[dameng.tables.T1.columns]
+PRICE = "NUMBER(20,4)"
+"#,
+        )
+        .unwrap();
+        assert!(!mapping.is_empty());
+        assert!(mapping.types.is_empty());
+
+        let dm_type = |table: &str, column: &str, source: PgColumnType| {
+            mapping
+                .dm_type(table, column, &source)
+                .unwrap()
+                .map(|x| x.to_string())
+        };
+        let varchar = PgColumnType::new(PgType::VARCHAR).with_length(10);
+
+        assert_eq!(
+            dm_type("T1", "PRICE", PgColumnType::new(PgType::NUMERIC)).as_deref(),
+            Some("NUMBER(20,4)")
+        );
+        assert_eq!(
+            dm_type("t1", "NAME", varchar.clone()).as_deref(),
+            Some("CHAR(10)")
+        );
+        assert_eq!(
+            dm_type("T2", "NAME", varchar).as_deref(),
+            Some("VARCHAR(10)")
+        );
+        assert_eq!(
+            dm_type("T2", "DATA", PgColumnType::new(PgType::JSONB)).as_deref(),
+            Some("TEXT")
+        );
+        assert_eq!(dm_type("T2", "ID", PgColumnType::new(PgType::INT4)), None);
+    }
+}
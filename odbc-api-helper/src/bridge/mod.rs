@@ -1,2 +1,6 @@
+pub mod column_type;
 pub mod dameng_to_pg;
+pub mod mapping;
+pub mod pg_catalog;
+pub mod pg_dialect;
 pub mod pg_to_dameng;
@@ -0,0 +1,1437 @@
+use crate::bridge::column_type::PgColumnType;
+use crate::error::OdbcHelperError;
+use crate::extension::pg::{PgColumn, PgColumnItem, PgQueryResult, PgValueInput};
+use crate::Convert;
+use pg_helper::table::{PgTableDesc, PgTableItem};
+use pg_helper::{builtin_types, type_info, PgType};
+use postgres_types::{Kind, Oid};
+use sqlparser::ast::Statement;
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+use std::str::FromStr;
+
+mod query;
+
+/// Oid of `pg_catalog` namespace
+pub const PG_CATALOG_NAMESPACE_OID: Oid = 11;
+/// Oid of `public` namespace
+pub const PUBLIC_NAMESPACE_OID: Oid = 2200;
+/// Oid of `information_schema` namespace, it is assigned by `initdb` in real postgres
+pub const INFORMATION_SCHEMA_NAMESPACE_OID: Oid = 13000;
+/// First oid of user defined objects, the emulated schema and tables start from it
+pub const FIRST_NORMAL_OBJECT_ID: Oid = 16384;
+
+const BOOTSTRAP_SUPERUSER_ID: Oid = 10;
+const BOOTSTRAP_SUPERUSER_NAME: &str = "postgres";
+const DEFAULT_COLLATION_OID: Oid = 100;
+/// Oid of `heap` table access method
+const HEAP_TABLE_AM_OID: Oid = 2;
+/// Oid of `btree` index access method
+const BTREE_AM_OID: Oid = 403;
+/// Oid of `C` and `POSIX` collations
+const C_COLLATION_OID: Oid = 950;
+const POSIX_COLLATION_OID: Oid = 951;
+
+/// Build catalog row of text values, bool value is `true` or `false`
+macro_rules! row {
+    ($($value:expr),* $(,)?) => {
+        vec![$(Some($value.to_string())),*]
+    };
+}
+
+/// Emulated postgres system catalog relations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogRelation {
+    PgNamespace,
+    PgClass,
+    PgAttribute,
+    PgType,
+    PgAttrdef,
+    PgAm,
+    PgCollation,
+    PgRoles,
+    PgDescription,
+    PgDepend,
+    PgInherits,
+    PgPolicy,
+    PgStatisticExt,
+    PgPublication,
+    PgPublicationNamespace,
+    PgPublicationRel,
+    /// `information_schema.schemata`
+    Schemata,
+    /// `information_schema.tables`
+    Tables,
+    /// `information_schema.columns`
+    Columns,
+}
+
+impl CatalogRelation {
+    pub const ALL: [CatalogRelation; 19] = [
+        CatalogRelation::PgNamespace,
+        CatalogRelation::PgClass,
+        CatalogRelation::PgAttribute,
+        CatalogRelation::PgType,
+        CatalogRelation::PgAttrdef,
+        CatalogRelation::PgAm,
+        CatalogRelation::PgCollation,
+        CatalogRelation::PgRoles,
+        CatalogRelation::PgDescription,
+        CatalogRelation::PgDepend,
+        CatalogRelation::PgInherits,
+        CatalogRelation::PgPolicy,
+        CatalogRelation::PgStatisticExt,
+        CatalogRelation::PgPublication,
+        CatalogRelation::PgPublicationNamespace,
+        CatalogRelation::PgPublicationRel,
+        CatalogRelation::Schemata,
+        CatalogRelation::Tables,
+        CatalogRelation::Columns,
+    ];
+
+    pub fn schema(&self) -> &'static str {
+        match self {
+            CatalogRelation::Schemata | CatalogRelation::Tables | CatalogRelation::Columns => {
+                "information_schema"
+            }
+            _ => "pg_catalog",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CatalogRelation::PgNamespace => "pg_namespace",
+            CatalogRelation::PgClass => "pg_class",
+            CatalogRelation::PgAttribute => "pg_attribute",
+            CatalogRelation::PgType => "pg_type",
+            CatalogRelation::PgAttrdef => "pg_attrdef",
+            CatalogRelation::PgAm => "pg_am",
+            CatalogRelation::PgCollation => "pg_collation",
+            CatalogRelation::PgRoles => "pg_roles",
+            CatalogRelation::PgDescription => "pg_description",
+            CatalogRelation::PgDepend => "pg_depend",
+            CatalogRelation::PgInherits => "pg_inherits",
+            CatalogRelation::PgPolicy => "pg_policy",
+            CatalogRelation::PgStatisticExt => "pg_statistic_ext",
+            CatalogRelation::PgPublication => "pg_publication",
+            CatalogRelation::PgPublicationNamespace => "pg_publication_namespace",
+            CatalogRelation::PgPublicationRel => "pg_publication_rel",
+            CatalogRelation::Schemata => "schemata",
+            CatalogRelation::Tables => "tables",
+            CatalogRelation::Columns => "columns",
+        }
+    }
+
+    /// Oid of relation in real postgres, used by `regclass`. `information_schema` views are
+    /// created by `initdb` without fixed oid.
+    pub fn oid(&self) -> Option<Oid> {
+        let oid = match self {
+            CatalogRelation::PgNamespace => 2615,
+            CatalogRelation::PgClass => 1259,
+            CatalogRelation::PgAttribute => 1249,
+            CatalogRelation::PgType => 1247,
+            CatalogRelation::PgAttrdef => 2604,
+            CatalogRelation::PgAm => 2601,
+            CatalogRelation::PgCollation => 3456,
+            CatalogRelation::PgRoles => 12000,
+            CatalogRelation::PgDescription => 2609,
+            CatalogRelation::PgDepend => 2608,
+            CatalogRelation::PgInherits => 2611,
+            CatalogRelation::PgPolicy => 3256,
+            CatalogRelation::PgStatisticExt => 3381,
+            CatalogRelation::PgPublication => 6104,
+            CatalogRelation::PgPublicationNamespace => 6237,
+            CatalogRelation::PgPublicationRel => 6106,
+            CatalogRelation::Schemata | CatalogRelation::Tables | CatalogRelation::Columns => {
+                return None
+            }
+        };
+        Some(oid)
+    }
+
+    /// Emulated columns, only the commonly queried columns of real postgres are included.
+    pub fn columns(&self) -> Vec<(&'static str, PgType)> {
+        match self {
+            CatalogRelation::PgNamespace => vec![
+                ("oid", PgType::OID),
+                ("nspname", PgType::NAME),
+                ("nspowner", PgType::OID),
+            ],
+            CatalogRelation::PgClass => vec![
+                ("oid", PgType::OID),
+                ("relname", PgType::NAME),
+                ("relnamespace", PgType::OID),
+                ("reltype", PgType::OID),
+                ("reloftype", PgType::OID),
+                ("relowner", PgType::OID),
+                ("relam", PgType::OID),
+                ("reltablespace", PgType::OID),
+                ("reltuples", PgType::FLOAT4),
+                ("reltoastrelid", PgType::OID),
+                ("relhasindex", PgType::BOOL),
+                ("relpersistence", PgType::CHAR),
+                ("relkind", PgType::CHAR),
+                ("relnatts", PgType::INT2),
+                ("relchecks", PgType::INT2),
+                ("relhasrules", PgType::BOOL),
+                ("relhastriggers", PgType::BOOL),
+                ("relhassubclass", PgType::BOOL),
+                ("relrowsecurity", PgType::BOOL),
+                ("relforcerowsecurity", PgType::BOOL),
+                ("relreplident", PgType::CHAR),
+                ("relispartition", PgType::BOOL),
+                ("reloptions", PgType::TEXT_ARRAY),
+                ("relpartbound", PgType::TEXT),
+            ],
+            CatalogRelation::PgAttribute => vec![
+                ("attrelid", PgType::OID),
+                ("attname", PgType::NAME),
+                ("atttypid", PgType::OID),
+                ("attlen", PgType::INT2),
+                ("attnum", PgType::INT2),
+                ("attndims", PgType::INT2),
+                ("atttypmod", PgType::INT4),
+                ("attbyval", PgType::BOOL),
+                ("attalign", PgType::CHAR),
+                ("attnotnull", PgType::BOOL),
+                ("atthasdef", PgType::BOOL),
+                ("attidentity", PgType::CHAR),
+                ("attgenerated", PgType::CHAR),
+                ("attisdropped", PgType::BOOL),
+                ("attislocal", PgType::BOOL),
+                ("attinhcount", PgType::INT2),
+                ("attcollation", PgType::OID),
+            ],
+            CatalogRelation::PgType => vec![
+                ("oid", PgType::OID),
+                ("typname", PgType::NAME),
+                ("typnamespace", PgType::OID),
+                ("typowner", PgType::OID),
+                ("typlen", PgType::INT2),
+                ("typbyval", PgType::BOOL),
+                ("typtype", PgType::CHAR),
+                ("typcategory", PgType::CHAR),
+                ("typisdefined", PgType::BOOL),
+                ("typdelim", PgType::CHAR),
+                ("typrelid", PgType::OID),
+                ("typelem", PgType::OID),
+                ("typarray", PgType::OID),
+                ("typalign", PgType::CHAR),
+                ("typnotnull", PgType::BOOL),
+                ("typbasetype", PgType::OID),
+                ("typtypmod", PgType::INT4),
+                ("typndims", PgType::INT4),
+                ("typcollation", PgType::OID),
+            ],
+            CatalogRelation::PgAttrdef => vec![
+                ("oid", PgType::OID),
+                ("adrelid", PgType::OID),
+                ("adnum", PgType::INT2),
+                ("adbin", PgType::TEXT),
+            ],
+            CatalogRelation::PgAm => vec![
+                ("oid", PgType::OID),
+                ("amname", PgType::NAME),
+                ("amhandler", PgType::REGPROC),
+                ("amtype", PgType::CHAR),
+            ],
+            CatalogRelation::PgCollation => vec![
+                ("oid", PgType::OID),
+                ("collname", PgType::NAME),
+                ("collnamespace", PgType::OID),
+                ("collowner", PgType::OID),
+                ("collprovider", PgType::CHAR),
+                ("collisdeterministic", PgType::BOOL),
+                ("collencoding", PgType::INT4),
+            ],
+            CatalogRelation::PgRoles => vec![
+                ("rolname", PgType::NAME),
+                ("rolsuper", PgType::BOOL),
+                ("rolinherit", PgType::BOOL),
+                ("rolcreaterole", PgType::BOOL),
+                ("rolcreatedb", PgType::BOOL),
+                ("rolcanlogin", PgType::BOOL),
+                ("rolreplication", PgType::BOOL),
+                ("rolconnlimit", PgType::INT4),
+                ("rolbypassrls", PgType::BOOL),
+                ("oid", PgType::OID),
+            ],
+            CatalogRelation::PgDescription => vec![
+                ("objoid", PgType::OID),
+                ("classoid", PgType::OID),
+                ("objsubid", PgType::INT4),
+                ("description", PgType::TEXT),
+            ],
+            CatalogRelation::PgDepend => vec![
+                ("classid", PgType::OID),
+                ("objid", PgType::OID),
+                ("objsubid", PgType::INT4),
+                ("refclassid", PgType::OID),
+                ("refobjid", PgType::OID),
+                ("refobjsubid", PgType::INT4),
+                ("deptype", PgType::CHAR),
+            ],
+            CatalogRelation::PgInherits => vec![
+                ("inhrelid", PgType::OID),
+                ("inhparent", PgType::OID),
+                ("inhseqno", PgType::INT4),
+                ("inhdetachpending", PgType::BOOL),
+            ],
+            CatalogRelation::PgPolicy => vec![
+                ("oid", PgType::OID),
+                ("polname", PgType::NAME),
+                ("polrelid", PgType::OID),
+                ("polcmd", PgType::CHAR),
+                ("polpermissive", PgType::BOOL),
+                ("polroles", PgType::OID_ARRAY),
+                ("polqual", PgType::PG_NODE_TREE),
+                ("polwithcheck", PgType::PG_NODE_TREE),
+            ],
+            CatalogRelation::PgStatisticExt => vec![
+                ("oid", PgType::OID),
+                ("stxrelid", PgType::OID),
+                ("stxname", PgType::NAME),
+                ("stxnamespace", PgType::OID),
+                ("stxowner", PgType::OID),
+                ("stxstattarget", PgType::INT4),
+                ("stxkeys", PgType::INT2_VECTOR),
+                ("stxkind", PgType::CHAR_ARRAY),
+            ],
+            CatalogRelation::PgPublication => vec![
+                ("oid", PgType::OID),
+                ("pubname", PgType::NAME),
+                ("pubowner", PgType::OID),
+                ("puballtables", PgType::BOOL),
+                ("pubinsert", PgType::BOOL),
+                ("pubupdate", PgType::BOOL),
+                ("pubdelete", PgType::BOOL),
+                ("pubtruncate", PgType::BOOL),
+                ("pubviaroot", PgType::BOOL),
+            ],
+            CatalogRelation::PgPublicationNamespace => vec![
+                ("oid", PgType::OID),
+                ("pnpubid", PgType::OID),
+                ("pnnspid", PgType::OID),
+            ],
+            CatalogRelation::PgPublicationRel => vec![
+                ("oid", PgType::OID),
+                ("prpubid", PgType::OID),
+                ("prrelid", PgType::OID),
+                ("prqual", PgType::PG_NODE_TREE),
+                ("prattrs", PgType::INT2_VECTOR),
+            ],
+            CatalogRelation::Schemata => vec![
+                ("catalog_name", PgType::NAME),
+                ("schema_name", PgType::NAME),
+                ("schema_owner", PgType::NAME),
+            ],
+            CatalogRelation::Tables => vec![
+                ("table_catalog", PgType::NAME),
+                ("table_schema", PgType::NAME),
+                ("table_name", PgType::NAME),
+                ("table_type", PgType::VARCHAR),
+                ("is_insertable_into", PgType::VARCHAR),
+                ("is_typed", PgType::VARCHAR),
+            ],
+            CatalogRelation::Columns => vec![
+                ("table_catalog", PgType::NAME),
+                ("table_schema", PgType::NAME),
+                ("table_name", PgType::NAME),
+                ("column_name", PgType::NAME),
+                ("ordinal_position", PgType::INT4),
+                ("column_default", PgType::VARCHAR),
+                ("is_nullable", PgType::VARCHAR),
+                ("data_type", PgType::VARCHAR),
+                ("character_maximum_length", PgType::INT4),
+                ("character_octet_length", PgType::INT4),
+                ("numeric_precision", PgType::INT4),
+                ("numeric_precision_radix", PgType::INT4),
+                ("numeric_scale", PgType::INT4),
+                ("datetime_precision", PgType::INT4),
+                ("udt_catalog", PgType::NAME),
+                ("udt_schema", PgType::NAME),
+                ("udt_name", PgType::NAME),
+                ("is_updatable", PgType::VARCHAR),
+            ],
+        }
+    }
+}
+
+/// Parse relation name, E.g: `pg_class`, `pg_catalog.pg_class`, `information_schema.columns`.
+/// `pg_catalog` relations could be unqualified, because `pg_catalog` is always in search path.
+impl FromStr for CatalogRelation {
+    type Err = OdbcHelperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (schema, name) = match s.split_once('.') {
+            Some((schema, name)) => (Some(schema), name),
+            None => (None, s),
+        };
+        CatalogRelation::ALL
+            .into_iter()
+            .find(|x| {
+                x.name().eq_ignore_ascii_case(name)
+                    && schema.map_or(x.schema() == "pg_catalog", |schema| {
+                        x.schema().eq_ignore_ascii_case(schema)
+                    })
+            })
+            .ok_or_else(|| sql_error!("unknown catalog relation:{}", s))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CatalogClass {
+    oid: Oid,
+    name: String,
+    /// sorted by column index
+    columns: Vec<PgTableItem>,
+}
+
+/// Emulated postgres `pg_catalog` and `information_schema` of one schema, built from table
+/// describe converted by `bridge::dameng_to_pg`. Postgres clients connected through the bridge
+/// see the tables as native postgres tables.
+///
+/// When `case_sensitive` is false, schema, table and column names are folded to lowercase, the
+/// same as unquoted identifiers of postgres.
+#[derive(Debug, Clone)]
+pub struct PgCatalog {
+    database: String,
+    schema: String,
+    namespace_oid: Oid,
+    tables: Vec<CatalogClass>,
+}
+
+impl PgCatalog {
+    pub fn new(database: &str, schema: &str, desc: &PgTableDesc, case_sensitive: bool) -> Self {
+        let fold = |name: &str| {
+            if case_sensitive {
+                name.to_string()
+            } else {
+                name.to_lowercase()
+            }
+        };
+        let schema = fold(schema);
+        let namespace_oid = if schema == "public" {
+            PUBLIC_NAMESPACE_OID
+        } else {
+            FIRST_NORMAL_OBJECT_ID
+        };
+
+        let mut tables: Vec<CatalogClass> = desc
+            .data
+            .iter()
+            .map(|(name, items)| {
+                let mut columns = items.clone();
+                columns.sort_by_key(|x| x.col_index);
+                for column in columns.iter_mut() {
+                    column.name = fold(&column.name);
+                }
+                CatalogClass {
+                    oid: 0,
+                    name: fold(name),
+                    columns,
+                }
+            })
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        for (index, table) in tables.iter_mut().enumerate() {
+            table.oid = FIRST_NORMAL_OBJECT_ID + 1 + index as Oid;
+        }
+
+        Self {
+            database: fold(database),
+            schema,
+            namespace_oid,
+            tables,
+        }
+    }
+
+    /// Oid of table, `None` if table is not exist
+    pub fn table_oid(&self, name: &str) -> Option<Oid> {
+        self.tables.iter().find(|x| x.name == name).map(|x| x.oid)
+    }
+
+    /// Oid of table or catalog relation, E.g: `t1`, `sysdba.t1`, `pg_catalog.pg_class`
+    fn class_oid(&self, name: &str) -> Option<Oid> {
+        if let Some(oid) = CatalogRelation::from_str(name).ok().and_then(|x| x.oid()) {
+            return Some(oid);
+        }
+        let (schema, name) = match name.rsplit_once('.') {
+            Some((schema, name)) => (Some(schema.trim_matches('"')), name),
+            None => (None, name),
+        };
+        match schema {
+            Some(schema) if schema != self.schema => None,
+            _ => self.table_oid(name.trim_matches('"')),
+        }
+    }
+
+    /// Text output of `regclass`, `regtype` and `regnamespace` value, the oid itself if the
+    /// object is not exist
+    fn reg_name(&self, pg_type: &PgType, oid: Oid) -> String {
+        let name = match *pg_type {
+            PgType::REGCLASS => self
+                .tables
+                .iter()
+                .find(|x| x.oid == oid)
+                .map(|x| x.name.to_string())
+                .or_else(|| {
+                    CatalogRelation::ALL
+                        .into_iter()
+                        .find(|x| x.oid() == Some(oid))
+                        .map(|x| x.name().to_string())
+                }),
+            PgType::REGNAMESPACE => self
+                .namespaces()
+                .into_iter()
+                .find(|(x, _)| *x == oid)
+                .map(|(_, name)| name.to_string()),
+            PgType::REGTYPE => PgType::from_oid(oid).map(|_| query::format_type(oid, None)),
+            _ => None,
+        };
+        name.unwrap_or_else(|| oid.to_string())
+    }
+
+    fn namespace_oid(&self, name: &str) -> Option<Oid> {
+        match name {
+            "pg_catalog" => Some(PG_CATALOG_NAMESPACE_OID),
+            "information_schema" => Some(INFORMATION_SCHEMA_NAMESPACE_OID),
+            x if x == self.schema => Some(self.namespace_oid),
+            _ => None,
+        }
+    }
+
+    /// All rows of relation
+    pub fn relation(&self, relation: CatalogRelation) -> PgQueryResult {
+        let columns = relation.columns();
+        to_query_result(
+            columns.iter().map(|(name, pg_type)| (*name, pg_type)),
+            columns.iter().enumerate().map(|(index, _)| index),
+            self.rows(relation),
+        )
+    }
+
+    /// Answer query of emulated catalog relations.
+    ///
+    /// Returns `None` when the sql does not reference catalog relations or could not be parsed,
+    /// the sql should be executed by the database. A single `SELECT [DISTINCT]` is evaluated in
+    /// memory:
+    /// - `FROM` relations joined by `,`, `[INNER] JOIN`, `LEFT JOIN` or `CROSS JOIN`
+    /// - `UNION`, `INTERSECT` and `EXCEPT` of `SELECT`s
+    /// - `WHERE` and `ON` conditions combined by `AND`, `OR` and `NOT` of comparison,
+    ///   `[NOT] IN`, `[NOT] LIKE|ILIKE`, `~~`, `~`, `~*`, `!~`, `OPERATOR(pg_catalog.~)`,
+    ///   `BETWEEN`, `ANY`, `ALL`, `[NOT] EXISTS` and `IS [NOT] NULL|TRUE|FALSE`
+    /// - `CASE`, scalar subqueries which could reference columns of the outer query, `ARRAY[..]`,
+    ///   `ARRAY(SELECT ..)` and `COLLATE` which is ignored
+    /// - casts, name cast to `regclass`, `regnamespace` and `regtype` is resolved to oid
+    /// - functions `pg_table_is_visible`, `format_type`, `pg_get_expr`, `pg_get_userbyid`,
+    ///   `array_to_string`, `current_schema`, `current_database` and the functions describing
+    ///   objects which are not emulated, E.g: `pg_get_partkeydef` returns NULL
+    /// - `ORDER BY` expression, output column name or position, `LIMIT` and `OFFSET`
+    ///
+    /// `GROUP BY`, aggregates and set returning functions return error.
+    pub fn query(&self, sql: &str) -> Result<Option<PgQueryResult>, OdbcHelperError> {
+        let statements = match Parser::parse_sql(&PostgreSqlDialect {}, sql) {
+            Ok(statements) => statements,
+            Err(_) => return Ok(None),
+        };
+        if !statements.iter().any(query::references_catalog) {
+            return Ok(None);
+        }
+
+        let result = match statements.as_slice() {
+            [Statement::Query(query)] => query::evaluate(self, query),
+            _ => Err(sql_error!("only single SELECT is supported")),
+        };
+        result
+            .map(Some)
+            .map_err(|e| sql_error!("unsupported pg catalog query: {}, error: {}", sql, e))
+    }
+
+    fn rows(&self, relation: CatalogRelation) -> Vec<Vec<Option<String>>> {
+        match relation {
+            CatalogRelation::PgNamespace => self.namespace_rows(),
+            CatalogRelation::PgClass => self.class_rows(),
+            CatalogRelation::PgAttribute => self.attribute_rows(),
+            CatalogRelation::PgType => self.type_rows(),
+            CatalogRelation::PgAttrdef => self.attrdef_rows(),
+            CatalogRelation::PgAm => vec![
+                row![HEAP_TABLE_AM_OID, "heap", "heap_tableam_handler", 't'],
+                row![BTREE_AM_OID, "btree", "bthandler", 'i'],
+            ],
+            CatalogRelation::PgCollation => [
+                (DEFAULT_COLLATION_OID, "default", 'd'),
+                (C_COLLATION_OID, "C", 'c'),
+                (POSIX_COLLATION_OID, "POSIX", 'c'),
+            ]
+            .into_iter()
+            .map(|(oid, name, provider)| {
+                row![
+                    oid,
+                    name,
+                    PG_CATALOG_NAMESPACE_OID,
+                    BOOTSTRAP_SUPERUSER_ID,
+                    provider,
+                    true,
+                    -1
+                ]
+            })
+            .collect(),
+            CatalogRelation::PgRoles => vec![row![
+                BOOTSTRAP_SUPERUSER_NAME,
+                true,
+                true,
+                true,
+                true,
+                true,
+                true,
+                -1,
+                true,
+                BOOTSTRAP_SUPERUSER_ID
+            ]],
+            // Comments, dependencies, inheritance, policies, extended statistics and
+            // publications are not emulated
+            CatalogRelation::PgDescription
+            | CatalogRelation::PgDepend
+            | CatalogRelation::PgInherits
+            | CatalogRelation::PgPolicy
+            | CatalogRelation::PgStatisticExt
+            | CatalogRelation::PgPublication
+            | CatalogRelation::PgPublicationNamespace
+            | CatalogRelation::PgPublicationRel => vec![],
+            CatalogRelation::Schemata => self.schemata_rows(),
+            CatalogRelation::Tables => self.tables_rows(),
+            CatalogRelation::Columns => self.columns_rows(),
+        }
+    }
+
+    fn namespaces(&self) -> Vec<(Oid, &str)> {
+        let mut namespaces = vec![
+            (PG_CATALOG_NAMESPACE_OID, "pg_catalog"),
+            (INFORMATION_SCHEMA_NAMESPACE_OID, "information_schema"),
+        ];
+        if self.namespace_oid(&self.schema) == Some(self.namespace_oid) {
+            namespaces.push((self.namespace_oid, &self.schema));
+        }
+        namespaces
+    }
+
+    fn namespace_rows(&self) -> Vec<Vec<Option<String>>> {
+        self.namespaces()
+            .into_iter()
+            .map(|(oid, name)| row![oid, name, BOOTSTRAP_SUPERUSER_ID])
+            .collect()
+    }
+
+    fn class_rows(&self) -> Vec<Vec<Option<String>>> {
+        self.tables
+            .iter()
+            .map(|table| {
+                let mut row = row![
+                    table.oid,
+                    table.name,
+                    self.namespace_oid,
+                    0,
+                    0,
+                    BOOTSTRAP_SUPERUSER_ID,
+                    HEAP_TABLE_AM_OID,
+                    0,
+                    -1,
+                    0,
+                    false,
+                    'p',
+                    'r',
+                    table.columns.len(),
+                    0,
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    'd',
+                    false
+                ];
+                // `reloptions` and `relpartbound`
+                row.extend([None, None]);
+                row
+            })
+            .collect()
+    }
+
+    fn attribute_rows(&self) -> Vec<Vec<Option<String>>> {
+        let mut rows = vec![];
+        for table in self.tables.iter() {
+            for (index, column) in table.columns.iter().enumerate() {
+                let info = type_info(&column.r#type);
+                let array_dims = matches!(column.r#type.kind(), Kind::Array(_)) as i32;
+                let collation = match info.typcategory {
+                    'S' => DEFAULT_COLLATION_OID,
+                    _ => 0,
+                };
+                rows.push(row![
+                    table.oid,
+                    column.name,
+                    column.r#type.oid(),
+                    info.typlen,
+                    index + 1,
+                    array_dims,
+                    type_modifier(column),
+                    info.typbyval,
+                    info.typalign,
+                    !column.nullable,
+                    column.default_val.is_some(),
+                    "",
+                    "",
+                    false,
+                    true,
+                    0,
+                    collation
+                ]);
+            }
+        }
+        rows
+    }
+
+    fn type_rows(&self) -> Vec<Vec<Option<String>>> {
+        builtin_types()
+            .map(|(oid, name, info)| {
+                row![
+                    oid,
+                    name,
+                    PG_CATALOG_NAMESPACE_OID,
+                    BOOTSTRAP_SUPERUSER_ID,
+                    info.typlen,
+                    info.typbyval,
+                    info.typtype,
+                    info.typcategory,
+                    true,
+                    info.typdelim,
+                    0,
+                    info.typelem,
+                    info.typarray,
+                    info.typalign,
+                    false,
+                    0,
+                    -1,
+                    0,
+                    match info.typcategory {
+                        'S' => DEFAULT_COLLATION_OID,
+                        _ => 0,
+                    }
+                ]
+            })
+            .collect()
+    }
+
+    fn attrdef_rows(&self) -> Vec<Vec<Option<String>>> {
+        let mut rows = vec![];
+        let mut oid = FIRST_NORMAL_OBJECT_ID + 1 + self.tables.len() as Oid;
+        for table in self.tables.iter() {
+            for (index, column) in table.columns.iter().enumerate() {
+                if let Some(default_val) = column.default_val.as_ref() {
+                    rows.push(row![oid, table.oid, index + 1, default_val]);
+                    oid += 1;
+                }
+            }
+        }
+        rows
+    }
+
+    fn schemata_rows(&self) -> Vec<Vec<Option<String>>> {
+        self.namespaces()
+            .into_iter()
+            .map(|(_, name)| row![self.database, name, BOOTSTRAP_SUPERUSER_NAME])
+            .collect()
+    }
+
+    fn tables_rows(&self) -> Vec<Vec<Option<String>>> {
+        self.tables
+            .iter()
+            .map(|table| {
+                row![
+                    self.database,
+                    self.schema,
+                    table.name,
+                    "BASE TABLE",
+                    "YES",
+                    "NO"
+                ]
+            })
+            .collect()
+    }
+
+    fn columns_rows(&self) -> Vec<Vec<Option<String>>> {
+        let mut rows = vec![];
+        for table in self.tables.iter() {
+            for (index, column) in table.columns.iter().enumerate() {
+                let column_type: PgColumnType = column.convert();
+                let info = InformationSchemaType::new(&column_type);
+                let mut row = row![
+                    self.database,
+                    self.schema,
+                    table.name,
+                    column.name,
+                    index + 1
+                ];
+                row.push(column.default_val.clone());
+                row.extend(row![
+                    if column.nullable { "YES" } else { "NO" },
+                    info.data_type
+                ]);
+                row.extend(
+                    [
+                        info.character_maximum_length,
+                        info.character_octet_length,
+                        info.numeric_precision,
+                        info.numeric_precision_radix,
+                        info.numeric_scale,
+                        info.datetime_precision,
+                    ]
+                    .into_iter()
+                    .map(|x| x.map(|x| x.to_string())),
+                );
+                row.extend(row![
+                    self.database,
+                    "pg_catalog",
+                    column.r#type.name(),
+                    "YES"
+                ]);
+                rows.push(row);
+            }
+        }
+        rows
+    }
+}
+
+fn to_query_result<'a>(
+    columns: impl Iterator<Item = (&'a str, &'a PgType)>,
+    indexes: impl Iterator<Item = usize> + Clone,
+    rows: Vec<Vec<Option<String>>>,
+) -> PgQueryResult {
+    let columns: Vec<PgColumn> = columns
+        .map(|(name, pg_type)| PgColumn {
+            name: name.to_string(),
+            pg_type: pg_type.clone(),
+            oid: pg_type.oid(),
+            nullable: true,
+        })
+        .collect();
+    let data = rows
+        .into_iter()
+        .map(|row| {
+            indexes
+                .clone()
+                .zip(columns.iter())
+                .map(|(index, column)| PgColumnItem {
+                    data: row[index].as_deref().map(|x| to_value(&column.pg_type, x)),
+                })
+                .collect()
+        })
+        .collect();
+    PgQueryResult { columns, data }
+}
+
+fn to_value(pg_type: &PgType, value: &str) -> PgValueInput {
+    match *pg_type {
+        PgType::OID => PgValueInput::Oid(value.parse().unwrap_or_default()),
+        PgType::INT2 => PgValueInput::Int2(value.parse().unwrap_or_default()),
+        PgType::INT4 => PgValueInput::Int4(value.parse().unwrap_or_default()),
+        PgType::INT8 => PgValueInput::Int8(value.parse().unwrap_or_default()),
+        PgType::FLOAT4 => PgValueInput::Float4(value.parse().unwrap_or_default()),
+        PgType::FLOAT8 => PgValueInput::Float8(value.parse().unwrap_or_default()),
+        PgType::BOOL => PgValueInput::Bool(value == "true"),
+        PgType::CHAR => PgValueInput::Char(value.bytes().next().unwrap_or_default() as i8),
+        PgType::NAME => PgValueInput::Name(value.to_string()),
+        PgType::VARCHAR => PgValueInput::Varchar(value.to_string()),
+        _ => PgValueInput::Text(value.to_string()),
+    }
+}
+
+/// `atttypmod` of column, -1 means no type modifier
+fn type_modifier(column: &PgTableItem) -> i32 {
+    // `VARHDRSZ` is added to length of character types and numeric
+    const VARHDRSZ: i32 = 4;
+    let column_type: PgColumnType = column.convert();
+    let modifier = match column_type.base {
+        PgType::VARCHAR | PgType::BPCHAR => column_type.length.map(|x| x as i32 + VARHDRSZ),
+        PgType::BIT | PgType::VARBIT => column_type.length.map(|x| x as i32),
+        PgType::NUMERIC => column_type
+            .precision
+            .map(|x| ((x as i32) << 16 | column_type.scale.unwrap_or(0) as i32) + VARHDRSZ),
+        PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+            column_type.fractional_seconds.map(|x| x as i32)
+        }
+        _ => None,
+    };
+    modifier.unwrap_or(-1)
+}
+
+/// Type columns of `information_schema.columns`
+#[derive(Debug, Default)]
+struct InformationSchemaType {
+    data_type: String,
+    character_maximum_length: Option<usize>,
+    character_octet_length: Option<usize>,
+    numeric_precision: Option<usize>,
+    numeric_precision_radix: Option<usize>,
+    numeric_scale: Option<usize>,
+    datetime_precision: Option<usize>,
+}
+
+impl InformationSchemaType {
+    fn new(column_type: &PgColumnType) -> Self {
+        // max bytes of one character in UTF8 encoding
+        const MAX_ENCODING_LENGTH: usize = 4;
+        const MAX_OCTET_LENGTH: usize = 1073741824;
+        const DEFAULT_FRACTIONAL_SECONDS: usize = 6;
+
+        let integer = |bits: usize| Self {
+            numeric_precision: Some(bits),
+            numeric_precision_radix: Some(2),
+            numeric_scale: Some(0),
+            ..Self::default()
+        };
+        let float = |bits: usize| Self {
+            numeric_precision: Some(bits),
+            numeric_precision_radix: Some(2),
+            ..Self::default()
+        };
+        let character = |length: Option<usize>| Self {
+            character_maximum_length: length,
+            character_octet_length: Some(
+                length.map_or(MAX_OCTET_LENGTH, |x| x * MAX_ENCODING_LENGTH),
+            ),
+            ..Self::default()
+        };
+        let datetime = |precision: Option<usize>| Self {
+            datetime_precision: Some(precision.unwrap_or(DEFAULT_FRACTIONAL_SECONDS)),
+            ..Self::default()
+        };
+
+        let info = match column_type.base {
+            PgType::INT2 => integer(16),
+            PgType::INT4 => integer(32),
+            PgType::INT8 => integer(64),
+            PgType::FLOAT4 => float(24),
+            PgType::FLOAT8 => float(53),
+            PgType::NUMERIC => Self {
+                numeric_precision: column_type.precision,
+                numeric_precision_radix: Some(10),
+                numeric_scale: column_type.scale.or(column_type.precision.map(|_| 0)),
+                ..Self::default()
+            },
+            PgType::VARCHAR => character(column_type.length),
+            PgType::BPCHAR => character(column_type.length.or(Some(1))),
+            PgType::TEXT => character(None),
+            PgType::DATE => datetime(Some(0)),
+            PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+                datetime(column_type.fractional_seconds)
+            }
+            PgType::INTERVAL => datetime(None),
+            _ => Self::default(),
+        };
+        let base = &column_type.base;
+        let data_type = match standard_type_name(base) {
+            Some(x) => x,
+            None if matches!(base.kind(), Kind::Array(_)) => "ARRAY",
+            None if base.oid() >= FIRST_NORMAL_OBJECT_ID => "USER-DEFINED",
+            None => base.name(),
+        };
+        Self {
+            data_type: data_type.to_string(),
+            ..info
+        }
+    }
+}
+
+/// SQL standard name of type, used by `format_type` and `information_schema.columns`
+fn standard_type_name(pg_type: &PgType) -> Option<&'static str> {
+    let name = match *pg_type {
+        PgType::BOOL => "boolean",
+        PgType::INT2 => "smallint",
+        PgType::INT4 => "integer",
+        PgType::INT8 => "bigint",
+        PgType::FLOAT4 => "real",
+        PgType::FLOAT8 => "double precision",
+        PgType::NUMERIC => "numeric",
+        PgType::VARCHAR => "character varying",
+        PgType::BPCHAR => "character",
+        PgType::TEXT => "text",
+        PgType::DATE => "date",
+        PgType::TIME => "time without time zone",
+        PgType::TIMETZ => "time with time zone",
+        PgType::TIMESTAMP => "timestamp without time zone",
+        PgType::TIMESTAMPTZ => "timestamp with time zone",
+        PgType::INTERVAL => "interval",
+        PgType::BIT => "bit",
+        PgType::VARBIT => "bit varying",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> PgCatalog {
+        let column = |name: &str, col_index, r#type, length, scale, nullable| PgTableItem {
+            name: name.to_string(),
+            table_id: 1155,
+            col_index,
+            r#type,
+            length,
+            scale,
+            nullable,
+            default_val: None,
+            table_name: "T1".to_string(),
+            create_time: "".to_string(),
+        };
+        let mut desc = PgTableDesc::default();
+        desc.data.insert(
+            "T1".to_string(),
+            vec![
+                column("NAME", 1, PgType::VARCHAR, 100, 0, true),
+                PgTableItem {
+                    default_val: Some("nextval('SEQ_T1')".to_string()),
+                    ..column("ID", 0, PgType::INT8, 8, 0, false)
+                },
+                column("PRICE", 2, PgType::NUMERIC, 10, 2, true),
+                column("CREATED", 3, PgType::TIMESTAMP, 8, 3, true),
+            ],
+        );
+        desc.data.insert(
+            "T0".to_string(),
+            vec![column("C1", 0, PgType::TEXT, 0, 0, true)],
+        );
+        PgCatalog::new("DAMENG", "SYSDBA", &desc, false)
+    }
+
+    fn query(catalog: &PgCatalog, sql: &str) -> Vec<Vec<Option<PgValueInput>>> {
+        catalog
+            .query(sql)
+            .unwrap()
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|row| row.into_iter().map(|x| x.data).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_pg_catalog_query() {
+        use PgValueInput::*;
+        let catalog = catalog();
+
+        assert!(catalog.query("SELECT * FROM T1").unwrap().is_none());
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT c.oid, c.relname AS name FROM pg_catalog.pg_class c \
+                 WHERE c.relnamespace = 'sysdba'::regnamespace AND c.relkind IN ('r', 'p') \
+                 ORDER BY name DESC"
+            ),
+            vec![
+                vec![Some(Oid(16386)), Some(Name("t1".into()))],
+                vec![Some(Oid(16385)), Some(Name("t0".into()))],
+            ]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                "select attname, atttypid, atttypmod, attnotnull, atthasdef from pg_attribute \
+                 where attrelid = 'sysdba.t1'::regclass and attnum > 0 order by attnum"
+            ),
+            vec![
+                vec![
+                    Some(Name("id".into())),
+                    Some(Oid(20)),
+                    Some(Int4(-1)),
+                    Some(Bool(true)),
+                    Some(Bool(true))
+                ],
+                vec![
+                    Some(Name("name".into())),
+                    Some(Oid(1043)),
+                    Some(Int4(104)),
+                    Some(Bool(false)),
+                    Some(Bool(false))
+                ],
+                vec![
+                    Some(Name("price".into())),
+                    Some(Oid(1700)),
+                    Some(Int4((10 << 16 | 2) + 4)),
+                    Some(Bool(false)),
+                    Some(Bool(false))
+                ],
+                vec![
+                    Some(Name("created".into())),
+                    Some(Oid(1114)),
+                    Some(Int4(3)),
+                    Some(Bool(false)),
+                    Some(Bool(false))
+                ],
+            ]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT column_name, data_type, character_maximum_length, numeric_precision, \
+                 numeric_scale, column_default FROM information_schema.columns \
+                 WHERE table_schema = 'sysdba' AND table_name LIKE 't_' \
+                 AND is_nullable = 'NO'"
+            ),
+            vec![vec![
+                Some(Name("id".into())),
+                Some(Varchar("bigint".into())),
+                None,
+                Some(Int4(64)),
+                Some(Int4(0)),
+                Some(Varchar("nextval('SEQ_T1')".into())),
+            ]]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT typname, typlen FROM pg_type WHERE oid = 'int4'::regtype LIMIT 1"
+            ),
+            vec![vec![Some(Name("int4".into())), Some(Int2(4))]]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT nspname FROM pg_namespace WHERE nspname NOT LIKE 'pg\\_%' ORDER BY 1"
+            ),
+            vec![
+                vec![Some(Name("information_schema".into()))],
+                vec![Some(Name("sysdba".into()))],
+            ]
+        );
+
+        assert_eq!(
+            catalog
+                .relation(CatalogRelation::Tables)
+                .data
+                .into_iter()
+                .map(|mut x| x.remove(2).data)
+                .collect::<Vec<_>>(),
+            vec![Some(Name("t0".into())), Some(Name("t1".into()))]
+        );
+
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT n.nspname, c.relname FROM pg_catalog.pg_class c \
+                 LEFT JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+                 WHERE (c.relkind = 'r' OR c.relkind = 'v') AND c.relname ~~ 't%' \
+                 AND pg_catalog.pg_table_is_visible(c.oid) ORDER BY 1, 2"
+            ),
+            vec![
+                vec![Some(Name("sysdba".into())), Some(Name("t0".into()))],
+                vec![Some(Name("sysdba".into())), Some(Name("t1".into()))],
+            ]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT DISTINCT format_type(atttypid, NULL) AS t FROM pg_attribute \
+                 WHERE attrelid = 't0'::regclass OR atttypid = 23 ORDER BY t"
+            ),
+            vec![vec![Some(Text("text".into()))]]
+        );
+
+        for sql in [
+            "SELECT foo FROM pg_class",
+            "SELECT oid FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace",
+            "SELECT * FROM pg_class WHERE oid = 'nope'::regclass",
+            "SELECT relkind, relname FROM pg_class GROUP BY relkind, relname",
+            "SELECT relname FROM pg_class WHERE oid = (SELECT oid FROM pg_class)",
+            "SELECT count(*) FROM pg_class",
+            "SELECT relname FROM pg_class WHERE relname ~ '('",
+            "SELECT relname FROM pg_class WHERE relname OPERATOR(public.~) 't'",
+            "DELETE FROM pg_class",
+        ] {
+            assert!(catalog.query(sql).is_err(), "{}", sql);
+        }
+    }
+
+    /// Queries of psql 15 `\dt` and `\d t1` captured by `psql -E`
+    #[test]
+    fn test_pg_catalog_psql_query() {
+        use PgValueInput::*;
+        let catalog = catalog();
+
+        assert_eq!(
+            query(
+                &catalog,
+                r#"SELECT n.nspname as "Schema",
+  c.relname as "Name",
+  CASE c.relkind WHEN 'r' THEN 'table' WHEN 'v' THEN 'view' WHEN 'm' THEN 'materialized view' WHEN 'i' THEN 'index' WHEN 'S' THEN 'sequence' WHEN 't' THEN 'TOAST table' WHEN 'f' THEN 'foreign table' WHEN 'p' THEN 'partitioned table' WHEN 'I' THEN 'partitioned index' END as "Type",
+  pg_catalog.pg_get_userbyid(c.relowner) as "Owner"
+FROM pg_catalog.pg_class c
+     LEFT JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+     LEFT JOIN pg_catalog.pg_am am ON am.oid = c.relam
+WHERE c.relkind IN ('r','p','')
+      AND n.nspname <> 'pg_catalog'
+      AND n.nspname !~ '^pg_toast'
+      AND n.nspname <> 'information_schema'
+  AND pg_catalog.pg_table_is_visible(c.oid)
+ORDER BY 1,2;"#
+            ),
+            vec![
+                vec![
+                    Some(Name("sysdba".into())),
+                    Some(Name("t0".into())),
+                    Some(Text("table".into())),
+                    Some(Name("postgres".into()))
+                ],
+                vec![
+                    Some(Name("sysdba".into())),
+                    Some(Name("t1".into())),
+                    Some(Text("table".into())),
+                    Some(Name("postgres".into()))
+                ],
+            ]
+        );
+
+        assert_eq!(
+            query(
+                &catalog,
+                r#"SELECT c.oid,
+  n.nspname,
+  c.relname
+FROM pg_catalog.pg_class c
+     LEFT JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relname OPERATOR(pg_catalog.~) '^(t1)$' COLLATE pg_catalog.default
+  AND pg_catalog.pg_table_is_visible(c.oid)
+ORDER BY 2, 3;"#
+            ),
+            vec![vec![
+                Some(Oid(16386)),
+                Some(Name("sysdba".into())),
+                Some(Name("t1".into()))
+            ]]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                r#"SELECT c.relchecks, c.relkind, c.relhasindex, c.relhasrules, c.relhastriggers, c.relrowsecurity, c.relforcerowsecurity, false AS relhasoids, c.relispartition, '', c.reltablespace, CASE WHEN c.reloftype = 0 THEN '' ELSE c.reloftype::pg_catalog.regtype::pg_catalog.text END, c.relpersistence, c.relreplident, am.amname
+FROM pg_catalog.pg_class c
+ LEFT JOIN pg_catalog.pg_class tc ON (c.reltoastrelid = tc.oid)
+LEFT JOIN pg_catalog.pg_am am ON (c.relam = am.oid)
+WHERE c.oid = '16386';"#
+            ),
+            vec![vec![
+                Some(Int2(0)),
+                Some(Char(b'r' as i8)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Bool(false)),
+                Some(Text("".into())),
+                Some(Oid(0)),
+                Some(Text("".into())),
+                Some(Char(b'p' as i8)),
+                Some(Char(b'd' as i8)),
+                Some(Name("heap".into())),
+            ]]
+        );
+        assert_eq!(
+            query(
+                &catalog,
+                r#"SELECT a.attname,
+  pg_catalog.format_type(a.atttypid, a.atttypmod),
+  (SELECT pg_catalog.pg_get_expr(d.adbin, d.adrelid, true)
+   FROM pg_catalog.pg_attrdef d
+   WHERE d.adrelid = a.attrelid AND d.adnum = a.attnum AND a.atthasdef),
+  a.attnotnull,
+  (SELECT c.collname FROM pg_catalog.pg_collation c, pg_catalog.pg_type t
+   WHERE c.oid = a.attcollation AND t.oid = a.atttypid AND a.attcollation <> t.typcollation) AS attcollation,
+  a.attidentity,
+  a.attgenerated
+FROM pg_catalog.pg_attribute a
+WHERE a.attrelid = '16386' AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY a.attnum;"#
+            ),
+            [
+                ("id", "bigint", Some("nextval('SEQ_T1')"), true),
+                ("name", "character varying(100)", None, false),
+                ("price", "numeric(10,2)", None, false),
+                ("created", "timestamp(3) without time zone", None, false),
+            ]
+            .into_iter()
+            .map(|(name, data_type, default, not_null)| vec![
+                Some(Name(name.into())),
+                Some(Text(data_type.into())),
+                default.map(|x| Text(x.into())),
+                Some(Bool(not_null)),
+                None,
+                Some(Char(0)),
+                Some(Char(0)),
+            ])
+            .collect::<Vec<_>>()
+        );
+
+        // Policies, extended statistics, publications and inheritance are not emulated
+        for sql in [
+            r#"SELECT pol.polname, pol.polpermissive,
+  CASE WHEN pol.polroles = '{0}' THEN NULL ELSE pg_catalog.array_to_string(array(select rolname from pg_catalog.pg_roles where oid = any (pol.polroles) order by 1),',') END,
+  pg_catalog.pg_get_expr(pol.polqual, pol.polrelid),
+  pg_catalog.pg_get_expr(pol.polwithcheck, pol.polrelid),
+  CASE pol.polcmd
+    WHEN 'r' THEN 'SELECT'
+    WHEN 'a' THEN 'INSERT'
+    WHEN 'w' THEN 'UPDATE'
+    WHEN 'd' THEN 'DELETE'
+    END AS cmd
+FROM pg_catalog.pg_policy pol
+WHERE pol.polrelid = '16386' ORDER BY 1;"#,
+            r#"SELECT oid, stxrelid::pg_catalog.regclass, stxnamespace::pg_catalog.regnamespace::pg_catalog.text AS nsp, stxname,
+pg_catalog.pg_get_statisticsobjdef_columns(oid) AS columns,
+  'd' = any(stxkind) AS ndist_enabled,
+  'f' = any(stxkind) AS deps_enabled,
+  'm' = any(stxkind) AS mcv_enabled,
+stxstattarget
+FROM pg_catalog.pg_statistic_ext
+WHERE stxrelid = '16386'
+ORDER BY nsp, stxname;"#,
+            r#"SELECT pubname
+     , NULL
+     , NULL
+FROM pg_catalog.pg_publication p
+     JOIN pg_catalog.pg_publication_namespace pn ON p.oid = pn.pnpubid
+     JOIN pg_catalog.pg_class pc ON pc.relnamespace = pn.pnnspid
+WHERE pc.oid ='16386' and pg_catalog.pg_relation_is_publishable('16386')
+UNION
+SELECT pubname
+     , pg_get_expr(pr.prqual, c.oid)
+     , (CASE WHEN pr.prattrs IS NOT NULL THEN
+         (SELECT string_agg(attname, ', ')
+           FROM pg_catalog.generate_series(0, pg_catalog.array_upper(pr.prattrs::pg_catalog.int2[], 1)) s,
+                pg_catalog.pg_attribute
+          WHERE attrelid = pr.prrelid AND attnum = prattrs[s])
+        ELSE NULL END) FROM pg_catalog.pg_publication p
+     JOIN pg_catalog.pg_publication_rel pr ON p.oid = pr.prpubid
+     JOIN pg_catalog.pg_class c ON c.oid = pr.prrelid
+WHERE pr.prrelid = '16386'
+UNION
+SELECT pubname
+     , NULL
+     , NULL
+FROM pg_catalog.pg_publication p
+WHERE p.puballtables AND pg_catalog.pg_relation_is_publishable('16386')
+ORDER BY 1;"#,
+            r#"SELECT c.oid::pg_catalog.regclass
+FROM pg_catalog.pg_class c, pg_catalog.pg_inherits i
+WHERE c.oid = i.inhparent AND i.inhrelid = '16386'
+  AND c.relkind != 'p' AND c.relkind != 'I'
+ORDER BY inhseqno;"#,
+            r#"SELECT c.oid::pg_catalog.regclass, c.relkind, inhdetachpending, pg_catalog.pg_get_expr(c.relpartbound, c.oid)
+FROM pg_catalog.pg_class c, pg_catalog.pg_inherits i
+WHERE c.oid = i.inhrelid AND i.inhparent = '16386'
+ORDER BY pg_catalog.pg_get_expr(c.relpartbound, c.oid) = 'DEFAULT', c.oid::pg_catalog.regclass::pg_catalog.text;"#,
+        ] {
+            assert_eq!(query(&catalog, sql), Vec::<Vec<_>>::new(), "{}", sql);
+        }
+    }
+
+    /// DBeaver schema, table and column queries, bound parameters inlined
+    #[test]
+    fn test_pg_catalog_dbeaver_query() {
+        use PgValueInput::*;
+        let catalog = catalog();
+
+        let result = catalog
+            .query(
+                "SELECT n.oid,n.*,d.description FROM pg_catalog.pg_namespace n
+LEFT OUTER JOIN pg_catalog.pg_description d ON d.objoid=n.oid AND d.objsubid=0 AND d.classoid='pg_namespace'::regclass
+ ORDER BY nspname",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result
+                .columns
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["oid", "oid", "nspname", "nspowner", "description"]
+        );
+        assert_eq!(
+            result
+                .data
+                .into_iter()
+                .map(|mut x| (x.remove(2).data, x.remove(3).data))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(Name("information_schema".into())), None),
+                (Some(Name("pg_catalog".into())), None),
+                (Some(Name("sysdba".into())), None),
+            ]
+        );
+
+        let result = catalog
+            .query(
+                "SELECT c.oid,c.*,d.description,pg_catalog.pg_get_expr(c.relpartbound, c.oid) as partition_expr,  pg_catalog.pg_get_partkeydef(c.oid) as partition_key 
+FROM pg_catalog.pg_class c
+LEFT OUTER JOIN pg_catalog.pg_description d ON d.objoid=c.oid AND d.objsubid=0 AND d.classoid='pg_class'::regclass
+WHERE c.relnamespace=16384 AND c.relkind not in ('i','I','c')",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result
+                .columns
+                .iter()
+                .rev()
+                .take(3)
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["partition_key", "partition_expr", "description"]
+        );
+        assert_eq!(
+            result
+                .data
+                .into_iter()
+                .map(|mut x| x.remove(2).data)
+                .collect::<Vec<_>>(),
+            vec![Some(Name("t0".into())), Some(Name("t1".into()))]
+        );
+
+        assert_eq!(
+            query(
+                &catalog,
+                "SELECT c.relname,a.*,pg_catalog.pg_get_expr(ad.adbin, ad.adrelid, true) as def_value,dsc.description,dep.objid
+FROM pg_catalog.pg_attribute a
+INNER JOIN pg_catalog.pg_class c ON (a.attrelid=c.oid)
+LEFT OUTER JOIN pg_catalog.pg_attrdef ad ON (a.attrelid=ad.adrelid AND a.attnum = ad.adnum)
+LEFT OUTER JOIN pg_catalog.pg_description dsc ON (c.oid=dsc.objoid AND a.attnum = dsc.objsubid)
+LEFT OUTER JOIN pg_depend dep on dep.refobjid = a.attrelid AND dep.deptype = 'i' and dep.refobjsubid = a.attnum and dep.classid = dep.refclassid
+WHERE NOT a.attisdropped AND c.relkind not in ('i','I','c') AND c.oid=16386
+ORDER BY a.attnum"
+            )
+            .into_iter()
+            .map(|mut x| {
+                let objid = x.pop().unwrap();
+                let description = x.pop().unwrap();
+                let def_value = x.pop().unwrap();
+                (x.remove(2), def_value, description, objid)
+            })
+            .collect::<Vec<_>>(),
+            vec![
+                (
+                    Some(Name("id".into())),
+                    Some(Text("nextval('SEQ_T1')".into())),
+                    None,
+                    None
+                ),
+                (Some(Name("name".into())), None, None, None),
+                (Some(Name("price".into())), None, None, None),
+                (Some(Name("created".into())), None, None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1404 @@
+use super::{
+    standard_type_name, to_query_result, CatalogRelation, PgCatalog, BOOTSTRAP_SUPERUSER_ID,
+    BOOTSTRAP_SUPERUSER_NAME,
+};
+use crate::error::OdbcHelperError;
+use crate::extension::pg::PgQueryResult;
+use pg_helper::type_info;
+use pg_helper::PgType;
+use postgres_types::{Kind, Oid};
+use regex::RegexBuilder;
+use sqlparser::ast::{
+    visit_relations, Array, BinaryOperator, Distinct, Expr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, GroupByExpr, Ident, JoinConstraint, JoinOperator, ObjectName, OrderByExpr,
+    Query, Select, SelectItem, SetExpr, SetOperator, SetQuantifier, Statement, TableFactor,
+    TableWithJoins, UnaryOperator, Value,
+};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+/// Whether statement references catalog relations, `pg_catalog` relations could be unqualified.
+pub(super) fn references_catalog(statement: &Statement) -> bool {
+    visit_relations(statement, |name| {
+        if CatalogRelation::from_str(&object_name(name)).is_ok() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_break()
+}
+
+/// Evaluate query of catalog relations, `regclass`, `regtype` and `regnamespace` output is the
+/// object name and literal output is text, the same as postgres.
+pub(super) fn evaluate(
+    catalog: &PgCatalog,
+    query: &Query,
+) -> Result<PgQueryResult, OdbcHelperError> {
+    let output = QueryEvaluator::new(catalog, None).query(query)?;
+    let types: Vec<PgType> = output
+        .types
+        .into_iter()
+        .map(|x| match x {
+            PgType::UNKNOWN => PgType::TEXT,
+            x => x,
+        })
+        .collect();
+    let rows = output
+        .rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .zip(types.iter())
+                .map(|(value, pg_type)| match *pg_type {
+                    PgType::REGCLASS | PgType::REGTYPE | PgType::REGNAMESPACE => {
+                        value.map(|x| match x.parse() {
+                            Ok(oid) => catalog.reg_name(pg_type, oid),
+                            Err(_) => x,
+                        })
+                    }
+                    _ => value,
+                })
+                .collect()
+        })
+        .collect();
+    Ok(to_query_result(
+        output.labels.iter().map(String::as_str).zip(types.iter()),
+        0..types.len(),
+        rows,
+    ))
+}
+
+/// Identifier, unquoted identifier is folded to lowercase
+fn ident_name(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+fn object_name(name: &ObjectName) -> String {
+    name.0.iter().map(ident_name).collect::<Vec<_>>().join(".")
+}
+
+/// Output column name of expression without alias, the same as postgres
+fn label(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(ident) => ident_name(ident),
+        Expr::CompoundIdentifier(idents) => idents.last().map(ident_name).unwrap_or_default(),
+        Expr::Function(function) => function.name.0.last().map(ident_name).unwrap_or_default(),
+        Expr::Cast { expr, .. } | Expr::Nested(expr) | Expr::Collate { expr, .. } => label(expr),
+        Expr::Case { .. } => "case".to_string(),
+        Expr::Exists { .. } => "exists".to_string(),
+        Expr::Array(_) => "array".to_string(),
+        // Scalar subquery is named by its output column
+        Expr::Subquery(query) => match query.body.as_ref() {
+            SetExpr::Select(select) => match select.projection.first() {
+                Some(SelectItem::UnnamedExpr(expr)) => label(expr),
+                Some(SelectItem::ExprWithAlias { alias, .. }) => ident_name(alias),
+                _ => "?column?".to_string(),
+            },
+            _ => "?column?".to_string(),
+        },
+        _ => "?column?".to_string(),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" | "on" | "1" => Some(true),
+        "f" | "false" | "n" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Three-valued `AND`, `None` is NULL
+fn and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// Three-valued `OR`, `None` is NULL
+fn or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// Value of catalog query expression, text representation as catalog rows. `None` is NULL.
+#[derive(Debug, Clone)]
+struct Datum {
+    pg_type: PgType,
+    value: Option<String>,
+}
+
+impl Datum {
+    fn new<T: ToString>(pg_type: PgType, value: Option<T>) -> Self {
+        Self {
+            pg_type,
+            value: value.map(|x| x.to_string()),
+        }
+    }
+
+    /// String literal and NULL are unknown type until compared or cast
+    fn unknown(value: Option<String>) -> Self {
+        Self::new(PgType::UNKNOWN, value)
+    }
+
+    fn bool(value: Option<bool>) -> Self {
+        Self::new(PgType::BOOL, value)
+    }
+
+    fn as_bool(&self) -> Result<Option<bool>, OdbcHelperError> {
+        match &self.value {
+            None => Ok(None),
+            Some(x) if matches!(self.pg_type, PgType::BOOL | PgType::UNKNOWN) => parse_bool(x)
+                .map(Some)
+                .ok_or_else(|| sql_error!("invalid input syntax for type boolean:{}", x)),
+            Some(_) => Err(sql_error!(
+                "argument of type {} is not boolean",
+                self.pg_type.name()
+            )),
+        }
+    }
+
+    fn parse<T: FromStr>(&self) -> Result<Option<T>, OdbcHelperError> {
+        self.value
+            .as_deref()
+            .map(|x| {
+                x.trim()
+                    .parse()
+                    .map_err(|_| sql_error!("invalid input syntax for integer:{}", x))
+            })
+            .transpose()
+    }
+
+    /// Element type and elements of array or vector value, NULL array has no elements
+    fn elements(&self) -> Result<(PgType, Vec<Option<String>>), OdbcHelperError> {
+        let (element, vector) = match (self.pg_type.clone(), self.pg_type.kind()) {
+            (PgType::INT2_VECTOR, _) => (PgType::INT2, true),
+            (PgType::OID_VECTOR, _) => (PgType::OID, true),
+            (PgType::UNKNOWN, _) => (PgType::UNKNOWN, false),
+            (_, Kind::Array(element)) => (element.clone(), false),
+            (x, _) => return Err(sql_error!("{} is not an array", x.name())),
+        };
+        let elements = match self.value.as_deref() {
+            None => vec![],
+            Some(value) if vector => value
+                .split_whitespace()
+                .map(|x| Some(x.to_string()))
+                .collect(),
+            Some(value) => parse_array(value)?,
+        };
+        Ok((element, elements))
+    }
+}
+
+/// Elements of array text representation, E.g: `{a,"b c",NULL}`. Multidimensional array is not
+/// supported.
+fn parse_array(value: &str) -> Result<Vec<Option<String>>, OdbcHelperError> {
+    let invalid = || sql_error!("malformed array literal:{}", value);
+    let inner = value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|x| x.strip_suffix('}'))
+        .ok_or_else(invalid)?;
+    if inner.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut elements = vec![];
+    let mut chars = inner.chars().peekable();
+    loop {
+        while chars.next_if(|x| x.is_whitespace()).is_some() {}
+        let element = if chars.next_if_eq(&'"').is_some() {
+            let mut element = String::new();
+            loop {
+                match chars.next().ok_or_else(invalid)? {
+                    '"' => break,
+                    '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                    x => element.push(x),
+                }
+            }
+            while chars.next_if(|x| x.is_whitespace()).is_some() {}
+            Some(element)
+        } else {
+            let mut element = String::new();
+            while let Some(x) = chars.next_if(|x| *x != ',') {
+                match x {
+                    '{' | '}' | '"' => return Err(invalid()),
+                    '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                    x => element.push(x),
+                }
+            }
+            let element = element.trim_end();
+            match element {
+                "" => return Err(invalid()),
+                x if x.eq_ignore_ascii_case("null") => None,
+                x => Some(x.to_string()),
+            }
+        };
+        elements.push(element);
+        match chars.next() {
+            None => return Ok(elements),
+            Some(',') => {}
+            Some(_) => return Err(invalid()),
+        }
+    }
+}
+
+/// Array text representation of elements, element is quoted when it is empty, `NULL` or contains
+/// delimiter, braces, quote, backslash or whitespace.
+fn format_array(elements: &[Option<String>]) -> String {
+    let elements: Vec<String> = elements
+        .iter()
+        .map(|element| match element {
+            None => "NULL".to_string(),
+            Some(x)
+                if x.is_empty()
+                    || x.eq_ignore_ascii_case("null")
+                    || x.contains(|c: char| "{},\"\\".contains(c) || c.is_whitespace()) =>
+            {
+                format!("\"{}\"", x.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            Some(x) => x.to_string(),
+        })
+        .collect();
+    format!("{{{}}}", elements.join(","))
+}
+
+/// Array type of element type, `text[]` if array type is not exist
+fn array_of(element: &PgType) -> PgType {
+    match *element {
+        PgType::UNKNOWN => PgType::TEXT_ARRAY,
+        _ => PgType::from_oid(type_info(element).typarray).unwrap_or(PgType::TEXT_ARRAY),
+    }
+}
+
+/// Compare by the type of the side which is not a literal, `None` if either is NULL or value is
+/// invalid for type
+fn compare_datum(a: &Datum, b: &Datum) -> Option<Ordering> {
+    let pg_type = match a.pg_type {
+        PgType::UNKNOWN => &b.pg_type,
+        _ => &a.pg_type,
+    };
+    compare(pg_type, a.value.as_deref()?, b.value.as_deref()?)
+}
+
+/// Ordering of sort key, NULL is larger than any value by default, the same as postgres
+fn sort_ordering(order_by: &OrderByExpr, a: &Datum, b: &Datum) -> Ordering {
+    let asc = order_by.asc.unwrap_or(true);
+    let nulls_first = order_by.nulls_first.unwrap_or(!asc);
+    match (&a.value, &b.value) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) if nulls_first => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) if nulls_first => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        _ => {
+            let ordering = compare_datum(a, b).unwrap_or(Ordering::Equal);
+            if asc {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        }
+    }
+}
+
+/// Compare catalog value with literal by column type, `None` if literal is invalid for type
+fn compare(pg_type: &PgType, value: &str, literal: &str) -> Option<Ordering> {
+    match *pg_type {
+        PgType::OID
+        | PgType::REGCLASS
+        | PgType::REGTYPE
+        | PgType::REGNAMESPACE
+        | PgType::INT2
+        | PgType::INT4
+        | PgType::INT8
+        | PgType::FLOAT4
+        | PgType::FLOAT8
+        | PgType::NUMERIC => {
+            let value: f64 = value.trim().parse().ok()?;
+            let literal: f64 = literal.trim().parse().ok()?;
+            value.partial_cmp(&literal)
+        }
+        PgType::BOOL => Some(parse_bool(value)?.cmp(&parse_bool(literal)?)),
+        _ => Some(value.cmp(literal)),
+    }
+}
+
+/// SQL `LIKE` pattern match, `%` matches any characters, `_` matches one character and `escape`
+/// escapes the next character.
+fn like(value: &str, pattern: &str, escape: Option<char>) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    fn matches(value: &[char], pattern: &[char], escape: Option<char>) -> bool {
+        match pattern.split_first() {
+            None => value.is_empty(),
+            Some((c, rest)) if Some(*c) == escape && !rest.is_empty() => {
+                value.first() == Some(&rest[0]) && matches(&value[1..], &rest[1..], escape)
+            }
+            Some(('%', rest)) => (0..=value.len()).any(|i| matches(&value[i..], rest, escape)),
+            Some(('_', rest)) => !value.is_empty() && matches(&value[1..], rest, escape),
+            Some((c, rest)) => value.first() == Some(c) && matches(&value[1..], rest, escape),
+        }
+    }
+    matches(&value, &pattern, escape)
+}
+
+fn like_datum(
+    value: &Datum,
+    pattern: &Datum,
+    escape: Option<&str>,
+    ignore_case: bool,
+    negated: bool,
+) -> Result<Datum, OdbcHelperError> {
+    let escape = match escape {
+        None => Some('\\'),
+        Some("") => None,
+        Some(x) if x.chars().count() == 1 => x.chars().next(),
+        Some(x) => return Err(sql_error!("invalid escape string:{}", x)),
+    };
+    let matched = match (value.value.as_deref(), pattern.value.as_deref()) {
+        (Some(value), Some(pattern)) if ignore_case => {
+            Some(like(&value.to_lowercase(), &pattern.to_lowercase(), escape))
+        }
+        (Some(value), Some(pattern)) => Some(like(value, pattern, escape)),
+        _ => None,
+    };
+    Ok(Datum::bool(matched.map(|x| x != negated)))
+}
+
+/// POSIX regular expression match `~`, `~*`, `!~` and `!~*`
+fn regex_datum(
+    value: &Datum,
+    pattern: &Datum,
+    ignore_case: bool,
+    negated: bool,
+) -> Result<Datum, OdbcHelperError> {
+    let matched = match (value.value.as_deref(), pattern.value.as_deref()) {
+        (Some(value), Some(pattern)) => {
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(ignore_case)
+                .build()
+                .map_err(|e| sql_error!("invalid regular expression:{}, error:{}", pattern, e))?;
+            Some(regex.is_match(value))
+        }
+        _ => None,
+    };
+    Ok(Datum::bool(matched.map(|x| x != negated)))
+}
+
+/// Operator of `OPERATOR(pg_catalog.~)` syntax which psql uses, only `pg_catalog` operators
+/// are known
+fn custom_operator(parts: &[String]) -> Result<BinaryOperator, OdbcHelperError> {
+    let op = match parts {
+        [op] => op,
+        [schema, op] if schema.eq_ignore_ascii_case("pg_catalog") => op,
+        _ => return Err(sql_error!("unsupported operator {}", parts.join("."))),
+    };
+    let op = match op.as_str() {
+        "=" => BinaryOperator::Eq,
+        "<>" | "!=" => BinaryOperator::NotEq,
+        "<" => BinaryOperator::Lt,
+        "<=" => BinaryOperator::LtEq,
+        ">" => BinaryOperator::Gt,
+        ">=" => BinaryOperator::GtEq,
+        "~" => BinaryOperator::PGRegexMatch,
+        "~*" => BinaryOperator::PGRegexIMatch,
+        "!~" => BinaryOperator::PGRegexNotMatch,
+        "!~*" => BinaryOperator::PGRegexNotIMatch,
+        "~~" => BinaryOperator::PGLikeMatch,
+        "~~*" => BinaryOperator::PGILikeMatch,
+        "!~~" => BinaryOperator::PGNotLikeMatch,
+        "!~~*" => BinaryOperator::PGNotILikeMatch,
+        "||" => BinaryOperator::StringConcat,
+        x => return Err(sql_error!("unsupported operator {}", x)),
+    };
+    Ok(op)
+}
+
+/// Binary operator except `AND` and `OR`, which are evaluated lazily
+fn binary(op: &BinaryOperator, left: &Datum, right: &Datum) -> Result<Datum, OdbcHelperError> {
+    let compared = compare_datum(left, right);
+    let datum = match op {
+        BinaryOperator::Eq => Datum::bool(compared.map(|x| x.is_eq())),
+        BinaryOperator::NotEq => Datum::bool(compared.map(|x| x.is_ne())),
+        BinaryOperator::Lt => Datum::bool(compared.map(|x| x.is_lt())),
+        BinaryOperator::LtEq => Datum::bool(compared.map(|x| x.is_le())),
+        BinaryOperator::Gt => Datum::bool(compared.map(|x| x.is_gt())),
+        BinaryOperator::GtEq => Datum::bool(compared.map(|x| x.is_ge())),
+        BinaryOperator::PGLikeMatch => like_datum(left, right, None, false, false)?,
+        BinaryOperator::PGILikeMatch => like_datum(left, right, None, true, false)?,
+        BinaryOperator::PGNotLikeMatch => like_datum(left, right, None, false, true)?,
+        BinaryOperator::PGNotILikeMatch => like_datum(left, right, None, true, true)?,
+        BinaryOperator::PGRegexMatch => regex_datum(left, right, false, false)?,
+        BinaryOperator::PGRegexIMatch => regex_datum(left, right, true, false)?,
+        BinaryOperator::PGRegexNotMatch => regex_datum(left, right, false, true)?,
+        BinaryOperator::PGRegexNotIMatch => regex_datum(left, right, true, true)?,
+        BinaryOperator::StringConcat => Datum::new(
+            PgType::TEXT,
+            left.value
+                .clone()
+                .zip(right.value.clone())
+                .map(|(a, b)| a + &b),
+        ),
+        BinaryOperator::PGCustomBinaryOperator(parts) => {
+            binary(&custom_operator(parts)?, left, right)?
+        }
+        x => return Err(sql_error!("unsupported operator {}", x)),
+    };
+    Ok(datum)
+}
+
+fn literal(value: &Value) -> Result<Datum, OdbcHelperError> {
+    let datum = match value {
+        Value::Number(x, _) => {
+            let pg_type = if x.parse::<i32>().is_ok() {
+                PgType::INT4
+            } else if x.parse::<i64>().is_ok() {
+                PgType::INT8
+            } else {
+                PgType::NUMERIC
+            };
+            Datum::new(pg_type, Some(x))
+        }
+        Value::SingleQuotedString(x) | Value::EscapedStringLiteral(x) => {
+            Datum::unknown(Some(x.to_string()))
+        }
+        Value::DollarQuotedString(x) => Datum::unknown(Some(x.value.to_string())),
+        Value::Boolean(x) => Datum::bool(Some(*x)),
+        Value::Null => Datum::unknown(None),
+        x => return Err(sql_error!("unsupported literal {}", x)),
+    };
+    Ok(datum)
+}
+
+/// `format_type(type_oid, typemod)`, SQL standard type name with modifiers, E.g:
+/// `character varying(100)`, `numeric(10,2)`, `timestamp(3) without time zone`.
+pub(super) fn format_type(oid: Oid, typmod: Option<i32>) -> String {
+    // `VARHDRSZ` is added to length of character types and numeric
+    const VARHDRSZ: i32 = 4;
+    let pg_type = match PgType::from_oid(oid) {
+        Some(x) => x,
+        None => return "???".to_string(),
+    };
+    if let Kind::Array(element) = pg_type.kind() {
+        return format!("{}[]", format_type(element.oid(), typmod));
+    }
+    let name = standard_type_name(&pg_type).unwrap_or(pg_type.name());
+    let typmod = match typmod.filter(|x| *x >= 0) {
+        Some(x) => x,
+        None => return name.to_string(),
+    };
+    match pg_type {
+        PgType::VARCHAR | PgType::BPCHAR => format!("{}({})", name, typmod - VARHDRSZ),
+        PgType::BIT | PgType::VARBIT => format!("{}({})", name, typmod),
+        PgType::NUMERIC => {
+            let typmod = typmod - VARHDRSZ;
+            format!("{}({},{})", name, typmod >> 16, typmod & 0xffff)
+        }
+        PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+            match name.split_once(' ') {
+                Some((base, zone)) => format!("{}({}) {}", base, typmod, zone),
+                None => format!("{}({})", name, typmod),
+            }
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Column of FROM clause, `qualifier` is the alias or relation name
+#[derive(Debug)]
+struct ScopeColumn {
+    qualifier: String,
+    name: &'static str,
+    pg_type: PgType,
+}
+
+type Row = Vec<Option<String>>;
+
+/// Select list item, wildcard is expanded to columns
+enum Projection<'e> {
+    Column(usize),
+    Expr(&'e Expr),
+}
+
+/// Result of query, `types` are `UNKNOWN` for NULL and string literal
+struct Output {
+    labels: Vec<String>,
+    types: Vec<PgType>,
+    rows: Vec<Row>,
+}
+
+impl Output {
+    /// Single column of subquery used as expression
+    fn column(self) -> Result<(PgType, Vec<Option<String>>), OdbcHelperError> {
+        match self.types.as_slice() {
+            [pg_type] => Ok((
+                pg_type.clone(),
+                self.rows.into_iter().map(|mut x| x.remove(0)).collect(),
+            )),
+            _ => Err(sql_error!("subquery must return only one column")),
+        }
+    }
+
+    /// `ORDER BY` of `UNION`, `INTERSECT` and `EXCEPT`, only output column position or name
+    fn sort(&mut self, order_by: &[OrderByExpr]) -> Result<(), OdbcHelperError> {
+        let indexes = order_by
+            .iter()
+            .map(|x| {
+                let index = match &x.expr {
+                    Expr::Value(Value::Number(x, _)) => {
+                        x.parse::<usize>().ok().and_then(|x| x.checked_sub(1))
+                    }
+                    Expr::Identifier(ident) => {
+                        let name = ident_name(ident);
+                        self.labels.iter().position(|x| *x == name)
+                    }
+                    _ => None,
+                };
+                index.filter(|x| *x < self.labels.len()).ok_or_else(|| {
+                    sql_error!("invalid UNION/INTERSECT/EXCEPT ORDER BY clause {}", x)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let types = &self.types;
+        self.rows.sort_by(|a, b| {
+            order_by
+                .iter()
+                .zip(indexes.iter())
+                .map(|(order_by, index)| {
+                    let datum = |row: &Row| Datum::new(types[*index].clone(), row[*index].as_ref());
+                    sort_ordering(order_by, &datum(a), &datum(b))
+                })
+                .find(|x| x.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(())
+    }
+}
+
+/// Row of the enclosing query, referenced by correlated subquery
+#[derive(Clone, Copy)]
+struct Outer<'a> {
+    scope: &'a QueryEvaluator<'a>,
+    row: &'a Row,
+}
+
+/// Evaluate query of catalog relations in memory, relations of FROM clause are joined by nested
+/// loop. `columns` are the columns of joined relations so far, column which is not found is
+/// looked up in the `outer` queries.
+struct QueryEvaluator<'a> {
+    catalog: &'a PgCatalog,
+    columns: Vec<ScopeColumn>,
+    outer: Option<Outer<'a>>,
+}
+
+impl<'a> QueryEvaluator<'a> {
+    fn new(catalog: &'a PgCatalog, outer: Option<Outer<'a>>) -> Self {
+        Self {
+            catalog,
+            columns: vec![],
+            outer,
+        }
+    }
+
+    fn query(&self, query: &Query) -> Result<Output, OdbcHelperError> {
+        if query.with.is_some()
+            || query.fetch.is_some()
+            || !query.limit_by.is_empty()
+            || !query.locks.is_empty()
+        {
+            return Err(sql_error!(
+                "WITH, FETCH, LIMIT BY and locking are not supported"
+            ));
+        }
+        let order_by = query.order_by.as_ref().map_or(&[][..], |x| &x.exprs[..]);
+        let mut output = match query.body.as_ref() {
+            SetExpr::Select(select) => {
+                Self::new(self.catalog, self.outer).select(select, order_by)?
+            }
+            body => {
+                let mut output = self.set_expr(body)?;
+                output.sort(order_by)?;
+                output
+            }
+        };
+
+        let offset = match &query.offset {
+            Some(offset) => self.eval(&offset.value, &vec![])?.parse()?,
+            None => None,
+        };
+        let limit = match &query.limit {
+            Some(limit) => self.eval(limit, &vec![])?.parse()?,
+            None => None,
+        };
+        output.rows = output
+            .rows
+            .into_iter()
+            .skip(offset.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(output)
+    }
+
+    /// Operand of `UNION`, `INTERSECT` and `EXCEPT`
+    fn set_expr(&self, body: &SetExpr) -> Result<Output, OdbcHelperError> {
+        match body {
+            SetExpr::Select(select) => Self::new(self.catalog, self.outer).select(select, &[]),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation {
+                op,
+                set_quantifier,
+                left,
+                right,
+            } => {
+                let all = match set_quantifier {
+                    SetQuantifier::All => true,
+                    SetQuantifier::Distinct | SetQuantifier::None => false,
+                    x => return Err(sql_error!("unsupported set quantifier {}", x)),
+                };
+                let (mut left, right) = (self.set_expr(left)?, self.set_expr(right)?);
+                if left.types.len() != right.types.len() {
+                    return Err(sql_error!(
+                        "each {} query must have the same number of columns",
+                        op
+                    ));
+                }
+                for (left, right) in left.types.iter_mut().zip(right.types) {
+                    if *left == PgType::UNKNOWN {
+                        *left = right;
+                    }
+                }
+                let rows = match op {
+                    SetOperator::Union => left.rows.into_iter().chain(right.rows).collect(),
+                    SetOperator::Intersect | SetOperator::Except => {
+                        let intersect = *op == SetOperator::Intersect;
+                        let mut right = right.rows;
+                        let mut rows = vec![];
+                        for row in left.rows {
+                            let found = right.iter().position(|x| *x == row);
+                            if let (Some(index), true) = (found, all) {
+                                right.remove(index);
+                            }
+                            if found.is_some() == intersect {
+                                rows.push(row);
+                            }
+                        }
+                        rows
+                    }
+                };
+                left.rows = rows;
+                if !all {
+                    let mut seen = HashSet::new();
+                    left.rows.retain(|x| seen.insert(x.clone()));
+                }
+                Ok(left)
+            }
+            x => Err(sql_error!("unsupported query {}", x)),
+        }
+    }
+
+    fn select(
+        mut self,
+        select: &Select,
+        order_by: &[OrderByExpr],
+    ) -> Result<Output, OdbcHelperError> {
+        let group_by = !matches!(&select.group_by, GroupByExpr::Expressions(x, _) if x.is_empty());
+        if group_by
+            || select.having.is_some()
+            || select.top.is_some()
+            || select.into.is_some()
+            || matches!(select.distinct, Some(Distinct::On(_)))
+        {
+            return Err(sql_error!(
+                "GROUP BY, HAVING, TOP, INTO and DISTINCT ON are not supported"
+            ));
+        }
+
+        let mut rows = self.from(&select.from)?;
+        if let Some(selection) = &select.selection {
+            let mut filtered = vec![];
+            for row in rows {
+                if self.eval(selection, &row)?.as_bool()? == Some(true) {
+                    filtered.push(row);
+                }
+            }
+            rows = filtered;
+        }
+
+        let projection = self.projection(&select.projection)?;
+        // Output types are the types of expressions evaluated with NULL columns
+        let null_row: Row = vec![None; self.columns.len()];
+        let types = projection
+            .iter()
+            .map(|(_, x)| self.output(x, &null_row).map(|x| x.pg_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut results = vec![];
+        for row in rows.iter() {
+            let outputs = projection
+                .iter()
+                .map(|(_, x)| self.output(x, row))
+                .collect::<Result<Vec<_>, _>>()?;
+            let keys = order_by
+                .iter()
+                .map(|x| self.sort_key(&x.expr, &projection, &outputs, row))
+                .collect::<Result<Vec<_>, _>>()?;
+            results.push((outputs, keys));
+        }
+        results.sort_by(|(_, a), (_, b)| {
+            order_by
+                .iter()
+                .zip(a.iter().zip(b.iter()))
+                .map(|(order_by, (a, b))| sort_ordering(order_by, a, b))
+                .find(|x| x.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut rows: Vec<Row> = results
+            .into_iter()
+            .map(|(outputs, _)| outputs.into_iter().map(|x| x.value).collect())
+            .collect();
+        if select.distinct.is_some() {
+            let mut seen = HashSet::new();
+            rows.retain(|x| seen.insert(x.clone()));
+        }
+        Ok(Output {
+            labels: projection.into_iter().map(|(label, _)| label).collect(),
+            types,
+            rows,
+        })
+    }
+
+    fn from(&mut self, from: &[TableWithJoins]) -> Result<Vec<Row>, OdbcHelperError> {
+        // One empty row is the identity of cross join
+        let mut rows = vec![vec![]];
+        for table in from {
+            rows = self.join(rows, &table.relation, None)?;
+            for join in table.joins.iter() {
+                rows = self.join(rows, &join.relation, Some(&join.join_operator))?;
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Join relation to rows, cross join if `operator` is `None`
+    fn join(
+        &mut self,
+        rows: Vec<Row>,
+        relation: &TableFactor,
+        operator: Option<&JoinOperator>,
+    ) -> Result<Vec<Row>, OdbcHelperError> {
+        let (relation, qualifier) = match relation {
+            TableFactor::Table {
+                name,
+                alias,
+                args: None,
+                ..
+            } => {
+                let relation = CatalogRelation::from_str(&object_name(name))?;
+                let qualifier = match alias {
+                    Some(alias) if alias.columns.is_empty() => ident_name(&alias.name),
+                    Some(alias) => return Err(sql_error!("unsupported column alias {}", alias)),
+                    None => relation.name().to_string(),
+                };
+                (relation, qualifier)
+            }
+            x => return Err(sql_error!("unsupported FROM item {}", x)),
+        };
+        if self.columns.iter().any(|x| x.qualifier == qualifier) {
+            return Err(sql_error!(
+                "table name {} specified more than once",
+                qualifier
+            ));
+        }
+        let (constraint, left_outer) = match operator {
+            None | Some(JoinOperator::CrossJoin) => (&JoinConstraint::None, false),
+            Some(JoinOperator::Inner(constraint)) => (constraint, false),
+            Some(JoinOperator::LeftOuter(constraint)) => (constraint, true),
+            Some(x) => return Err(sql_error!("unsupported join {:?}", x)),
+        };
+        let condition = match constraint {
+            JoinConstraint::None => None,
+            JoinConstraint::On(expr) => Some(expr),
+            x => return Err(sql_error!("unsupported join constraint {:?}", x)),
+        };
+
+        let columns = relation.columns();
+        let width = columns.len();
+        self.columns
+            .extend(columns.into_iter().map(|(name, pg_type)| ScopeColumn {
+                qualifier: qualifier.clone(),
+                name,
+                pg_type,
+            }));
+        let relation_rows = self.catalog.rows(relation);
+        let mut joined = vec![];
+        for left in rows {
+            let mut matched = false;
+            for right in relation_rows.iter() {
+                let row: Row = left.iter().chain(right.iter()).cloned().collect();
+                if let Some(condition) = condition {
+                    if self.eval(condition, &row)?.as_bool()? != Some(true) {
+                        continue;
+                    }
+                }
+                matched = true;
+                joined.push(row);
+            }
+            if left_outer && !matched {
+                joined.push(left.into_iter().chain(vec![None; width]).collect());
+            }
+        }
+        Ok(joined)
+    }
+
+    fn projection<'e>(
+        &self,
+        items: &'e [SelectItem],
+    ) -> Result<Vec<(String, Projection<'e>)>, OdbcHelperError> {
+        let mut projection = vec![];
+        for item in items {
+            match item {
+                SelectItem::Wildcard(_) => projection.extend(
+                    self.columns
+                        .iter()
+                        .enumerate()
+                        .map(|(index, x)| (x.name.to_string(), Projection::Column(index))),
+                ),
+                SelectItem::QualifiedWildcard(name, _) => {
+                    let qualifier = name.0.last().map(ident_name).unwrap_or_default();
+                    let columns: Vec<_> = self
+                        .columns
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, x)| x.qualifier == qualifier)
+                        .map(|(index, x)| (x.name.to_string(), Projection::Column(index)))
+                        .collect();
+                    if columns.is_empty() {
+                        return Err(sql_error!(
+                            "missing FROM-clause entry for table {}",
+                            qualifier
+                        ));
+                    }
+                    projection.extend(columns);
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    projection.push((label(expr), Projection::Expr(expr)))
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    projection.push((ident_name(alias), Projection::Expr(expr)))
+                }
+            }
+        }
+        Ok(projection)
+    }
+
+    fn output(&self, projection: &Projection, row: &Row) -> Result<Datum, OdbcHelperError> {
+        match projection {
+            Projection::Column(index) => Ok(Datum::new(
+                self.columns[*index].pg_type.clone(),
+                row[*index].as_ref(),
+            )),
+            Projection::Expr(expr) => self.eval(expr, row),
+        }
+    }
+
+    /// `ORDER BY` output column position, output column name or expression of input columns
+    fn sort_key(
+        &self,
+        expr: &Expr,
+        projection: &[(String, Projection)],
+        outputs: &[Datum],
+        row: &Row,
+    ) -> Result<Datum, OdbcHelperError> {
+        match expr {
+            Expr::Value(Value::Number(x, _)) => x
+                .parse::<usize>()
+                .ok()
+                .and_then(|x| outputs.get(x.wrapping_sub(1)))
+                .cloned()
+                .ok_or_else(|| sql_error!("ORDER BY position {} is not in select list", x)),
+            Expr::Identifier(ident) => {
+                let name = ident_name(ident);
+                match projection.iter().position(|(label, _)| *label == name) {
+                    Some(index) => Ok(outputs[index].clone()),
+                    None => self.eval(expr, row),
+                }
+            }
+            _ => self.eval(expr, row),
+        }
+    }
+
+    /// Column of this query or the outer queries, `None` if not found
+    fn lookup(
+        &self,
+        qualifier: Option<&str>,
+        name: &str,
+        row: &Row,
+    ) -> Result<Option<Datum>, OdbcHelperError> {
+        let mut found = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.name == name && qualifier.is_none_or(|q| x.qualifier == q))
+            .map(|(index, _)| index);
+        match (found.next(), found.next()) {
+            (Some(index), None) => Ok(Some(Datum::new(
+                self.columns[index].pg_type.clone(),
+                row[index].as_ref(),
+            ))),
+            (Some(_), Some(_)) => Err(sql_error!("column reference {} is ambiguous", name)),
+            (None, _) => match self.outer {
+                Some(outer) => outer.scope.lookup(qualifier, name, outer.row),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn has_qualifier(&self, qualifier: &str) -> bool {
+        self.columns.iter().any(|x| x.qualifier == qualifier)
+            || self.outer.is_some_and(|x| x.scope.has_qualifier(qualifier))
+    }
+
+    fn column(
+        &self,
+        qualifier: Option<&str>,
+        name: &str,
+        row: &Row,
+    ) -> Result<Datum, OdbcHelperError> {
+        match self.lookup(qualifier, name, row)? {
+            Some(datum) => Ok(datum),
+            None => match qualifier {
+                Some(q) if !self.has_qualifier(q) => {
+                    Err(sql_error!("missing FROM-clause entry for table {}", q))
+                }
+                _ => Err(sql_error!("column {} does not exist", name)),
+            },
+        }
+    }
+
+    /// Evaluate subquery, columns of `row` could be referenced by the subquery
+    fn subquery(&self, query: &Query, row: &Row) -> Result<Output, OdbcHelperError> {
+        QueryEvaluator::new(self.catalog, Some(Outer { scope: self, row })).query(query)
+    }
+
+    fn eval(&self, expr: &Expr, row: &Row) -> Result<Datum, OdbcHelperError> {
+        let datum = match expr {
+            Expr::Identifier(ident) => self.column(None, &ident_name(ident), row)?,
+            // `schema.relation.column` is resolved by relation name
+            Expr::CompoundIdentifier(idents) if idents.len() >= 2 => self.column(
+                Some(&ident_name(&idents[idents.len() - 2])),
+                &ident_name(&idents[idents.len() - 1]),
+                row,
+            )?,
+            Expr::Value(value) => literal(value)?,
+            Expr::Nested(expr) => self.eval(expr, row)?,
+            // Only the default collation is emulated
+            Expr::Collate { expr, .. } => self.eval(expr, row)?,
+            Expr::Cast {
+                expr,
+                data_type,
+                format: None,
+                ..
+            } => self.cast(self.eval(expr, row)?, &data_type.to_string())?,
+            Expr::UnaryOp { op, expr } => {
+                let datum = self.eval(expr, row)?;
+                match op {
+                    UnaryOperator::Not => Datum::bool(datum.as_bool()?.map(|x| !x)),
+                    UnaryOperator::Plus => datum,
+                    UnaryOperator::Minus => Datum {
+                        value: datum.value.map(|x| match x.strip_prefix('-') {
+                            Some(x) => x.to_string(),
+                            None => format!("-{}", x),
+                        }),
+                        pg_type: datum.pg_type,
+                    },
+                    x => return Err(sql_error!("unsupported operator {}", x)),
+                }
+            }
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => match self.eval(left, row)?.as_bool()? {
+                Some(false) => Datum::bool(Some(false)),
+                left => Datum::bool(and(left, self.eval(right, row)?.as_bool()?)),
+            },
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Or,
+                right,
+            } => match self.eval(left, row)?.as_bool()? {
+                Some(true) => Datum::bool(Some(true)),
+                left => Datum::bool(or(left, self.eval(right, row)?.as_bool()?)),
+            },
+            Expr::BinaryOp { left, op, right } => {
+                binary(op, &self.eval(left, row)?, &self.eval(right, row)?)?
+            }
+            Expr::IsNull(expr) => Datum::bool(Some(self.eval(expr, row)?.value.is_none())),
+            Expr::IsNotNull(expr) => Datum::bool(Some(self.eval(expr, row)?.value.is_some())),
+            Expr::IsTrue(expr) => Datum::bool(Some(self.eval(expr, row)?.as_bool()? == Some(true))),
+            Expr::IsNotTrue(expr) => {
+                Datum::bool(Some(self.eval(expr, row)?.as_bool()? != Some(true)))
+            }
+            Expr::IsFalse(expr) => {
+                Datum::bool(Some(self.eval(expr, row)?.as_bool()? == Some(false)))
+            }
+            Expr::IsNotFalse(expr) => {
+                Datum::bool(Some(self.eval(expr, row)?.as_bool()? != Some(false)))
+            }
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let value = self.eval(expr, row)?;
+                let list = list
+                    .iter()
+                    .map(|x| self.eval(x, row))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let found = self.any(&BinaryOperator::Eq, &value, list)?;
+                Datum::bool(found.map(|x| x != *negated))
+            }
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                let value = self.eval(expr, row)?;
+                let (pg_type, values) = self.subquery(subquery, row)?.column()?;
+                let list = values.into_iter().map(|x| Datum::new(pg_type.clone(), x));
+                let found = self.any(&BinaryOperator::Eq, &value, list)?;
+                Datum::bool(found.map(|x| x != *negated))
+            }
+            Expr::AnyOp {
+                left,
+                compare_op,
+                right,
+                ..
+            } => {
+                let value = self.eval(left, row)?;
+                let list = self.list(right, row)?;
+                Datum::bool(self.any(compare_op, &value, list)?)
+            }
+            // False if any comparison is false, otherwise NULL if any comparison is NULL
+            Expr::AllOp {
+                left,
+                compare_op,
+                right,
+            } => {
+                let value = self.eval(left, row)?;
+                let mut result = Some(true);
+                for item in self.list(right, row)? {
+                    match binary(compare_op, &value, &item)?.as_bool()? {
+                        Some(false) => {
+                            result = Some(false);
+                            break;
+                        }
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                Datum::bool(result)
+            }
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let value = self.eval(expr, row)?;
+                let low = compare_datum(&value, &self.eval(low, row)?).map(|x| x.is_ge());
+                let high = compare_datum(&value, &self.eval(high, row)?).map(|x| x.is_le());
+                Datum::bool(and(low, high).map(|x| x != *negated))
+            }
+            Expr::Like {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char,
+            } => like_datum(
+                &self.eval(expr, row)?,
+                &self.eval(pattern, row)?,
+                escape_char.as_deref(),
+                false,
+                *negated,
+            )?,
+            Expr::ILike {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char,
+            } => like_datum(
+                &self.eval(expr, row)?,
+                &self.eval(pattern, row)?,
+                escape_char.as_deref(),
+                true,
+                *negated,
+            )?,
+            // Only the matched branch is evaluated, the same as postgres
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let operand = operand.as_ref().map(|x| self.eval(x, row)).transpose()?;
+                let mut matched = None;
+                for (condition, result) in conditions.iter().zip(results.iter()) {
+                    let condition = self.eval(condition, row)?;
+                    let is_match = match &operand {
+                        Some(operand) => {
+                            compare_datum(operand, &condition) == Some(Ordering::Equal)
+                        }
+                        None => condition.as_bool()? == Some(true),
+                    };
+                    if is_match {
+                        matched = Some(result);
+                        break;
+                    }
+                }
+                let datum = match matched.or(else_result.as_deref()) {
+                    Some(result) => self.eval(result, row)?,
+                    None => Datum::unknown(None),
+                };
+                // Results of string literals are text
+                match datum.pg_type {
+                    PgType::UNKNOWN => Datum::new(PgType::TEXT, datum.value),
+                    _ => datum,
+                }
+            }
+            Expr::Subquery(query) => {
+                let (pg_type, mut values) = self.subquery(query, row)?.column()?;
+                if values.len() > 1 {
+                    return Err(sql_error!(
+                        "more than one row returned by a subquery used as an expression"
+                    ));
+                }
+                Datum::new(pg_type, values.pop().flatten())
+            }
+            Expr::Exists { subquery, negated } => {
+                let exists = !self.subquery(subquery, row)?.rows.is_empty();
+                Datum::bool(Some(exists != *negated))
+            }
+            Expr::Array(Array { elem, .. }) => {
+                let elements = elem
+                    .iter()
+                    .map(|x| self.eval(x, row))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let element = elements
+                    .iter()
+                    .map(|x| x.pg_type.clone())
+                    .find(|x| *x != PgType::UNKNOWN)
+                    .unwrap_or(PgType::TEXT);
+                let values: Vec<_> = elements.into_iter().map(|x| x.value).collect();
+                Datum::new(array_of(&element), Some(format_array(&values)))
+            }
+            Expr::Function(function) => self.function(function, row)?,
+            x => return Err(sql_error!("unsupported expression {}", x)),
+        };
+        Ok(datum)
+    }
+
+    /// Right side of `ANY` and `ALL`, subquery or array
+    fn list(&self, expr: &Expr, row: &Row) -> Result<Vec<Datum>, OdbcHelperError> {
+        let (pg_type, values) = match expr {
+            Expr::Subquery(query) => self.subquery(query, row)?.column()?,
+            Expr::Nested(expr) => return self.list(expr, row),
+            expr => self.eval(expr, row)?.elements()?,
+        };
+        Ok(values
+            .into_iter()
+            .map(|x| Datum::new(pg_type.clone(), x))
+            .collect())
+    }
+
+    /// `value op ANY (list)`, NULL if no item matches and any comparison is NULL
+    fn any(
+        &self,
+        op: &BinaryOperator,
+        value: &Datum,
+        list: impl IntoIterator<Item = Datum>,
+    ) -> Result<Option<bool>, OdbcHelperError> {
+        let mut result = Some(false);
+        for item in list {
+            match binary(op, value, &item)?.as_bool()? {
+                Some(true) => return Ok(Some(true)),
+                None => result = None,
+                Some(false) => {}
+            }
+        }
+        Ok(result)
+    }
+
+    /// Cast value to type, object name cast to `regclass`, `regnamespace` and `regtype` is
+    /// resolved to oid, and oid of these types cast to text is the object name
+    fn cast(&self, datum: Datum, data_type: &str) -> Result<Datum, OdbcHelperError> {
+        let data_type = data_type.to_lowercase();
+        let data_type = data_type.strip_prefix("pg_catalog.").unwrap_or(&data_type);
+        let pg_type = match data_type {
+            "oid" => PgType::OID,
+            "regclass" => PgType::REGCLASS,
+            "regnamespace" => PgType::REGNAMESPACE,
+            "regtype" => PgType::REGTYPE,
+            x => match x.strip_suffix("[]") {
+                Some(element) => array_of(
+                    &pg_helper::type_from_name(element.trim_start_matches("pg_catalog."))
+                        .ok_or_else(|| sql_error!("unsupported cast to {}", x))?,
+                ),
+                None => pg_helper::type_from_name(x)
+                    .ok_or_else(|| sql_error!("unsupported cast to {}", x))?,
+            },
+        };
+        let value = match datum.value {
+            None => None,
+            Some(value) => match (datum.pg_type.clone(), pg_type.clone()) {
+                (
+                    PgType::REGCLASS | PgType::REGTYPE | PgType::REGNAMESPACE,
+                    PgType::TEXT | PgType::VARCHAR | PgType::NAME | PgType::BPCHAR,
+                ) => match value.parse() {
+                    Ok(oid) => Some(self.catalog.reg_name(&datum.pg_type, oid)),
+                    Err(_) => Some(value),
+                },
+                (_, PgType::OID | PgType::REGCLASS | PgType::REGTYPE | PgType::REGNAMESPACE)
+                    if value.trim().parse::<Oid>().is_ok() =>
+                {
+                    Some(value.trim().to_string())
+                }
+                (_, PgType::REGCLASS) => {
+                    let oid = self
+                        .catalog
+                        .class_oid(&value)
+                        .ok_or_else(|| sql_error!("relation {} does not exist", value))?;
+                    Some(oid.to_string())
+                }
+                (_, PgType::REGNAMESPACE) => {
+                    let oid = self
+                        .catalog
+                        .namespace_oid(&value)
+                        .ok_or_else(|| sql_error!("schema {} does not exist", value))?;
+                    Some(oid.to_string())
+                }
+                (_, PgType::REGTYPE) => {
+                    let pg_type = pg_helper::type_from_name(&value)
+                        .ok_or_else(|| sql_error!("type {} does not exist", value))?;
+                    Some(pg_type.oid().to_string())
+                }
+                (_, PgType::OID) => {
+                    return Err(sql_error!("invalid input syntax for type oid:{}", value))
+                }
+                (_, PgType::BOOL) => {
+                    let value = parse_bool(&value).ok_or_else(|| {
+                        sql_error!("invalid input syntax for type boolean:{}", value)
+                    })?;
+                    Some(value.to_string())
+                }
+                _ => Some(value),
+            },
+        };
+        Ok(Datum { pg_type, value })
+    }
+
+    fn function(&self, function: &Function, row: &Row) -> Result<Datum, OdbcHelperError> {
+        let name = function.name.0.last().map(ident_name).unwrap_or_default();
+        let args = match &function.args {
+            _ if function.filter.is_some() || function.over.is_some() => {
+                return Err(sql_error!("unsupported function {}", function))
+            }
+            // `ARRAY(SELECT ..)`
+            FunctionArguments::Subquery(query) if name == "array" => {
+                let (element, values) = self.subquery(query, row)?.column()?;
+                return Ok(Datum::new(array_of(&element), Some(format_array(&values))));
+            }
+            FunctionArguments::None => vec![],
+            FunctionArguments::List(list)
+                if list.duplicate_treatment.is_none() && list.clauses.is_empty() =>
+            {
+                list.args
+                    .iter()
+                    .map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => self.eval(expr, row),
+                        x => Err(sql_error!("unsupported function argument {}", x)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            _ => return Err(sql_error!("unsupported function {}", function)),
+        };
+
+        let datum = match (name.as_str(), args.as_slice()) {
+            // All emulated tables are in the search path
+            ("pg_table_is_visible", [oid]) => Datum::bool(
+                oid.parse::<Oid>()?
+                    .map(|oid| self.catalog.tables.iter().any(|x| x.oid == oid)),
+            ),
+            // Tables are publishable, system catalogs are not
+            ("pg_relation_is_publishable", [oid]) => Datum::bool(
+                oid.parse::<Oid>()?
+                    .map(|oid| self.catalog.tables.iter().any(|x| x.oid == oid)),
+            ),
+            ("format_type", [oid, typmod]) => {
+                let typmod = typmod.parse::<i32>()?;
+                Datum::new(
+                    PgType::TEXT,
+                    oid.parse::<Oid>()?.map(|oid| format_type(oid, typmod)),
+                )
+            }
+            // Default value is kept as expression text in `pg_attrdef.adbin`
+            ("pg_get_expr", [expr, _] | [expr, _, _]) => {
+                Datum::new(PgType::TEXT, expr.value.as_ref())
+            }
+            ("pg_get_userbyid", [oid]) => Datum::new(
+                PgType::NAME,
+                oid.parse::<Oid>()?.map(|oid| match oid {
+                    BOOTSTRAP_SUPERUSER_ID => BOOTSTRAP_SUPERUSER_NAME.to_string(),
+                    oid => format!("unknown (OID={})", oid),
+                }),
+            ),
+            // Comments, partitions and extended statistics are not emulated
+            ("obj_description" | "shobj_description", [_] | [_, _])
+            | ("col_description", [_, _])
+            | ("pg_get_partkeydef", [_])
+            | ("pg_get_statisticsobjdef_columns", [_]) => Datum::new(PgType::TEXT, None::<String>),
+            ("array_to_string", [array, delimiter] | [array, delimiter, _]) => {
+                let null = args.get(2).and_then(|x| x.value.as_deref());
+                let value = match (&array.value, &delimiter.value) {
+                    (Some(_), Some(delimiter)) => Some(
+                        array
+                            .elements()?
+                            .1
+                            .into_iter()
+                            .filter_map(|x| x.or_else(|| null.map(str::to_string)))
+                            .collect::<Vec<_>>()
+                            .join(delimiter),
+                    ),
+                    _ => None,
+                };
+                Datum::new(PgType::TEXT, value)
+            }
+            ("current_schema", []) => Datum::new(PgType::NAME, Some(&self.catalog.schema)),
+            ("current_database", []) => Datum::new(PgType::NAME, Some(&self.catalog.database)),
+            _ => {
+                return Err(sql_error!(
+                    "function {} with {} arguments is not supported",
+                    name,
+                    args.len()
+                ))
+            }
+        };
+        Ok(datum)
+    }
+}
@@ -0,0 +1,465 @@
+//! Translate postgres sql to dameng sql.
+//!
+//! The sql is parsed with postgres dialect of `sqlparser`, the syntax tree is rewritten and then
+//! rendered back to sql:
+//! - `LIMIT n OFFSET m` to `OFFSET m ROWS FETCH FIRST n ROWS ONLY`
+//! - `expr::type` cast to `CAST(expr AS type)`, postgres only types are mapped to dameng types
+//! - `ILIKE` and `~~*` to `UPPER(expr) LIKE UPPER(pattern)`, `~~` to `LIKE`
+//! - `now()` to `CURRENT_TIMESTAMP`
+//! - `string_agg(expr, delimiter ORDER BY ..)` to `LISTAGG(expr, delimiter) WITHIN GROUP (ORDER BY ..)`
+//! - `E''` and dollar quoted strings to standard string literals
+//! - `$1`, `$2` .. placeholders to odbc `?`, the `$n` of each `?` is kept in
+//!   [`DamengSql::placeholders`] so repeated and reordered `$n` are bound by number
+//! - identifiers are folded the same way as [`Options::case_sensitive`](crate::executor::database::Options)
+//!
+//! Constructs without dameng equivalent, E.g: `RETURNING`, `ON CONFLICT`, `DISTINCT ON`, return error.
+use crate::error::OdbcHelperError;
+use sqlparser::ast::{
+    AssignmentTarget, BinaryOperator, CastKind, DataType, Delete, Distinct, Expr, Fetch, Function,
+    FunctionArg, FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList, FunctionArguments,
+    Ident, Insert, ObjectName, Offset, OffsetRows, OrderByExpr, Query, SetExpr, Statement,
+    TimezoneInfo, Value, VisitMut, VisitorMut,
+};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::ops::ControlFlow;
+
+/// Translate postgres sql to dameng sql.
+///
+/// `case_sensitive` false: identifiers are uppercase in dameng, quoted identifiers are converted to
+/// uppercase. true: identifiers keep original name, unquoted identifiers are lowercase as postgres
+/// does, so they are quoted.
+///
+/// ```
+/// use odbc_api_helper::bridge::pg_dialect::pg_to_dameng_sql;
+///
+/// let sql = pg_to_dameng_sql("SELECT id::text FROM t WHERE name ILIKE $1 LIMIT 10", false).unwrap();
+/// assert_eq!(
+///     sql,
+///     "SELECT CAST(id AS TEXT) FROM t WHERE UPPER(name) LIKE UPPER(?) OFFSET 0 ROWS FETCH FIRST 10 ROWS ONLY"
+/// );
+/// ```
+pub fn pg_to_dameng_sql(sql: &str, case_sensitive: bool) -> Result<String, OdbcHelperError> {
+    pg_to_dameng_statement(sql, case_sensitive).map(|x| x.sql)
+}
+
+/// Postgres sql translated to dameng dialect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamengSql {
+    pub sql: String,
+    /// The `$n` number of each odbc `?` placeholder in order, empty if sql has no `$n`.
+    /// E.g: `[1, 2, 1]` of `a = $1 AND b = $2 OR c = $1`
+    pub placeholders: Vec<usize>,
+}
+
+/// Translate postgres sql to dameng sql like [`pg_to_dameng_sql`], and keep the `$n` number of
+/// each `?` placeholder, parameters are bound by them in order.
+///
+/// ```
+/// use odbc_api_helper::bridge::pg_dialect::pg_to_dameng_statement;
+///
+/// let statement = pg_to_dameng_statement("SELECT * FROM t WHERE a = $2 OR b = $1 OR c = $2", false).unwrap();
+/// assert_eq!(statement.sql, "SELECT * FROM t WHERE a = ? OR b = ? OR c = ?");
+/// assert_eq!(statement.placeholders, vec![2, 1, 2]);
+/// ```
+pub fn pg_to_dameng_statement(
+    sql: &str,
+    case_sensitive: bool,
+) -> Result<DamengSql, OdbcHelperError> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| sql_error!("parse postgres sql error:{}, sql:{}", e, sql))?;
+
+    let mut translator = DamengTranslator { case_sensitive };
+    if let ControlFlow::Break(e) = statements.visit(&mut translator) {
+        return Err(sql_error!(
+            "translate postgres sql to dameng error:{}, sql:{}",
+            e,
+            sql
+        ));
+    }
+
+    let sql = statements
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let (sql, placeholders) = replace_placeholders(&sql)?;
+    Ok(DamengSql { sql, placeholders })
+}
+
+struct DamengTranslator {
+    case_sensitive: bool,
+}
+
+impl DamengTranslator {
+    fn fold_ident(&self, ident: &mut Ident) {
+        match ident.quote_style {
+            Some(_) if !self.case_sensitive => ident.value = ident.value.to_uppercase(),
+            None if self.case_sensitive => {
+                ident.value = ident.value.to_lowercase();
+                ident.quote_style = Some('"');
+            }
+            _ => {}
+        }
+    }
+
+    fn fold_object_name(&self, name: &mut ObjectName) {
+        name.0.iter_mut().for_each(|x| self.fold_ident(x))
+    }
+}
+
+impl VisitorMut for DamengTranslator {
+    type Break = String;
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        if let SetExpr::Select(select) = query.body.as_ref() {
+            if let Some(Distinct::On(_)) = select.distinct {
+                return unsupported("DISTINCT ON");
+            }
+        }
+        if !query.limit_by.is_empty() {
+            return unsupported("LIMIT BY");
+        }
+        if query.fetch.is_some() || query.limit.is_some() {
+            if let Some(limit) = query.limit.take() {
+                query.fetch = Some(Fetch {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(limit),
+                });
+            }
+            // dameng requires `OFFSET` before `FETCH`
+            let offset = query.offset.get_or_insert(Offset {
+                value: Expr::Value(number("0")),
+                rows: OffsetRows::Rows,
+            });
+            offset.rows = OffsetRows::Rows;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &mut ObjectName) -> ControlFlow<Self::Break> {
+        self.fold_object_name(relation);
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_statement(&mut self, statement: &mut Statement) -> ControlFlow<Self::Break> {
+        match statement {
+            Statement::Insert(Insert {
+                returning: Some(_), ..
+            })
+            | Statement::Update {
+                returning: Some(_), ..
+            }
+            | Statement::Delete(Delete {
+                returning: Some(_), ..
+            }) => return unsupported("RETURNING"),
+            Statement::Insert(Insert { on: Some(_), .. }) => return unsupported("ON CONFLICT"),
+            Statement::Insert(insert) => insert.columns.iter_mut().for_each(|x| self.fold_ident(x)),
+            Statement::Update { assignments, .. } => {
+                for assignment in assignments.iter_mut() {
+                    match &mut assignment.target {
+                        AssignmentTarget::ColumnName(name) => self.fold_object_name(name),
+                        AssignmentTarget::Tuple(names) => {
+                            names.iter_mut().for_each(|x| self.fold_object_name(x))
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<Self::Break> {
+        match expr {
+            Expr::Identifier(ident) => self.fold_ident(ident),
+            Expr::CompoundIdentifier(idents) => idents.iter_mut().for_each(|x| self.fold_ident(x)),
+            Expr::Cast {
+                kind, data_type, ..
+            } => {
+                if *kind == CastKind::DoubleColon {
+                    *kind = CastKind::Cast;
+                }
+                match dameng_data_type(data_type) {
+                    Some(x) => *data_type = x,
+                    None => return unsupported(format!("cast to {}", data_type)),
+                }
+            }
+            Expr::ILike { any: true, .. } => return unsupported("ILIKE ANY"),
+            Expr::ILike {
+                negated,
+                expr: left,
+                pattern,
+                escape_char,
+                ..
+            } => {
+                *expr = Expr::Like {
+                    negated: *negated,
+                    any: false,
+                    expr: Box::new(upper(*left.clone())),
+                    pattern: Box::new(upper(*pattern.clone())),
+                    escape_char: escape_char.take(),
+                }
+            }
+            Expr::BinaryOp { left, op, right } => {
+                let (negated, ignore_case) = match op {
+                    BinaryOperator::PGLikeMatch => (false, false),
+                    BinaryOperator::PGNotLikeMatch => (true, false),
+                    BinaryOperator::PGILikeMatch => (false, true),
+                    BinaryOperator::PGNotILikeMatch => (true, true),
+                    BinaryOperator::PGRegexMatch
+                    | BinaryOperator::PGRegexIMatch
+                    | BinaryOperator::PGRegexNotMatch
+                    | BinaryOperator::PGRegexNotIMatch
+                    | BinaryOperator::Arrow
+                    | BinaryOperator::LongArrow
+                    | BinaryOperator::HashArrow
+                    | BinaryOperator::HashLongArrow
+                    | BinaryOperator::AtArrow
+                    | BinaryOperator::ArrowAt
+                    | BinaryOperator::PGBitwiseXor
+                    | BinaryOperator::PGOverlap => return unsupported(format!("operator {}", op)),
+                    _ => return ControlFlow::Continue(()),
+                };
+                let (left, right) = if ignore_case {
+                    (upper(*left.clone()), upper(*right.clone()))
+                } else {
+                    (*left.clone(), *right.clone())
+                };
+                *expr = Expr::Like {
+                    negated,
+                    any: false,
+                    expr: Box::new(left),
+                    pattern: Box::new(right),
+                    escape_char: None,
+                }
+            }
+            Expr::Value(value) => match value {
+                Value::DollarQuotedString(s) => {
+                    *value = Value::SingleQuotedString(std::mem::take(&mut s.value))
+                }
+                Value::EscapedStringLiteral(s) => {
+                    *value = Value::SingleQuotedString(std::mem::take(s))
+                }
+                _ => {}
+            },
+            Expr::Function(function) => return translate_function(function),
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn translate_function(function: &mut Function) -> ControlFlow<String> {
+    let name = match &function.name.0[..] {
+        [ident] if ident.quote_style.is_none() => ident.value.to_lowercase(),
+        _ => return ControlFlow::Continue(()),
+    };
+    match name.as_str() {
+        "now" => {
+            if !matches!(&function.args, FunctionArguments::List(x) if x.args.is_empty()) {
+                return unsupported("now with arguments");
+            }
+            function.name = ObjectName(vec![Ident::new("CURRENT_TIMESTAMP")]);
+            function.args = FunctionArguments::None;
+        }
+        "string_agg" => {
+            let list = match &mut function.args {
+                FunctionArguments::List(list) if list.args.len() == 2 => list,
+                _ => return unsupported("string_agg without exactly 2 arguments"),
+            };
+            if list.duplicate_treatment.is_some() {
+                return unsupported("string_agg with DISTINCT");
+            }
+            if function.filter.is_some() || function.over.is_some() {
+                return unsupported("string_agg with FILTER or OVER");
+            }
+            let mut order_by: Vec<OrderByExpr> = vec![];
+            for clause in list.clauses.drain(..) {
+                match clause {
+                    FunctionArgumentClause::OrderBy(x) => order_by = x,
+                    x => return unsupported(format!("string_agg with {}", x)),
+                }
+            }
+            // dameng `LISTAGG` requires `WITHIN GROUP`
+            if order_by.is_empty() {
+                order_by.push(OrderByExpr {
+                    expr: Expr::Value(Value::Null),
+                    asc: None,
+                    nulls_first: None,
+                    with_fill: None,
+                });
+            }
+            function.name = ObjectName(vec![Ident::new("LISTAGG")]);
+            function.within_group = order_by;
+        }
+        _ => {}
+    }
+    ControlFlow::Continue(())
+}
+
+/// Map postgres only data type to dameng data type, return `None` if the type is not supported.
+fn dameng_data_type(data_type: &DataType) -> Option<DataType> {
+    let data_type = match data_type {
+        DataType::Bool | DataType::Boolean => DataType::Bit(None),
+        DataType::Int2(x) => DataType::SmallInt(*x),
+        DataType::Int4(x) => DataType::Integer(*x),
+        DataType::Int8(x) => DataType::BigInt(*x),
+        DataType::Float4 => DataType::Real,
+        DataType::Float8 => DataType::DoublePrecision,
+        DataType::Bytea => DataType::Blob(None),
+        DataType::JSON | DataType::JSONB => DataType::Clob(None),
+        DataType::Uuid => DataType::Varchar(None),
+        DataType::Timestamp(p, TimezoneInfo::Tz) => {
+            DataType::Timestamp(*p, TimezoneInfo::WithTimeZone)
+        }
+        DataType::Timestamp(p, TimezoneInfo::WithoutTimeZone) => {
+            DataType::Timestamp(*p, TimezoneInfo::None)
+        }
+        DataType::Time(p, TimezoneInfo::Tz) => DataType::Time(*p, TimezoneInfo::WithTimeZone),
+        DataType::Time(p, TimezoneInfo::WithoutTimeZone) => DataType::Time(*p, TimezoneInfo::None),
+        DataType::Text
+        | DataType::Char(_)
+        | DataType::Character(_)
+        | DataType::Varchar(_)
+        | DataType::CharacterVarying(_)
+        | DataType::CharVarying(_)
+        | DataType::SmallInt(_)
+        | DataType::Int(_)
+        | DataType::Integer(_)
+        | DataType::BigInt(_)
+        | DataType::Real
+        | DataType::Double
+        | DataType::DoublePrecision
+        | DataType::Float(_)
+        | DataType::Numeric(_)
+        | DataType::Decimal(_)
+        | DataType::Dec(_)
+        | DataType::Date
+        | DataType::Timestamp(..)
+        | DataType::Time(..)
+        | DataType::Blob(_)
+        | DataType::Clob(_)
+        | DataType::Varbinary(_)
+        | DataType::Binary(_) => data_type.clone(),
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+/// Replace postgres `$n` placeholders with odbc `?`, return the sql and the `$n` number of each `?`.
+fn replace_placeholders(sql: &str) -> Result<(String, Vec<usize>), OdbcHelperError> {
+    let mut tokens = Tokenizer::new(&PostgreSqlDialect {}, sql)
+        .with_unescape(false)
+        .tokenize()
+        .map_err(|e| sql_error!("tokenize sql error:{}, sql:{}", e, sql))?;
+    let mut placeholders = vec![];
+    let mut positional = false;
+    for token in tokens.iter_mut() {
+        if let Token::Placeholder(x) = token {
+            match x.strip_prefix('$') {
+                Some(n) => {
+                    let n = n
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| sql_error!("invalid placeholder {}, sql:{}", x, sql))?;
+                    placeholders.push(n);
+                    *x = "?".to_string();
+                }
+                None => positional = true,
+            }
+        }
+    }
+    if positional && !placeholders.is_empty() {
+        return Err(sql_error!("mixed `$n` and `?` placeholders, sql:{}", sql));
+    }
+    let sql = tokens.iter().map(|x| x.to_string()).collect();
+    Ok((sql, placeholders))
+}
+
+fn upper(expr: Expr) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![Ident::new("UPPER")]),
+        uses_odbc_syntax: false,
+        parameters: FunctionArguments::None,
+        args: FunctionArguments::List(FunctionArgumentList {
+            duplicate_treatment: None,
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))],
+            clauses: vec![],
+        }),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        within_group: vec![],
+    })
+}
+
+fn number(n: &str) -> Value {
+    Value::Number(n.to_string(), false)
+}
+
+fn unsupported<S: AsRef<str>>(construct: S) -> ControlFlow<String> {
+    ControlFlow::Break(format!(
+        "postgres `{}` is not supported by dameng",
+        construct.as_ref()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pg_to_dameng_sql() {
+        let translate = |sql: &str| pg_to_dameng_sql(sql, false).unwrap();
+        assert_eq!(
+            translate("SELECT * FROM \"user\" u WHERE u.\"Name\" NOT ILIKE 'a%' LIMIT 5 OFFSET 10"),
+            "SELECT * FROM \"USER\" AS u WHERE UPPER(u.\"NAME\") NOT LIKE UPPER('a%') OFFSET 10 ROWS FETCH FIRST 5 ROWS ONLY"
+        );
+        assert_eq!(
+            translate("select now(), '1'::int4, x::timestamptz, b ~~ 'x' from t"),
+            "SELECT CURRENT_TIMESTAMP, CAST('1' AS INTEGER), CAST(x AS TIMESTAMP WITH TIME ZONE), b LIKE 'x' FROM t"
+        );
+        assert_eq!(
+            translate("select string_agg(name, ',' order by id desc) from t group by k"),
+            "SELECT LISTAGG(name, ',') WITHIN GROUP (ORDER BY id DESC) FROM t GROUP BY k"
+        );
+        assert_eq!(
+            translate("select string_agg(name, $$;$$) from t"),
+            "SELECT LISTAGG(name, ';') WITHIN GROUP (ORDER BY NULL) FROM t"
+        );
+        assert_eq!(
+            translate("UPDATE t SET \"v\" = $1 WHERE id = $2"),
+            "UPDATE t SET \"V\" = ? WHERE id = ?"
+        );
+        assert_eq!(
+            pg_to_dameng_sql("insert into T (id, Name) values ($1, E'a\\'b')", true).unwrap(),
+            "INSERT INTO \"t\" (\"id\", \"name\") VALUES (?, 'a''b')"
+        );
+
+        let error = |sql: &str| pg_to_dameng_sql(sql, false).unwrap_err();
+        assert!(
+            format!("{}", error("insert into t values (1) returning id")).contains("RETURNING")
+        );
+        assert!(format!("{}", error("select distinct on (a) a from t")).contains("DISTINCT ON"));
+        assert!(format!("{}", error("select a::regclass from t")).contains("REGCLASS"));
+        assert!(format!("{}", error("select * from t where a = $0")).contains("$0"));
+
+        let statement = pg_to_dameng_statement(
+            "select * from t where a = $1 or b = $1 limit $3 offset $2",
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            statement.sql,
+            "SELECT * FROM t WHERE a = ? OR b = ? OFFSET ? ROWS FETCH FIRST ? ROWS ONLY"
+        );
+        assert_eq!(statement.placeholders, vec![1, 1, 2, 3]);
+        assert!(error("select from where")
+            .to_string()
+            .contains("parse postgres sql"));
+    }
+}
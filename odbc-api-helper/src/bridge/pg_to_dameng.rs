@@ -1,9 +1,15 @@
-use crate::TryConvert;
+use crate::bridge::column_type::{PgColumnType, TypeConversion};
+use crate::bridge::mapping::TypeMapping;
+use crate::error::OdbcHelperError;
+use crate::{Convert, TryConvert};
+use dameng_helper::table::DmTableItem;
 use dameng_helper::DmDateType;
+use pg_helper::table::PgTableItem;
 use pg_helper::PgType;
+use postgres_types::Kind;
 
 impl TryConvert<DmDateType> for &PgType {
-    type Error = anyhow::Error;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<DmDateType, Self::Error> {
         match *self {
@@ -11,12 +17,12 @@ impl TryConvert<DmDateType> for &PgType {
             PgType::BOOL => Ok(DmDateType::BIT),
             PgType::INT4 => Ok(DmDateType::INTEGER),
             PgType::INT8 => Ok(DmDateType::BIGINT),
-            PgType::INT2 => Ok(DmDateType::TINYINT),
-            PgType::BYTEA => Ok(DmDateType::BINARY),
+            PgType::INT2 => Ok(DmDateType::SMALLINT),
+            PgType::BYTEA => Ok(DmDateType::BLOB),
             PgType::VARBIT => Ok(DmDateType::VARBINARY),
-            PgType::FLOAT4 => Ok(DmDateType::DOUBLE),
+            PgType::FLOAT4 => Ok(DmDateType::REAL),
             PgType::FLOAT8 => Ok(DmDateType::DOUBLE_PRECISION),
-            PgType::CHAR => Ok(DmDateType::CHAR),
+            PgType::CHAR | PgType::BPCHAR => Ok(DmDateType::CHAR),
             PgType::VARCHAR => Ok(DmDateType::VARCHAR),
             PgType::TEXT => Ok(DmDateType::TEXT),
             PgType::DATE => Ok(DmDateType::DATE),
@@ -24,7 +30,173 @@ impl TryConvert<DmDateType> for &PgType {
             PgType::TIMESTAMP => Ok(DmDateType::TIMESTAMP),
             PgType::TIMETZ => Ok(DmDateType::TIME_WITH_TIME_ZONE),
             PgType::TIMESTAMPTZ => Ok(DmDateType::TIMESTAMP_WITH_TIME_ZONE),
-            _ => bail!("convert pg data_type to dameng data_type error:{}", self),
+            PgType::INTERVAL => Ok(DmDateType::INTERVAL_DAY_TO_SECOND),
+            PgType::JSON | PgType::JSONB => Ok(DmDateType::CLOB),
+            PgType::UUID => Ok(DmDateType::CHAR),
+            PgType::INET | PgType::CIDR => Ok(DmDateType::VARCHAR),
+            PgType::MONEY => Ok(DmDateType::DECIMAL),
+            // arrays are stored as postgres text representation, E.g: `{1,2,3}`
+            _ if matches!(self.kind(), Kind::Array(_)) => Ok(DmDateType::CLOB),
+            _ => Err(conversion_error!(
+                "pg data_type {} to dameng data_type",
+                self
+            )),
+        }
+    }
+}
+
+impl TryConvert<DmTableItem> for PgTableItem {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<DmTableItem, Self::Error> {
+        (self, &TypeMapping::default()).try_convert()
+    }
+}
+
+/// Convert postgres column to dameng column, the column type is overridden by [`TypeMapping`]
+impl TryConvert<DmTableItem> for (PgTableItem, &TypeMapping) {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<DmTableItem, Self::Error> {
+        let (item, mapping) = self;
+        let source: PgColumnType = (&item).convert();
+        let column_type = match mapping.dm_type(&item.table_name, &item.name, &source)? {
+            Some(column_type) => column_type,
+            None => {
+                let TypeConversion {
+                    column_type,
+                    warnings,
+                }: TypeConversion<DmDateType> = (&source).try_convert()?;
+                for warning in warnings {
+                    warn!("column {}.{}: {}", item.table_name, item.name, warning);
+                }
+                column_type
+            }
+        };
+
+        let default_val = item
+            .default_val
+            .as_deref()
+            .and_then(|x| pg_default_to_dm(x, &column_type.base));
+        Ok(DmTableItem {
+            name: item.name.to_string(),
+            table_id: item.table_id,
+            col_index: item.col_index,
+            length: column_type.item_length().unwrap_or(item.length),
+            scale: column_type.item_scale().unwrap_or(item.scale),
+            r#type: column_type.base,
+            nullable: item.nullable,
+            default_val,
+            table_name: item.table_name,
+            create_time: item.create_time,
+        })
+    }
+}
+
+/// Translate postgres column default expression to dameng, the counterpart of
+/// [`dm_default_to_pg`](crate::bridge::dameng_to_pg::dm_default_to_pg). Type casts are removed.
+///
+/// # Example
+///
+/// ```rust
+/// use dameng_helper::DmDateType;
+/// use odbc_api_helper::bridge::pg_to_dameng::pg_default_to_dm;
+///
+/// assert_eq!(
+///     pg_default_to_dm("nextval('t_id_seq'::regclass)", &DmDateType::BIGINT).as_deref(),
+///     Some("t_id_seq.NEXTVAL")
+/// );
+/// assert_eq!(
+///     pg_default_to_dm("now()", &DmDateType::TIMESTAMP).as_deref(),
+///     Some("CURRENT_TIMESTAMP")
+/// );
+/// assert_eq!(pg_default_to_dm("", &DmDateType::TEXT), None);
+/// ```
+pub fn pg_default_to_dm(default_val: &str, dm_type: &DmDateType) -> Option<String> {
+    let default_val = strip_pg_cast(default_val.trim());
+    if default_val.is_empty() {
+        return None;
+    }
+
+    let upper = default_val.to_uppercase();
+    let translated = match upper.as_str() {
+        "NOW()"
+        | "CURRENT_TIMESTAMP"
+        | "TRANSACTION_TIMESTAMP()"
+        | "STATEMENT_TIMESTAMP()"
+        | "CLOCK_TIMESTAMP()" => "CURRENT_TIMESTAMP",
+        "LOCALTIMESTAMP(0)" => "SYSDATE",
+        "LOCALTIME" => "CURTIME()",
+        "GEN_RANDOM_UUID()" | "UUID_GENERATE_V4()" => "GUID()",
+        "TRUE" | "'TRUE'" | "'T'" if *dm_type == DmDateType::BIT => "1",
+        "FALSE" | "'FALSE'" | "'F'" if *dm_type == DmDateType::BIT => "0",
+        // `nextval('SEQ'::regclass)` to `SEQ.NEXTVAL`
+        x if x.starts_with("NEXTVAL(") && x.ends_with(')') => {
+            let seq = strip_pg_cast(default_val["nextval(".len()..default_val.len() - 1].trim());
+            let seq = match seq.strip_prefix('\'').and_then(|x| x.strip_suffix('\'')) {
+                Some(seq) => seq.replace("''", "'"),
+                None => seq.to_string(),
+            };
+            return Some(format!("{}.NEXTVAL", seq));
+        }
+        _ => default_val,
+    };
+    Some(translated.to_string())
+}
+
+/// Remove trailing postgres type casts, E.g: `'foo'::character varying` is `'foo'`
+fn strip_pg_cast(mut value: &str) -> &str {
+    while let Some((expr, cast)) = value.rsplit_once("::") {
+        let is_type = cast
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " _.\"[](),".contains(c))
+            && cast.matches('(').count() == cast.matches(')').count();
+        if !is_type {
+            break;
+        }
+        value = expr.trim_end();
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pg_default_to_dm() {
+        let cases = [
+            (
+                "nextval('t_id_seq'::regclass)",
+                DmDateType::BIGINT,
+                Some("t_id_seq.NEXTVAL"),
+            ),
+            (
+                "nextval('SEQ_T1')",
+                DmDateType::BIGINT,
+                Some("SEQ_T1.NEXTVAL"),
+            ),
+            ("now()", DmDateType::TIMESTAMP, Some("CURRENT_TIMESTAMP")),
+            ("LOCALTIMESTAMP(0)", DmDateType::TIMESTAMP, Some("SYSDATE")),
+            ("gen_random_uuid()", DmDateType::CHAR, Some("GUID()")),
+            (
+                "gen_random_uuid()::text",
+                DmDateType::VARCHAR,
+                Some("GUID()"),
+            ),
+            ("true", DmDateType::BIT, Some("1")),
+            (
+                "'foo'::character varying",
+                DmDateType::VARCHAR,
+                Some("'foo'"),
+            ),
+            ("'a::b'", DmDateType::VARCHAR, Some("'a::b'")),
+            ("0::numeric(10,2)", DmDateType::NUMERIC, Some("0")),
+            ("CURRENT_DATE", DmDateType::DATE, Some("CURRENT_DATE")),
+            ("  ", DmDateType::TEXT, None),
+        ];
+        for (pg, dm_type, expect) in cases {
+            assert_eq!(pg_default_to_dm(pg, &dm_type).as_deref(), expect, "{}", pg);
         }
     }
 }
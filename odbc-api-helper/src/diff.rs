@@ -0,0 +1,864 @@
+use crate::error::OdbcHelperError;
+use crate::executor::database::Options;
+use crate::executor::table::TableDescResult;
+use crate::executor::SupportDatabase;
+use crate::{Convert, TryConvert};
+use dameng_helper::table::{DmTableDesc, DmTableItem};
+use odbc_common::{Print, TableData};
+use pg_helper::table::{PgTableDesc, PgTableItem};
+use pg_helper::PgType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Database neutral table describe, column types are normalised to postgres types through the
+/// bridge mappings, so that dameng and postgres tables can be compared.
+///
+/// It can be loaded from json:
+/// ```json
+/// {
+///   "data": {
+///     "T1": [
+///       { "name": "ID", "col_index": 0, "type_name": "int4", "nullable": false },
+///       { "name": "NAME", "col_index": 1, "type_name": "varchar", "length": 100, "nullable": true, "default_val": "'foo'" }
+///     ]
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDesc {
+    /// key is table name
+    pub data: BTreeMap<String, Vec<SchemaColumn>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaColumn {
+    pub name: String,
+    #[serde(default)]
+    pub col_index: usize,
+    /// postgres type name, E.g: `int4`, `varchar`, `timestamp`
+    pub type_name: String,
+    /// length or numeric precision
+    #[serde(default)]
+    pub length: usize,
+    /// numeric scale or fractional seconds precision
+    #[serde(default)]
+    pub scale: usize,
+    pub nullable: bool,
+    #[serde(default)]
+    pub default_val: Option<String>,
+}
+
+impl SchemaDesc {
+    pub fn from_json(json: &str) -> Result<Self, OdbcHelperError> {
+        serde_json::from_str(json).map_err(|e| metadata_error!(source = e, "invalid schema json"))
+    }
+
+    pub fn to_json(&self) -> Result<String, OdbcHelperError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| metadata_error!(source = e, "invalid schema json"))
+    }
+
+    /// Compare `self` as source with `target`, the result describes how to change source into
+    /// target. Table and column names are compared in uppercase when not `case_sensitive`.
+    pub fn diff(&self, target: &SchemaDesc, case_sensitive: bool) -> SchemaDiff {
+        let key = |x: &str| {
+            if case_sensitive {
+                x.to_string()
+            } else {
+                x.to_uppercase()
+            }
+        };
+        let source_tables: BTreeMap<_, _> =
+            self.data.iter().map(|(k, v)| (key(k), (k, v))).collect();
+        let target_tables: BTreeMap<_, _> =
+            target.data.iter().map(|(k, v)| (key(k), (k, v))).collect();
+
+        let mut tables = vec![];
+        for (table_key, (table_name, source_columns)) in source_tables.iter() {
+            match target_tables.get(table_key) {
+                Some((target_name, target_columns)) => {
+                    let columns = diff_columns(source_columns, target_columns, key);
+                    if !columns.is_empty() {
+                        tables.push(TableDiff::Changed {
+                            table_name: target_name.to_string(),
+                            columns,
+                        });
+                    }
+                }
+                None => tables.push(TableDiff::Dropped {
+                    table_name: table_name.to_string(),
+                }),
+            }
+        }
+        for (table_key, (table_name, target_columns)) in target_tables.iter() {
+            if !source_tables.contains_key(table_key) {
+                tables.push(TableDiff::Added {
+                    table_name: table_name.to_string(),
+                    columns: target_columns.to_vec(),
+                });
+            }
+        }
+        SchemaDiff { tables }
+    }
+}
+
+fn diff_columns<F>(source: &[SchemaColumn], target: &[SchemaColumn], key: F) -> Vec<ColumnDiff>
+where
+    F: Fn(&str) -> String,
+{
+    let source_map: BTreeMap<_, _> = source.iter().map(|x| (key(&x.name), x)).collect();
+    let target_map: BTreeMap<_, _> = target.iter().map(|x| (key(&x.name), x)).collect();
+
+    let mut target = target.iter().collect::<Vec<_>>();
+    target.sort_by_key(|x| x.col_index);
+
+    let mut columns = vec![];
+    for column in target {
+        match source_map.get(&key(&column.name)) {
+            Some(source_column) => {
+                let changes = source_column.changes(column);
+                if !changes.is_empty() {
+                    columns.push(ColumnDiff::Changed {
+                        source: (*source_column).clone(),
+                        target: column.clone(),
+                        changes,
+                    });
+                }
+            }
+            None => columns.push(ColumnDiff::Added(column.clone())),
+        }
+    }
+    for column in source {
+        if !target_map.contains_key(&key(&column.name)) {
+            columns.push(ColumnDiff::Dropped(column.clone()));
+        }
+    }
+    columns
+}
+
+impl SchemaColumn {
+    pub fn pg_type(&self) -> Result<PgType, OdbcHelperError> {
+        pg_helper::type_from_name(&self.type_name)
+            .ok_or_else(|| metadata_error!("unknown pg type name:{}", self.type_name))
+    }
+
+    fn to_pg_item(&self, table_name: &str) -> Result<PgTableItem, OdbcHelperError> {
+        Ok(PgTableItem {
+            name: self.name.to_string(),
+            table_id: 0,
+            col_index: self.col_index,
+            r#type: self.pg_type()?,
+            length: self.length,
+            scale: self.scale,
+            nullable: self.nullable,
+            default_val: self.default_val.clone(),
+            table_name: table_name.to_string(),
+            create_time: "".to_string(),
+        })
+    }
+
+    fn to_dm_item(&self, table_name: &str) -> Result<DmTableItem, OdbcHelperError> {
+        self.to_pg_item(table_name)?.try_convert()
+    }
+
+    /// Postgres type sql with type modifiers used to compare column type,
+    /// E.g: `varchar(100)`. Unknown type name is compared as is.
+    pub fn normalized_type(&self) -> String {
+        match self.to_pg_item("") {
+            Ok(item) => item.type_sql(),
+            Err(_) => self.type_name.trim().to_lowercase(),
+        }
+    }
+
+    /// Default value used to compare column default. Empty default is `None` and the trailing
+    /// postgres type cast is removed, E.g: `'foo'::character varying` is `'foo'`.
+    pub fn normalized_default(&self) -> Option<String> {
+        let default_val = self.default_val.as_deref()?.trim();
+        let default_val = match default_val.rsplit_once("::") {
+            Some((value, cast))
+                if cast
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '_') =>
+            {
+                value
+            }
+            _ => default_val,
+        };
+        Some(default_val.to_string()).filter(|x| !x.is_empty())
+    }
+
+    fn describe(&self) -> String {
+        let mut desc = self.normalized_type();
+        if let Some(default_val) = self.normalized_default() {
+            desc.push_str(" DEFAULT ");
+            desc.push_str(&default_val);
+        }
+        if !self.nullable {
+            desc.push_str(" NOT NULL");
+        }
+        desc
+    }
+
+    fn changes(&self, target: &SchemaColumn) -> Vec<ColumnChange> {
+        let mut changes = vec![];
+        if self.normalized_type() != target.normalized_type() {
+            changes.push(ColumnChange::Type);
+        }
+        if self.nullable != target.nullable {
+            changes.push(ColumnChange::Nullable);
+        }
+        if self.normalized_default() != target.normalized_default() {
+            changes.push(ColumnChange::Default);
+        }
+        changes
+    }
+}
+
+impl Convert<SchemaColumn> for PgTableItem {
+    fn convert(self) -> SchemaColumn {
+        SchemaColumn {
+            name: self.name,
+            col_index: self.col_index,
+            type_name: self.r#type.name().to_string(),
+            length: self.length,
+            scale: self.scale,
+            nullable: self.nullable,
+            default_val: self.default_val,
+        }
+    }
+}
+
+impl Convert<SchemaDesc> for PgTableDesc {
+    fn convert(self) -> SchemaDesc {
+        let data = self
+            .data
+            .into_iter()
+            .map(|(table_name, items)| {
+                let columns: Vec<SchemaColumn> = items.into_iter().map(|x| x.convert()).collect();
+                (table_name, columns)
+            })
+            .collect();
+        SchemaDesc { data }
+    }
+}
+
+/// Table describe of `show_table`, columns are converted to postgres types of `options.database`
+impl TryConvert<SchemaDesc> for (TableDescResult, &Options) {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<SchemaDesc, Self::Error> {
+        match self.1.database {
+            SupportDatabase::Dameng => {
+                let desc: DmTableDesc = self.0.try_convert()?;
+                desc.try_convert()
+            }
+            SupportDatabase::Pg => {
+                let desc: PgTableDesc = self.try_convert()?;
+                Ok(desc.convert())
+            }
+            _ => Err(OdbcHelperError::unsupported_database(
+                &self.1.database,
+                "describe schema",
+            )),
+        }
+    }
+}
+
+impl TryConvert<SchemaDesc> for DmTableDesc {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<SchemaDesc, Self::Error> {
+        let mut data = BTreeMap::new();
+        for (table_name, items) in self.data {
+            let mut columns = vec![];
+            for item in items {
+                let item: PgTableItem = item.try_convert()?;
+                let column: SchemaColumn = item.convert();
+                columns.push(column);
+            }
+            data.insert(table_name, columns);
+        }
+        Ok(SchemaDesc { data })
+    }
+}
+
+/// Column attribute changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnChange {
+    Type,
+    Nullable,
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnDiff {
+    /// column only exists in target
+    Added(SchemaColumn),
+    /// column only exists in source
+    Dropped(SchemaColumn),
+    Changed {
+        source: SchemaColumn,
+        target: SchemaColumn,
+        changes: Vec<ColumnChange>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableDiff {
+    /// table only exists in target
+    Added {
+        table_name: String,
+        columns: Vec<SchemaColumn>,
+    },
+    /// table only exists in source
+    Dropped { table_name: String },
+    Changed {
+        table_name: String,
+        columns: Vec<ColumnDiff>,
+    },
+}
+
+/// Schema difference between source and target, see [`SchemaDesc::diff`]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub tables: Vec<TableDiff>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Generate `ALTER TABLE` sql of `database` dialect changing source into target.
+    /// Added table generate `CREATE TABLE` sql, dropped table generate `DROP TABLE` sql.
+    pub fn to_alter_sql(
+        &self,
+        database: &SupportDatabase,
+        schema: Option<&str>,
+        case_sensitive: bool,
+    ) -> Result<String, OdbcHelperError> {
+        let dialect = AlterDialect::new(database, case_sensitive)?;
+
+        let mut sqls = vec![];
+        for table in self.tables.iter() {
+            match table {
+                TableDiff::Added {
+                    table_name,
+                    columns,
+                } => sqls.push(dialect.create_table(schema, table_name, columns)?),
+                TableDiff::Dropped { table_name } => {
+                    sqls.push(format!("DROP TABLE {};", dialect.table(schema, table_name)))
+                }
+                TableDiff::Changed {
+                    table_name,
+                    columns,
+                } => {
+                    let table = dialect.table(schema, table_name);
+                    for column in columns {
+                        sqls.extend(dialect.alter_column(&table, table_name, column)?);
+                    }
+                }
+            }
+        }
+        Ok(sqls.join("\n"))
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let mut rows = vec![];
+        for table in self.tables.iter() {
+            match table {
+                TableDiff::Added { table_name, .. } => rows.push(vec![
+                    table_name.to_string(),
+                    "".to_string(),
+                    "ADD TABLE".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                ]),
+                TableDiff::Dropped { table_name } => rows.push(vec![
+                    table_name.to_string(),
+                    "".to_string(),
+                    "DROP TABLE".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                ]),
+                TableDiff::Changed {
+                    table_name,
+                    columns,
+                } => {
+                    for column in columns {
+                        match column {
+                            ColumnDiff::Added(x) => rows.push(vec![
+                                table_name.to_string(),
+                                x.name.to_string(),
+                                "ADD COLUMN".to_string(),
+                                "".to_string(),
+                                x.describe(),
+                            ]),
+                            ColumnDiff::Dropped(x) => rows.push(vec![
+                                table_name.to_string(),
+                                x.name.to_string(),
+                                "DROP COLUMN".to_string(),
+                                x.describe(),
+                                "".to_string(),
+                            ]),
+                            ColumnDiff::Changed {
+                                source,
+                                target,
+                                changes,
+                            } => {
+                                for change in changes {
+                                    let (name, from, to) = match change {
+                                        ColumnChange::Type => (
+                                            "TYPE",
+                                            source.normalized_type(),
+                                            target.normalized_type(),
+                                        ),
+                                        ColumnChange::Nullable => (
+                                            "NULLABLE",
+                                            source.nullable.to_string(),
+                                            target.nullable.to_string(),
+                                        ),
+                                        ColumnChange::Default => (
+                                            "DEFAULT",
+                                            source.normalized_default().unwrap_or_default(),
+                                            target.normalized_default().unwrap_or_default(),
+                                        ),
+                                    };
+                                    rows.push(vec![
+                                        table_name.to_string(),
+                                        target.name.to_string(),
+                                        name.to_string(),
+                                        from,
+                                        to,
+                                    ]);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        rows
+    }
+}
+
+impl Print for SchemaDiff {
+    fn table_data(self) -> anyhow::Result<TableData> {
+        let headers = ["TABLE_NAME", "COLUMN_NAME", "CHANGE", "SOURCE", "TARGET"]
+            .into_iter()
+            .map(|x| x.to_string())
+            .collect();
+        Ok(TableData::from((headers, self.rows())))
+    }
+}
+
+/// Target database of generated `ALTER` sql
+#[derive(Debug)]
+enum AlterDialect {
+    Pg { case_sensitive: bool },
+    Dameng { case_sensitive: bool },
+}
+
+impl AlterDialect {
+    fn new(database: &SupportDatabase, case_sensitive: bool) -> Result<Self, OdbcHelperError> {
+        match database {
+            SupportDatabase::Pg => Ok(Self::Pg { case_sensitive }),
+            SupportDatabase::Dameng => Ok(Self::Dameng { case_sensitive }),
+            _ => Err(OdbcHelperError::unsupported_database(
+                database,
+                "generate alter sql",
+            )),
+        }
+    }
+
+    fn ident(&self, name: &str) -> String {
+        match *self {
+            Self::Pg { case_sensitive } => pg_helper::ddl::quote_ident(name, case_sensitive),
+            Self::Dameng { case_sensitive } => {
+                dameng_helper::ddl::quote_ident(&self.dm_name(name, case_sensitive))
+            }
+        }
+    }
+
+    /// Dameng fold unquoted identifier to uppercase
+    fn dm_name(&self, name: &str, case_sensitive: bool) -> String {
+        if case_sensitive {
+            name.to_string()
+        } else {
+            name.to_uppercase()
+        }
+    }
+
+    fn table(&self, schema: Option<&str>, table_name: &str) -> String {
+        match schema {
+            Some(schema) => format!("{}.{}", self.ident(schema), self.ident(table_name)),
+            None => self.ident(table_name),
+        }
+    }
+
+    fn create_table(
+        &self,
+        schema: Option<&str>,
+        table_name: &str,
+        columns: &[SchemaColumn],
+    ) -> Result<String, OdbcHelperError> {
+        match *self {
+            Self::Pg { case_sensitive } => {
+                let items = columns
+                    .iter()
+                    .map(|x| x.to_pg_item(table_name))
+                    .collect::<Result<Vec<_>, OdbcHelperError>>()?;
+                let desc = PgTableDesc {
+                    data: BTreeMap::from([(table_name.to_string(), items)]),
+                };
+                Ok(desc.to_create_table_sql(schema, case_sensitive))
+            }
+            Self::Dameng { case_sensitive } => {
+                let items = columns
+                    .iter()
+                    .map(|x| {
+                        let mut item = x.to_dm_item(table_name)?;
+                        item.name = self.dm_name(&item.name, case_sensitive);
+                        Ok(item)
+                    })
+                    .collect::<Result<Vec<_>, OdbcHelperError>>()?;
+                let desc = DmTableDesc {
+                    headers: Default::default(),
+                    data: BTreeMap::from([(self.dm_name(table_name, case_sensitive), items)]),
+                    keys: Default::default(),
+                };
+                let schema = schema.map(|x| self.dm_name(x, case_sensitive));
+                Ok(desc.to_create_table_sql(schema.as_deref())?)
+            }
+        }
+    }
+
+    fn column_type(
+        &self,
+        table_name: &str,
+        column: &SchemaColumn,
+    ) -> Result<String, OdbcHelperError> {
+        match self {
+            Self::Pg { .. } => Ok(column.to_pg_item(table_name)?.type_sql()),
+            Self::Dameng { .. } => Ok(column.to_dm_item(table_name)?.type_sql()?),
+        }
+    }
+
+    /// Column default of target database, postgres default is translated for dameng
+    fn default_val(
+        &self,
+        table_name: &str,
+        column: &SchemaColumn,
+    ) -> Result<Option<String>, OdbcHelperError> {
+        match self {
+            Self::Pg { .. } => Ok(column.normalized_default()),
+            Self::Dameng { .. } => Ok(column.to_dm_item(table_name)?.default_val),
+        }
+    }
+
+    fn column_definition(
+        &self,
+        table_name: &str,
+        column: &SchemaColumn,
+    ) -> Result<String, OdbcHelperError> {
+        let mut sql = format!(
+            "{} {}",
+            self.ident(&column.name),
+            self.column_type(table_name, column)?
+        );
+        if let Some(default_val) = self.default_val(table_name, column)? {
+            sql.push_str(" DEFAULT ");
+            sql.push_str(&default_val);
+        }
+        if !column.nullable {
+            sql.push_str(" NOT NULL");
+        }
+        Ok(sql)
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        table_name: &str,
+        column: &ColumnDiff,
+    ) -> Result<Vec<String>, OdbcHelperError> {
+        let sqls = match column {
+            ColumnDiff::Added(x) => vec![format!(
+                "ALTER TABLE {} ADD COLUMN {};",
+                table,
+                self.column_definition(table_name, x)?
+            )],
+            ColumnDiff::Dropped(x) => vec![format!(
+                "ALTER TABLE {} DROP COLUMN {};",
+                table,
+                self.ident(&x.name)
+            )],
+            ColumnDiff::Changed {
+                target, changes, ..
+            } => {
+                let column = self.ident(&target.name);
+                let mut sqls = vec![];
+                for change in changes {
+                    let action = match (change, self) {
+                        (ColumnChange::Type, Self::Pg { .. }) => format!(
+                            "ALTER COLUMN {} TYPE {}",
+                            column,
+                            self.column_type(table_name, target)?
+                        ),
+                        (ColumnChange::Type, Self::Dameng { .. }) => format!(
+                            "MODIFY {} {}",
+                            column,
+                            self.column_type(table_name, target)?
+                        ),
+                        (ColumnChange::Nullable, _) if !target.nullable => {
+                            format!("ALTER COLUMN {} SET NOT NULL", column)
+                        }
+                        (ColumnChange::Nullable, Self::Pg { .. }) => {
+                            format!("ALTER COLUMN {} DROP NOT NULL", column)
+                        }
+                        (ColumnChange::Nullable, Self::Dameng { .. }) => {
+                            format!("ALTER COLUMN {} SET NULL", column)
+                        }
+                        (ColumnChange::Default, _) => match self.default_val(table_name, target)? {
+                            Some(default_val) => {
+                                format!("ALTER COLUMN {} SET DEFAULT {}", column, default_val)
+                            }
+                            None => format!("ALTER COLUMN {} DROP DEFAULT", column),
+                        },
+                    };
+                    sqls.push(format!("ALTER TABLE {} {};", table, action));
+                }
+                sqls
+            }
+        };
+        Ok(sqls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_diff() {
+        let item =
+            |name: &str, col_index, r#type, length, nullable, default_val: &str| PgTableItem {
+                name: name.to_string(),
+                table_id: 1058,
+                col_index,
+                r#type,
+                length,
+                scale: 0,
+                nullable,
+                default_val: Some(default_val.to_string()),
+                table_name: "T1".to_string(),
+                create_time: "".to_string(),
+            };
+        let source: SchemaDesc = PgTableDesc {
+            data: BTreeMap::from([
+                (
+                    "t1".to_string(),
+                    vec![
+                        item("id", 0, PgType::INT4, 4, false, ""),
+                        item(
+                            "name",
+                            1,
+                            PgType::VARCHAR,
+                            50,
+                            true,
+                            "'foo'::character varying",
+                        ),
+                        item("age", 2, PgType::INT4, 4, true, ""),
+                        item("remark", 3, PgType::TEXT, 0, true, ""),
+                    ],
+                ),
+                (
+                    "t_old".to_string(),
+                    vec![item("id", 0, PgType::INT4, 4, false, "")],
+                ),
+            ]),
+        }
+        .convert();
+
+        let target = SchemaDesc::from_json(
+            r#"{
+  "data": {
+    "T1": [
+      { "name": "ID", "col_index": 0, "type_name": "integer", "nullable": false },
+      { "name": "NAME", "col_index": 1, "type_name": "varchar", "length": 100, "nullable": false, "default_val": "'foo'" },
+      { "name": "AGE", "col_index": 2, "type_name": "int8", "nullable": true, "default_val": "0" },
+      { "name": "EMAIL", "col_index": 3, "type_name": "varchar", "length": 200, "nullable": true }
+    ],
+    "T_NEW": [
+      { "name": "ID", "type_name": "int8", "nullable": false }
+    ]
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert!(source.diff(&target, true).tables.len() == 4);
+
+        let diff = source.diff(&target, false);
+        assert_eq!(diff.tables.len(), 3);
+        info!("\n{}", diff.clone().table_string().unwrap());
+
+        let sql = diff
+            .to_alter_sql(&SupportDatabase::Pg, Some("public"), false)
+            .unwrap();
+        let expect = r#"ALTER TABLE public.t1 ALTER COLUMN name TYPE varchar(100);
+ALTER TABLE public.t1 ALTER COLUMN name SET NOT NULL;
+ALTER TABLE public.t1 ALTER COLUMN age TYPE int8;
+ALTER TABLE public.t1 ALTER COLUMN age SET DEFAULT 0;
+ALTER TABLE public.t1 ADD COLUMN email varchar(200);
+ALTER TABLE public.t1 DROP COLUMN remark;
+DROP TABLE public.t_old;
+CREATE TABLE public.t_new (
+    id int8 NOT NULL
+);"#;
+        assert_eq!(sql, expect);
+
+        let sql = diff
+            .to_alter_sql(&SupportDatabase::Dameng, Some("SYSDBA"), false)
+            .unwrap();
+        assert!(sql.starts_with(
+            r#"ALTER TABLE "SYSDBA"."T1" MODIFY "NAME" VARCHAR(100);
+ALTER TABLE "SYSDBA"."T1" ALTER COLUMN "NAME" SET NOT NULL;"#
+        ));
+
+        assert!(diff
+            .to_alter_sql(&SupportDatabase::Mysql, None, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_pg_schema_desc() {
+        use crate::executor::table::{OdbcTableDesc, OdbcTableItem};
+
+        // `SQLColumns` row of postgres ODBC driver
+        let row = |name: &str,
+                   data_type: i16,
+                   type_name: &str,
+                   size: Option<usize>,
+                   digits: Option<i16>,
+                   nullable: bool,
+                   default_val: Option<&str>| {
+            let mut row: Vec<Option<String>> = vec![None; 18];
+            row[2] = Some("t1".to_string());
+            row[3] = Some(name.to_string());
+            row[4] = Some(data_type.to_string());
+            row[5] = Some(type_name.to_string());
+            row[6] = size.map(|x| x.to_string());
+            row[8] = digits.map(|x| x.to_string());
+            row[10] = Some(if nullable { "1" } else { "0" }.to_string());
+            row[12] = default_val.map(|x| x.to_string());
+            row
+        };
+        let rows = [
+            row(
+                "id",
+                4,
+                "serial",
+                Some(10),
+                Some(0),
+                false,
+                Some("nextval('t1_id_seq'::regclass)"),
+            ),
+            row("name", 12, "varchar", Some(100), None, true, None),
+            row("price", 2, "numeric", Some(10), Some(2), true, None),
+            row("created", 93, "timestamp", Some(26), Some(3), true, None),
+            row("tags", -1, "_text", None, None, true, None),
+        ];
+        let mut desc = OdbcTableDesc::default();
+        for (index, row) in rows.iter().enumerate() {
+            let mut row = row.clone();
+            row[16] = Some((index + 1).to_string());
+            let item = OdbcTableItem::from_sql_columns(&row).unwrap();
+            desc.data.entry("t1".to_string()).or_default().push(item);
+        }
+        let options = Options::new(SupportDatabase::Pg);
+        let source: SchemaDesc = (TableDescResult::from(desc), &options)
+            .try_convert()
+            .unwrap();
+
+        let target = SchemaDesc::from_json(
+            r#"{
+  "data": {
+    "T1": [
+      { "name": "ID", "col_index": 0, "type_name": "int4", "nullable": false, "default_val": "nextval('t1_id_seq'::regclass)" },
+      { "name": "NAME", "col_index": 1, "type_name": "varchar", "length": 100, "nullable": true },
+      { "name": "PRICE", "col_index": 2, "type_name": "numeric", "length": 10, "scale": 2, "nullable": true },
+      { "name": "CREATED", "col_index": 3, "type_name": "timestamp", "scale": 3, "nullable": true },
+      { "name": "TAGS", "col_index": 4, "type_name": "_text", "nullable": true }
+    ]
+  }
+}"#,
+        )
+        .unwrap();
+        assert_eq!(source.diff(&target, false).tables, vec![]);
+
+        let target = SchemaDesc::from_json(
+            r#"{ "data": { "t1": [ { "name": "id", "type_name": "int8", "nullable": false } ] } }"#,
+        )
+        .unwrap();
+        let diff = source.diff(&target, false);
+        assert_eq!(
+            diff.to_alter_sql(&SupportDatabase::Pg, None, false)
+                .unwrap(),
+            r#"ALTER TABLE t1 ALTER COLUMN id TYPE int8;
+ALTER TABLE t1 ALTER COLUMN id DROP DEFAULT;
+ALTER TABLE t1 DROP COLUMN name;
+ALTER TABLE t1 DROP COLUMN price;
+ALTER TABLE t1 DROP COLUMN created;
+ALTER TABLE t1 DROP COLUMN tags;"#
+        );
+    }
+
+    #[test]
+    fn test_dm_alter_default() {
+        let source = SchemaDesc::from_json(
+            r#"{
+  "data": {
+    "T1": [
+      { "name": "ID", "col_index": 0, "type_name": "int8", "nullable": false, "default_val": "nextval('t1_id_seq'::regclass)" },
+      { "name": "CREATED", "col_index": 1, "type_name": "timestamp", "nullable": true }
+    ]
+  }
+}"#,
+        )
+        .unwrap();
+        let target = SchemaDesc::from_json(
+            r#"{
+  "data": {
+    "T1": [
+      { "name": "ID", "col_index": 0, "type_name": "int8", "nullable": false, "default_val": "nextval('t1_id_seq'::regclass)" },
+      { "name": "CREATED", "col_index": 1, "type_name": "timestamp", "nullable": true, "default_val": "now()" },
+      { "name": "TAG", "col_index": 2, "type_name": "varchar", "length": 10, "nullable": true, "default_val": "'x'::character varying" }
+    ]
+  }
+}"#,
+        )
+        .unwrap();
+
+        let sql = SchemaDesc::default()
+            .diff(&source, false)
+            .to_alter_sql(&SupportDatabase::Dameng, None, false)
+            .unwrap();
+        assert_eq!(
+            sql,
+            r#"CREATE TABLE "T1" (
+    "ID" BIGINT DEFAULT t1_id_seq.NEXTVAL NOT NULL,
+    "CREATED" TIMESTAMP(0)
+);"#
+        );
+        let sql = source
+            .diff(&target, false)
+            .to_alter_sql(&SupportDatabase::Dameng, None, false)
+            .unwrap();
+        assert_eq!(
+            sql,
+            r#"ALTER TABLE "T1" ALTER COLUMN "CREATED" SET DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE "T1" ADD COLUMN "TAG" VARCHAR(10) DEFAULT 'x';"#
+        );
+    }
+}
@@ -1,11 +1,324 @@
+use crate::executor::SupportDatabase;
+use dameng_helper::error::DmError;
+use odbc_api::handles::{slice_to_cow_utf8, Record};
 use thiserror::Error;
 
+/// Underlying error kept as [`std::error::Error::source`] of [`OdbcHelperError`]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Error of odbc-api-helper public api, use [`std::error::Error::source`] to get the underlying error.
 #[derive(Error, Debug)]
 pub enum OdbcHelperError {
+    /// Failed to connect or the connection is lost
+    #[error("connection error:`{error}`")]
+    ConnectionError {
+        error: DbError,
+        #[source]
+        source: odbc_api::Error,
+    },
+    /// Error reported by odbc driver with diagnostic record
+    #[error("database error:`{error}`")]
+    DiagnosticError {
+        error: DbError,
+        #[source]
+        source: odbc_api::Error,
+    },
     #[error("odbc error:`{0}`")]
-    OdbcError(odbc_api::Error),
-    #[error("invalid sql params `{0}` error")]
-    SqlParamsError(String),
-    #[error("Failed to convert byte to {0}")]
-    TypeConversionError(String),
+    OdbcError(#[from] odbc_api::Error),
+    #[error("invalid sql params `{message}` error")]
+    SqlParamsError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+    /// Value or type could not be converted, `source` is the parse error if any
+    #[error("type conversion error:{message}")]
+    TypeConversionError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+    #[error("{operation} unsupported database:{database}")]
+    UnsupportedDatabase {
+        database: String,
+        operation: &'static str,
+    },
+    /// Metadata or mapping file could not be parsed, `source` is the parse error if any
+    #[error("Failed to parse metadata:{message}")]
+    MetadataParseError {
+        message: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+    /// Sql could not be parsed, translated or emulated
+    #[error("sql error:{0}")]
+    SqlError(String),
+    #[error("query error: statement has no result set")]
+    NoResultSet,
+    #[error(transparent)]
+    DmError(#[from] DmError),
+    /// Value could not be exported, or invalid export options
+    #[error("export error:{0}")]
+    ExportError(String),
+    #[error("arrow error:{0}")]
+    ArrowError(#[from] arrow_schema::ArrowError),
+    #[error("parquet error:{0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+    #[error("csv error:{0}")]
+    CsvError(#[from] csv::Error),
+}
+
+impl OdbcHelperError {
+    pub fn unsupported_database(database: &SupportDatabase, operation: &'static str) -> Self {
+        OdbcHelperError::UnsupportedDatabase {
+            database: format!("{:?}", database),
+            operation,
+        }
+    }
+
+    /// Decoded diagnostic record of connection or driver error
+    pub fn db_error(&self) -> Option<&DbError> {
+        match self {
+            OdbcHelperError::ConnectionError { error, .. }
+            | OdbcHelperError::DiagnosticError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Decode odbc error to [`DbError`], errors without diagnostic record are kept as is.
+    pub fn from_odbc(database: &SupportDatabase, source: odbc_api::Error) -> Self {
+        match DbError::from_odbc(database, &source) {
+            Some(error) if error.kind == DbErrorKind::ConnectionLost => {
+                OdbcHelperError::ConnectionError { error, source }
+            }
+            Some(error) => OdbcHelperError::DiagnosticError { error, source },
+            None => OdbcHelperError::OdbcError(source),
+        }
+    }
+}
+
+/// Dameng native error code of syntax error, E.g: `-2007: 第1 行附近出现错误`
+pub const DM_SYNTAX_ERROR: i32 = -2007;
+/// Dameng native error code of unique constraint violation, E.g: `-6602: 违反表[T]唯一性约束`
+pub const DM_UNIQUE_VIOLATION: i32 = -6602;
+/// Dameng native error codes of foreign key violation, inserting a row without parent
+/// and deleting a parent row with children, E.g: `-6625: 违反引用约束[FK_T]`
+pub const DM_FOREIGN_KEY_VIOLATION: &[i32] = &[-6604, -6625];
+/// Dameng native error code of deadlock, E.g: `-6407: 死锁`
+pub const DM_DEADLOCK: i32 = -6407;
+/// Dameng native error code of lost connection, E.g: `-6001: 网络通信异常`
+pub const DM_CONNECTION_LOST: i32 = -6001;
+
+/// Dameng error message keywords, both chinese and english messages are matched.
+/// Only used when the native error code is not recognised.
+const DM_MESSAGE_KEYWORDS: &[(DbErrorKind, &[&str])] = &[
+    (
+        DbErrorKind::UniqueViolation,
+        &["唯一性约束", "unique constraint"],
+    ),
+    (
+        DbErrorKind::ForeignKeyViolation,
+        &["引用约束", "foreign key", "referential"],
+    ),
+    (DbErrorKind::Deadlock, &["死锁", "deadlock"]),
+    (
+        DbErrorKind::ConnectionLost,
+        &["网络通信", "连接已断开", "communication", "connection lost"],
+    ),
+    (DbErrorKind::SyntaxError, &["语法", "syntax"]),
+];
+
+/// Classification of database error, see [`DbError::kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbErrorKind {
+    UniqueViolation,
+    ForeignKeyViolation,
+    Deadlock,
+    ConnectionLost,
+    SyntaxError,
+    Other,
+}
+
+impl DbErrorKind {
+    /// Postgres SQLSTATE of the error kind, `None` for [`DbErrorKind::Other`]
+    pub fn pg_sqlstate(&self) -> Option<&'static str> {
+        match self {
+            DbErrorKind::UniqueViolation => Some("23505"),
+            DbErrorKind::ForeignKeyViolation => Some("23503"),
+            DbErrorKind::Deadlock => Some("40P01"),
+            DbErrorKind::ConnectionLost => Some("08006"),
+            DbErrorKind::SyntaxError => Some("42601"),
+            DbErrorKind::Other => None,
+        }
+    }
+}
+
+/// Database error decoded from odbc diagnostic record.
+///
+/// ```
+/// use odbc_api_helper::error::{DbError, DbErrorKind};
+/// use odbc_api_helper::executor::SupportDatabase;
+///
+/// let error = DbError::new(&SupportDatabase::Dameng, "23000", -6602, "违反表[T]唯一性约束");
+/// assert_eq!(error.kind, DbErrorKind::UniqueViolation);
+/// assert_eq!(error.pg_sqlstate(), "23505");
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("State: {state}, Native error: {native_error}, Message: {message}")]
+pub struct DbError {
+    /// ODBC SQLSTATE, E.g: `23000`
+    pub state: String,
+    /// Native error code of the database
+    pub native_error: i32,
+    pub message: String,
+    pub kind: DbErrorKind,
+}
+
+impl DbError {
+    pub fn new<S: Into<String>, M: Into<String>>(
+        database: &SupportDatabase,
+        state: S,
+        native_error: i32,
+        message: M,
+    ) -> Self {
+        let state = state.into();
+        let message = message.into();
+        let kind = classify(database, &state, native_error, &message);
+        DbError {
+            state,
+            native_error,
+            message,
+            kind,
+        }
+    }
+
+    /// Decode the diagnostic record of odbc error, return `None` if the error has no record.
+    pub fn from_odbc(database: &SupportDatabase, error: &odbc_api::Error) -> Option<Self> {
+        let record = match error {
+            odbc_api::Error::Diagnostics { record, .. }
+            | odbc_api::Error::InvalidRowArraySize { record, .. }
+            | odbc_api::Error::UnsupportedOdbcApiVersion(record)
+            | odbc_api::Error::UnableToRepresentNull(record)
+            | odbc_api::Error::OracleOdbcDriverDoesNotSupport64Bit(record) => record,
+            _ => return None,
+        };
+        Some(Self::from_record(database, record))
+    }
+
+    pub fn from_record(database: &SupportDatabase, record: &Record) -> Self {
+        Self::new(
+            database,
+            record.state.as_str(),
+            record.native_error,
+            slice_to_cow_utf8(&record.message),
+        )
+    }
+
+    /// Postgres SQLSTATE for the wire protocol. The classified kind is used first, then ODBC
+    /// SQLSTATE is mapped, `XX000`(internal_error) if there is no equivalent.
+    pub fn pg_sqlstate(&self) -> &str {
+        if let Some(state) = self.kind.pg_sqlstate() {
+            return state;
+        }
+        match self.state.as_str() {
+            "42S01" => "42P07",
+            "42S02" => "42P01",
+            "42S21" => "42701",
+            "42S22" => "42703",
+            "HYT00" | "HY008" => "57014",
+            "HY000" => "XX000",
+            // ODBC 3.x SQLSTATE of these classes are the same as SQL standard
+            x if x.len() == 5
+                && [
+                    "01", "02", "08", "0A", "21", "22", "23", "25", "28", "40", "42",
+                ]
+                .contains(&&x[..2]) =>
+            {
+                x
+            }
+            _ => "XX000",
+        }
+    }
+}
+
+fn classify(
+    database: &SupportDatabase,
+    state: &str,
+    native_error: i32,
+    message: &str,
+) -> DbErrorKind {
+    if let SupportDatabase::Dameng = database {
+        match native_error {
+            DM_SYNTAX_ERROR => return DbErrorKind::SyntaxError,
+            DM_UNIQUE_VIOLATION => return DbErrorKind::UniqueViolation,
+            DM_DEADLOCK => return DbErrorKind::Deadlock,
+            x if DM_FOREIGN_KEY_VIOLATION.contains(&x) => return DbErrorKind::ForeignKeyViolation,
+            DM_CONNECTION_LOST => return DbErrorKind::ConnectionLost,
+            _ => {}
+        }
+        let message = message.to_lowercase();
+        if let Some((kind, _)) = DM_MESSAGE_KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|x| message.contains(x)))
+        {
+            return *kind;
+        }
+    }
+    match state {
+        "23505" => DbErrorKind::UniqueViolation,
+        "23503" => DbErrorKind::ForeignKeyViolation,
+        "40P01" => DbErrorKind::Deadlock,
+        "42601" => DbErrorKind::SyntaxError,
+        x if x.starts_with("08") => DbErrorKind::ConnectionLost,
+        _ => DbErrorKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_db_error_classify() {
+        let dm = |state: &str, native_error: i32, message: &str| {
+            DbError::new(&SupportDatabase::Dameng, state, native_error, message)
+        };
+        let error = dm("42000", -2007, "第1 行附近出现错误");
+        assert_eq!(error.kind, DbErrorKind::SyntaxError);
+        assert_eq!(error.pg_sqlstate(), "42601");
+
+        let error = dm("23000", -6625, "违反引用约束[FK_T]");
+        assert_eq!(error.kind, DbErrorKind::ForeignKeyViolation);
+        assert_eq!(error.pg_sqlstate(), "23503");
+        let error = dm("23000", -6604, "violate reference constraint[FK_T]");
+        assert_eq!(error.kind, DbErrorKind::ForeignKeyViolation);
+
+        let error = dm("40001", -6407, "死锁");
+        assert_eq!(error.kind, DbErrorKind::Deadlock);
+        assert_eq!(error.pg_sqlstate(), "40P01");
+
+        let error = dm("HY000", -6001, "网络通信异常");
+        assert_eq!(error.kind, DbErrorKind::ConnectionLost);
+        assert_eq!(error.pg_sqlstate(), "08006");
+
+        // native error code takes precedence over the message
+        let error = dm("23000", -6602, "违反表[T]唯一性约束, foreign key");
+        assert_eq!(error.kind, DbErrorKind::UniqueViolation);
+
+        // unknown native error code falls back to the message
+        let error = dm("23000", -1, "违反引用约束[FK_T]");
+        assert_eq!(error.kind, DbErrorKind::ForeignKeyViolation);
+
+        assert_eq!(dm("40001", -1, "Deadlock").kind, DbErrorKind::Deadlock);
+        assert_eq!(dm("08S01", -1, "network").kind, DbErrorKind::ConnectionLost);
+
+        let error = dm("42S02", -1, "无效的表或视图名[T]");
+        assert_eq!(error.kind, DbErrorKind::Other);
+        assert_eq!(error.pg_sqlstate(), "42P01");
+        assert_eq!(dm("HY000", -1, "unknown").pg_sqlstate(), "XX000");
+
+        let error = DbError::new(&SupportDatabase::Pg, "23505", 1, "duplicate key");
+        assert_eq!(error.kind, DbErrorKind::UniqueViolation);
+        assert_eq!(error.pg_sqlstate(), "23505");
+    }
 }
@@ -0,0 +1,240 @@
+use crate::error::OdbcHelperError;
+use odbc_api::buffers::TextRowSet;
+use odbc_api::handles::{Record, Statement, StatementImpl};
+use odbc_api::sys::{HStmt, SmallInt, SqlReturn, WChar};
+use odbc_api::{Connection, Cursor, CursorImpl};
+use odbc_common::{Print, TableData};
+
+/// One row of a catalog listing
+pub trait CatalogItem {
+    fn headers() -> Vec<&'static str>;
+
+    fn to_vec(&self) -> Vec<String>;
+}
+
+/// Catalog listing result, e.g: schemas, tables, views or sequences.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogResult<T> {
+    pub items: Vec<T>,
+}
+
+impl<T> CatalogResult<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self { items }
+    }
+
+    pub fn names(&self) -> Vec<&str>
+    where
+        T: AsRef<str>,
+    {
+        self.items.iter().map(|x| x.as_ref()).collect()
+    }
+}
+
+impl<T: CatalogItem> Print for CatalogResult<T> {
+    fn table_data(self) -> anyhow::Result<TableData> {
+        let headers = T::headers().into_iter().map(|x| x.to_string()).collect();
+        let rows = self.items.iter().map(|x| x.to_vec()).collect();
+        Ok(TableData::from((headers, rows)))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogSchema {
+    pub name: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogTable {
+    pub schema: String,
+    pub name: String,
+    pub remarks: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogView {
+    pub schema: String,
+    pub name: String,
+    pub remarks: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CatalogSequence {
+    pub schema: String,
+    pub name: String,
+}
+
+macro_rules! impl_as_ref_name {
+    ($($t:ty),+) => {
+        $(
+            impl AsRef<str> for $t {
+                fn as_ref(&self) -> &str {
+                    &self.name
+                }
+            }
+        )+
+    };
+}
+
+impl_as_ref_name!(CatalogSchema, CatalogTable, CatalogView, CatalogSequence);
+
+impl CatalogItem for CatalogSchema {
+    fn headers() -> Vec<&'static str> {
+        vec!["SCHEMA_NAME"]
+    }
+
+    fn to_vec(&self) -> Vec<String> {
+        vec![self.name.to_string()]
+    }
+}
+
+impl CatalogItem for CatalogTable {
+    fn headers() -> Vec<&'static str> {
+        vec!["SCHEMA_NAME", "TABLE_NAME", "REMARKS"]
+    }
+
+    fn to_vec(&self) -> Vec<String> {
+        vec![
+            self.schema.to_string(),
+            self.name.to_string(),
+            self.remarks.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl CatalogItem for CatalogView {
+    fn headers() -> Vec<&'static str> {
+        vec!["SCHEMA_NAME", "VIEW_NAME", "REMARKS"]
+    }
+
+    fn to_vec(&self) -> Vec<String> {
+        vec![
+            self.schema.to_string(),
+            self.name.to_string(),
+            self.remarks.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl CatalogItem for CatalogSequence {
+    fn headers() -> Vec<&'static str> {
+        vec!["SCHEMA_NAME", "SEQUENCE_NAME"]
+    }
+
+    fn to_vec(&self) -> Vec<String> {
+        vec![self.schema.to_string(), self.name.to_string()]
+    }
+}
+
+/// ODBC `SQLTables` result set columns:
+/// `TABLE_CAT`, `TABLE_SCHEM`, `TABLE_NAME`, `TABLE_TYPE`, `REMARKS`
+pub(crate) mod sql_tables {
+    pub const TABLE_SCHEM: usize = 1;
+    pub const TABLE_NAME: usize = 2;
+    pub const REMARKS: usize = 4;
+
+    pub const TABLE_TYPE_TABLE: &str = "TABLE";
+    pub const TABLE_TYPE_VIEW: &str = "VIEW";
+    pub const TABLE_TYPE_SEQUENCE: &str = "SEQUENCE";
+}
+
+/// ODBC `SQLPrimaryKeys` result set columns:
+/// `TABLE_CAT`, `TABLE_SCHEM`, `TABLE_NAME`, `COLUMN_NAME`, `KEY_SEQ`, `PK_NAME`
+pub(crate) mod sql_primary_keys {
+    pub const COLUMN_NAME: usize = 3;
+    pub const KEY_SEQ: usize = 4;
+}
+
+// `odbc-sys` does not declare `SQLPrimaryKeys`, it is linked from the same driver manager.
+extern "system" {
+    fn SQLPrimaryKeysW(
+        statement_handle: HStmt,
+        catalog_name: *const WChar,
+        catalog_name_length: SmallInt,
+        schema_name: *const WChar,
+        schema_name_length: SmallInt,
+        table_name: *const WChar,
+        table_name_length: SmallInt,
+    ) -> SqlReturn;
+}
+
+/// A cursor describing the primary key columns of table, the counterpart of
+/// `Connection::columns` for ODBC catalog function `SQLPrimaryKeys`. The returned cursor has the
+/// columns of [`sql_primary_keys`]. Arguments are not search patterns.
+pub(crate) fn primary_keys<'c>(
+    conn: &'c Connection<'_>,
+    catalog_name: &str,
+    schema_name: &str,
+    table_name: &str,
+) -> Result<CursorImpl<StatementImpl<'c>>, odbc_api::Error> {
+    let statement = conn.preallocate()?.into_statement();
+    let catalog_name: Vec<WChar> = catalog_name.encode_utf16().collect();
+    let schema_name: Vec<WChar> = schema_name.encode_utf16().collect();
+    let table_name: Vec<WChar> = table_name.encode_utf16().collect();
+    let ret = unsafe {
+        SQLPrimaryKeysW(
+            statement.as_sys(),
+            catalog_name.as_ptr(),
+            catalog_name.len().try_into().unwrap(),
+            schema_name.as_ptr(),
+            schema_name.len().try_into().unwrap(),
+            table_name.as_ptr(),
+            table_name.len().try_into().unwrap(),
+        )
+    };
+    match ret {
+        SqlReturn::SUCCESS | SqlReturn::SUCCESS_WITH_INFO => {
+            // Safe: `statement` is in cursor state
+            Ok(unsafe { CursorImpl::new(statement) })
+        }
+        _ => {
+            let mut record = Record::with_capacity(512);
+            if record.fill_from(&statement, 1) {
+                Err(odbc_api::Error::Diagnostics {
+                    record,
+                    function: "SQLPrimaryKeys",
+                })
+            } else {
+                Err(odbc_api::Error::NoDiagnostics {
+                    function: "SQLPrimaryKeys",
+                })
+            }
+        }
+    }
+}
+
+pub(crate) type TextRows = Vec<Vec<Option<String>>>;
+
+/// Fetch all rows of cursor as text. NULL value is `None`.
+pub(crate) fn fetch_text_rows<C: Cursor>(
+    mut cursor: C,
+    batch_size: usize,
+) -> Result<TextRows, OdbcHelperError> {
+    let mut buffers = TextRowSet::for_cursor(batch_size, &mut cursor, Some(4096))?;
+    let mut row_set_cursor = cursor.bind_buffer(&mut buffers)?;
+
+    let mut rows = vec![];
+    while let Some(batch) = row_set_cursor.fetch()? {
+        for row_index in 0..batch.num_rows() {
+            let row = (0..batch.num_cols())
+                .map(|col_index| {
+                    batch
+                        .at(col_index, row_index)
+                        .map(|x| String::from_utf8_lossy(x).to_string())
+                })
+                .collect();
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Get column value of text row, NULL value convert to empty string.
+pub(crate) fn text_at(row: &[Option<String>], index: usize) -> String {
+    row.get(index).cloned().flatten().unwrap_or_default()
+}
+
+/// Get column value of text row, empty string convert to `None`.
+pub(crate) fn opt_text_at(row: &[Option<String>], index: usize) -> Option<String> {
+    row.get(index).cloned().flatten().filter(|x| !x.is_empty())
+}
@@ -0,0 +1,296 @@
+use crate::executor::database::Options;
+use crate::executor::SupportDatabase;
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+/// Typed odbc connection string builder, attribute names follow the odbc driver of `database`.
+///
+/// # Example
+///
+/// ```rust
+/// use odbc_api_helper::executor::connection::ConnectionConfig;
+/// use odbc_api_helper::executor::SupportDatabase;
+///
+/// let config = ConnectionConfig::new(SupportDatabase::Dameng, "DM8")
+///     .with_server("127.0.0.1")
+///     .with_port(5236)
+///     .with_user("SYSDBA")
+///     .with_password("pass;word");
+/// assert_eq!(
+///     config.to_connection_string(),
+///     "Driver={DM8};Server=127.0.0.1;TCP_PORT=5236;UID=SYSDBA;PWD={pass;word};"
+/// );
+/// assert!(!format!("{:?}", config).contains("pass;word"));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConnectionConfig {
+    pub database: SupportDatabase,
+    /// Odbc driver name, E.g: `DM8`, `PostgreSQL Unicode`, `MySQL ODBC 8.0 Unicode Driver`
+    pub driver: String,
+    pub server: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    /// Default schema, it is the database of mysql and the `search_path` of postgres
+    pub schema: Option<String>,
+    /// Database name of postgres and mysql, dameng has one database per server
+    pub catalog: Option<String>,
+    pub login_timeout: Option<Duration>,
+    /// Client character set, E.g: dameng `PG_UTF8`, postgres `UTF8`, mysql `utf8mb4`
+    pub charset: Option<String>,
+    /// See [`Options::case_sensitive`]
+    pub case_sensitive: bool,
+    /// Extra driver specific attributes, appended as is
+    pub attributes: Vec<(String, String)>,
+}
+
+impl ConnectionConfig {
+    pub fn new<S: Into<String>>(database: SupportDatabase, driver: S) -> Self {
+        ConnectionConfig {
+            database,
+            driver: driver.into(),
+            server: None,
+            port: None,
+            user: None,
+            password: None,
+            schema: None,
+            catalog: None,
+            login_timeout: None,
+            charset: None,
+            case_sensitive: false,
+            attributes: vec![],
+        }
+    }
+
+    pub fn with_server<S: Into<String>>(mut self, server: S) -> Self {
+        self.server = Some(server.into());
+        self
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn with_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn with_password<S: Into<String>>(mut self, password: S) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn with_schema<S: Into<String>>(mut self, schema: S) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    pub fn with_catalog<S: Into<String>>(mut self, catalog: S) -> Self {
+        self.catalog = Some(catalog.into());
+        self
+    }
+
+    pub fn with_login_timeout(mut self, login_timeout: Duration) -> Self {
+        self.login_timeout = Some(login_timeout);
+        self
+    }
+
+    pub fn with_charset<S: Into<String>>(mut self, charset: S) -> Self {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    pub fn with_attribute<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// Helper options of the connection, other fields are default
+    pub fn options(&self) -> Options {
+        let mut options = Options::new(self.database);
+        options.case_sensitive = self.case_sensitive;
+        options
+    }
+
+    /// Build connection string, values are escaped by [`escape_value`]
+    pub fn to_connection_string(&self) -> String {
+        let mut attributes: Vec<(&str, String)> = vec![];
+        let timeout = self.login_timeout.map(|x| x.as_secs().to_string());
+        match self.database {
+            SupportDatabase::Dameng => {
+                push(&mut attributes, "Server", &self.server);
+                push(
+                    &mut attributes,
+                    "TCP_PORT",
+                    &self.port.map(|x| x.to_string()),
+                );
+                push(&mut attributes, "UID", &self.user);
+                push(&mut attributes, "PWD", &self.password);
+                push(&mut attributes, "SCHEMA", &self.schema);
+                push(&mut attributes, "LOGIN_TIMEOUT", &timeout);
+                push(&mut attributes, "CHARACTER_CODE", &self.charset);
+                if self.case_sensitive {
+                    attributes.push(("CASE_SENSITIVE", "1".to_string()));
+                }
+            }
+            SupportDatabase::Pg => {
+                push(&mut attributes, "Server", &self.server);
+                push(&mut attributes, "Port", &self.port.map(|x| x.to_string()));
+                push(&mut attributes, "Database", &self.catalog);
+                push(&mut attributes, "UID", &self.user);
+                push(&mut attributes, "PWD", &self.password);
+                let settings: Vec<String> = [
+                    self.schema
+                        .as_ref()
+                        .map(|x| format!("SET search_path TO {}", x)),
+                    self.charset
+                        .as_ref()
+                        .map(|x| format!("SET client_encoding TO '{}'", x)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !settings.is_empty() {
+                    attributes.push(("ConnSettings", settings.join(";")));
+                }
+                push(
+                    &mut attributes,
+                    "pqopt",
+                    &timeout.map(|x| format!("connect_timeout={}", x)),
+                );
+            }
+            SupportDatabase::Mysql => {
+                push(&mut attributes, "Server", &self.server);
+                push(&mut attributes, "Port", &self.port.map(|x| x.to_string()));
+                let database = self.schema.as_ref().or(self.catalog.as_ref()).cloned();
+                push(&mut attributes, "Database", &database);
+                push(&mut attributes, "UID", &self.user);
+                push(&mut attributes, "PWD", &self.password);
+                push(&mut attributes, "CHARSET", &self.charset);
+            }
+        }
+
+        // Driver name is always braced, which is required by names with spaces on some platforms
+        let mut s = format!("Driver={{{}}};", self.driver.replace('}', "}}"));
+        for (key, value) in attributes.iter().map(|(k, v)| (*k, v.as_str())).chain(
+            self.attributes
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        ) {
+            s.push_str(&format!("{}={};", key, escape_value(value)));
+        }
+        s
+    }
+}
+
+fn push(attributes: &mut Vec<(&'static str, String)>, key: &'static str, value: &Option<String>) {
+    if let Some(value) = value {
+        attributes.push((key, value.to_string()));
+    }
+}
+
+/// Password is hidden
+impl Debug for ConnectionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionConfig")
+            .field("database", &self.database)
+            .field("driver", &self.driver)
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .field("password", &self.password.as_ref().map(|_| "******"))
+            .field("schema", &self.schema)
+            .field("catalog", &self.catalog)
+            .field("login_timeout", &self.login_timeout)
+            .field("charset", &self.charset)
+            .field("case_sensitive", &self.case_sensitive)
+            .field("attributes", &self.attributes)
+            .finish()
+    }
+}
+
+/// Escape attribute value of connection string. Value containing `;`, `{`, `}`, `=` or
+/// surrounding whitespace is enclosed in braces, and `}` in it is doubled.
+///
+/// # Example
+///
+/// ```rust
+/// use odbc_api_helper::executor::connection::escape_value;
+///
+/// assert_eq!(escape_value("SYSDBA"), "SYSDBA");
+/// assert_eq!(escape_value("a;b"), "{a;b}");
+/// assert_eq!(escape_value("a}b"), "{a}}b}");
+/// ```
+pub fn escape_value(value: &str) -> String {
+    let need_braces = value.contains([';', '{', '}', '=']) || value.trim() != value;
+    if need_braces {
+        format!("{{{}}}", value.replace('}', "}}"))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_string() {
+        let config = ConnectionConfig::new(SupportDatabase::Dameng, "DM8")
+            .with_server("0.0.0.0")
+            .with_user("SYSDBA")
+            .with_password("SYSDBA001")
+            .with_schema("SYSDBA")
+            .with_login_timeout(Duration::from_secs(5))
+            .with_charset("PG_UTF8")
+            .with_case_sensitive(true);
+        assert_eq!(
+            config.to_connection_string(),
+            "Driver={DM8};Server=0.0.0.0;UID=SYSDBA;PWD=SYSDBA001;SCHEMA=SYSDBA;LOGIN_TIMEOUT=5;\
+             CHARACTER_CODE=PG_UTF8;CASE_SENSITIVE=1;"
+        );
+        assert!(config.options().case_sensitive);
+
+        let config = ConnectionConfig::new(SupportDatabase::Pg, "PostgreSQL Unicode")
+            .with_server("localhost")
+            .with_port(5432)
+            .with_catalog("test")
+            .with_user("postgres")
+            .with_password(" p}w ")
+            .with_schema("public")
+            .with_charset("UTF8")
+            .with_login_timeout(Duration::from_secs(10))
+            .with_attribute("SSLmode", "require");
+        assert_eq!(
+            config.to_connection_string(),
+            "Driver={PostgreSQL Unicode};Server=localhost;Port=5432;Database=test;UID=postgres;\
+             PWD={ p}}w };ConnSettings={SET search_path TO public;SET client_encoding TO 'UTF8'};\
+             pqopt={connect_timeout=10};SSLmode=require;"
+        );
+
+        let config = ConnectionConfig::new(SupportDatabase::Mysql, "MySQL")
+            .with_server("localhost")
+            .with_schema("test")
+            .with_user("root")
+            .with_charset("utf8mb4");
+        assert_eq!(
+            config.to_connection_string(),
+            "Driver={MySQL};Server=localhost;Database=test;UID=root;CHARSET=utf8mb4;"
+        );
+    }
+
+    #[test]
+    fn test_debug_hide_password() {
+        let config = ConnectionConfig::new(SupportDatabase::Dameng, "DM8").with_password("secret");
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("password: Some(\"******\")"));
+    }
+}
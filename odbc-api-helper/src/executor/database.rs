@@ -1,27 +1,47 @@
+use crate::bridge::mapping::TypeMapping;
+use crate::bridge::pg_catalog::PgCatalog;
+use crate::bridge::pg_dialect::{pg_to_dameng_statement, DamengSql};
+use crate::diff::SchemaDesc;
+use crate::error::OdbcHelperError;
+use crate::executor::catalog::{
+    fetch_text_rows, opt_text_at, primary_keys, sql_primary_keys, sql_tables, text_at,
+    CatalogResult, CatalogSchema, CatalogSequence, CatalogTable, CatalogView, TextRows,
+};
+use crate::executor::connection::ConnectionConfig;
 use crate::executor::execute::ExecResult;
 use crate::executor::query::QueryResult;
-use crate::executor::statement::StatementInput;
-use crate::executor::table::TableDescResult;
+use crate::executor::statement::{bind_by_placeholders, Statement, StatementInput};
+use crate::executor::table::{sql_columns, OdbcTableDesc, OdbcTableItem, TableDescResult};
 use crate::executor::SupportDatabase;
+use crate::export::{export_cursor, ExportOptions};
 use crate::extension::odbc::{OdbcColumn, OdbcColumnItem};
+use crate::extension::pg::{PgQueryResult, PgValueInput};
+use crate::import::{import_rows, ImportColumn, ImportFile, ImportOptions, ImportResult};
 use crate::{Convert, TryConvert};
+use dameng_helper::catalog::{list_objects_sql, DmObjectType, LIST_SCHEMAS_SQL};
+use dameng_helper::ddl::quote_ident;
+use dameng_helper::table::DmTableDesc;
 use dameng_helper::DmAdapter;
 use either::Either;
 use odbc_api::buffers::{AnySlice, BufferDescription, ColumnarAnyBuffer};
 use odbc_api::handles::StatementImpl;
 use odbc_api::{
-    ColumnDescription, Connection, Cursor, CursorImpl, ParameterCollectionRef, ResultSetMetadata,
+    ColumnDescription, Connection, Cursor, CursorImpl, Environment, ParameterCollectionRef,
+    ResultSetMetadata,
 };
+use pg_helper::table::PgTableDesc;
+use std::io::Write;
 use std::ops::IndexMut;
+use std::path::Path;
 
 pub trait ConnectionTrait {
     /// Execute a `[Statement]`  INSETT,UPDATE,DELETE
-    fn execute<S>(&self, stmt: S) -> anyhow::Result<ExecResult>
+    fn execute<S>(&self, stmt: S) -> Result<ExecResult, OdbcHelperError>
     where
         S: StatementInput;
 
     /// Execute a `[Statement]` and return a collection Vec<[QueryResult]> on success
-    fn query<S>(&self, stmt: S) -> anyhow::Result<QueryResult>
+    fn query<S>(&self, stmt: S) -> Result<QueryResult, OdbcHelperError>
     where
         S: StatementInput;
 
@@ -29,17 +49,32 @@ pub trait ConnectionTrait {
         &self,
         db_name: &str,
         table_names: Vec<String>,
-    ) -> anyhow::Result<TableDescResult>;
+    ) -> Result<TableDescResult, OdbcHelperError>;
+
+    /// List all schemas of the connected database
+    fn list_schemas(&self) -> Result<CatalogResult<CatalogSchema>, OdbcHelperError>;
+
+    /// List all tables in `schema`
+    fn list_tables(&self, schema: &str) -> Result<CatalogResult<CatalogTable>, OdbcHelperError>;
+
+    /// List all views in `schema`
+    fn list_views(&self, schema: &str) -> Result<CatalogResult<CatalogView>, OdbcHelperError>;
+
+    /// List all sequences in `schema`
+    fn list_sequences(
+        &self,
+        schema: &str,
+    ) -> Result<CatalogResult<CatalogSequence>, OdbcHelperError>;
 
     // begin transaction
-    fn begin(&self) -> anyhow::Result<()>;
+    fn begin(&self) -> Result<(), OdbcHelperError>;
 
     // finish transaction
-    fn finish(&self) -> anyhow::Result<()>;
+    fn finish(&self) -> Result<(), OdbcHelperError>;
 
-    fn commit(&self) -> anyhow::Result<()>;
+    fn commit(&self) -> Result<(), OdbcHelperError>;
 
-    fn rollback(&self) -> anyhow::Result<()>;
+    fn rollback(&self) -> Result<(), OdbcHelperError>;
 }
 
 #[allow(missing_debug_implementations)]
@@ -58,6 +93,10 @@ pub struct Options {
     // false:all column name convert uppercase
     // true: ignore，keep original column name
     pub case_sensitive: bool,
+    // user defined dameng to postgres type mapping, default is empty.
+    pub type_mapping: TypeMapping,
+    // translate postgres sql to the database dialect before `execute` and `query`, default is false.
+    pub translate_pg_sql: bool,
 }
 
 impl Options {
@@ -75,6 +114,8 @@ impl Options {
             max_str_len: Self::MAX_STR_LEN,
             max_binary_len: Self::MAX_BINARY_LEN,
             case_sensitive: false,
+            type_mapping: TypeMapping::default(),
+            translate_pg_sql: false,
         }
     }
 
@@ -97,24 +138,32 @@ impl Options {
 }
 
 impl<'a> ConnectionTrait for OdbcDbConnection<'a> {
-    fn execute<S>(&self, stmt: S) -> anyhow::Result<ExecResult>
+    fn execute<S>(&self, stmt: S) -> Result<ExecResult, OdbcHelperError>
     where
         S: StatementInput,
     {
-        let sql = stmt.to_sql().to_string();
+        let DamengSql { sql, placeholders } = self.translate_sql(stmt.to_sql())?;
         match stmt.values()? {
+            Either::Left(params) if !placeholders.is_empty() => {
+                let params = bind_by_placeholders(&params, &placeholders)?;
+                self.exec_result(sql, &params[..])
+            }
             Either::Left(params) => self.exec_result(sql, &params[..]),
             Either::Right(()) => self.exec_result(sql, ()),
         }
     }
 
-    fn query<S>(&self, stmt: S) -> anyhow::Result<QueryResult>
+    fn query<S>(&self, stmt: S) -> Result<QueryResult, OdbcHelperError>
     where
         S: StatementInput,
     {
-        let sql = stmt.to_sql().to_string();
+        let DamengSql { sql, placeholders } = self.translate_sql(stmt.to_sql())?;
 
         match stmt.values()? {
+            Either::Left(params) if !placeholders.is_empty() => {
+                let params = bind_by_placeholders(&params, &placeholders)?;
+                self.query_result(&sql, &params[..])
+            }
             Either::Left(params) => self.query_result(&sql, &params[..]),
             Either::Right(()) => self.query_result(&sql, ()),
         }
@@ -124,45 +173,399 @@ impl<'a> ConnectionTrait for OdbcDbConnection<'a> {
         &self,
         db_name: &str,
         table_names: Vec<String>,
-    ) -> anyhow::Result<TableDescResult> {
+    ) -> Result<TableDescResult, OdbcHelperError> {
         self.table_desc(db_name, table_names)
     }
 
-    fn begin(&self) -> anyhow::Result<()> {
-        Ok(self.conn.set_autocommit(false)?)
+    fn list_schemas(&self) -> Result<CatalogResult<CatalogSchema>, OdbcHelperError> {
+        let schemas: Vec<CatalogSchema> = match self.options.database {
+            SupportDatabase::Dameng => self
+                .query_text_rows(LIST_SCHEMAS_SQL)?
+                .iter()
+                .map(|row| CatalogSchema {
+                    name: text_at(row, 0),
+                })
+                .collect(),
+            _ => {
+                // `SQL_ALL_SCHEMAS`: catalog and table name are empty string, schema name is `%`
+                let cursor = self
+                    .conn
+                    .tables("", "%", "", "")
+                    .map_err(|e| self.db_error(e))?;
+                let mut schemas: Vec<CatalogSchema> = vec![];
+                for row in fetch_text_rows(cursor, self.options.max_batch_size)? {
+                    let name = text_at(&row, sql_tables::TABLE_SCHEM);
+                    if !name.is_empty() && !schemas.iter().any(|x| x.name == name) {
+                        schemas.push(CatalogSchema { name });
+                    }
+                }
+                schemas
+            }
+        };
+        Ok(CatalogResult::new(schemas))
+    }
+
+    fn list_tables(&self, schema: &str) -> Result<CatalogResult<CatalogTable>, OdbcHelperError> {
+        let tables = self
+            .list_objects(schema, DmObjectType::Table, sql_tables::TABLE_TYPE_TABLE)?
+            .into_iter()
+            .map(|(schema, name, remarks)| CatalogTable {
+                schema,
+                name,
+                remarks,
+            })
+            .collect();
+        Ok(CatalogResult::new(tables))
+    }
+
+    fn list_views(&self, schema: &str) -> Result<CatalogResult<CatalogView>, OdbcHelperError> {
+        let views = self
+            .list_objects(schema, DmObjectType::View, sql_tables::TABLE_TYPE_VIEW)?
+            .into_iter()
+            .map(|(schema, name, remarks)| CatalogView {
+                schema,
+                name,
+                remarks,
+            })
+            .collect();
+        Ok(CatalogResult::new(views))
+    }
+
+    fn list_sequences(
+        &self,
+        schema: &str,
+    ) -> Result<CatalogResult<CatalogSequence>, OdbcHelperError> {
+        let sequences = self
+            .list_objects(
+                schema,
+                DmObjectType::Sequence,
+                sql_tables::TABLE_TYPE_SEQUENCE,
+            )?
+            .into_iter()
+            .map(|(schema, name, _)| CatalogSequence { schema, name })
+            .collect();
+        Ok(CatalogResult::new(sequences))
+    }
+
+    fn begin(&self) -> Result<(), OdbcHelperError> {
+        self.conn
+            .set_autocommit(false)
+            .map_err(|e| self.db_error(e))
     }
 
-    fn finish(&self) -> anyhow::Result<()> {
-        self.conn.set_autocommit(true)?;
-        Ok(())
+    fn finish(&self) -> Result<(), OdbcHelperError> {
+        self.conn.set_autocommit(true).map_err(|e| self.db_error(e))
     }
 
-    fn commit(&self) -> anyhow::Result<()> {
-        self.conn.commit()?;
-        Ok(())
+    fn commit(&self) -> Result<(), OdbcHelperError> {
+        self.conn.commit().map_err(|e| self.db_error(e))
     }
 
-    fn rollback(&self) -> anyhow::Result<()> {
-        self.conn.rollback()?;
-        Ok(())
+    fn rollback(&self) -> Result<(), OdbcHelperError> {
+        self.conn.rollback().map_err(|e| self.db_error(e))
     }
 }
 
 impl<'a> OdbcDbConnection<'a> {
-    pub fn new(conn: Connection<'a>, options: Options) -> anyhow::Result<Self> {
+    pub fn new(conn: Connection<'a>, options: Options) -> Result<Self, OdbcHelperError> {
         let options = options.check();
         let connection = Self { conn, options };
         Ok(connection)
     }
 
+    /// Connect with [`ConnectionConfig`] and the options of config
+    pub fn connect(
+        env: &'a Environment,
+        config: &ConnectionConfig,
+    ) -> Result<Self, OdbcHelperError> {
+        Self::connect_with_options(env, config, config.options())
+    }
+
+    /// Connect with [`ConnectionConfig`], `options.database` should be the same as config
+    pub fn connect_with_options(
+        env: &'a Environment,
+        config: &ConnectionConfig,
+        options: Options,
+    ) -> Result<Self, OdbcHelperError> {
+        debug!("connect:{:?}", config);
+        let conn = env
+            .connect_with_connection_string(&config.to_connection_string())
+            .map_err(|e| OdbcHelperError::from_odbc(&config.database, e))?;
+        Self::new(conn, options)
+    }
+
+    /// Get primary keys, unique constraints and indexes of tables, only support dameng database.
+    /// The result could fill `DmTableDesc` by `DmTableDesc::with_keys`.
+    pub fn show_table_keys(
+        &self,
+        db_name: &str,
+        table_names: Vec<String>,
+    ) -> Result<TableDescResult, OdbcHelperError> {
+        let db = &self.options.database;
+        match db {
+            SupportDatabase::Dameng => {
+                let describe = CursorImpl::get_table_keys_sql(
+                    table_names,
+                    db_name,
+                    self.options.case_sensitive,
+                );
+                let cursor = self.query_cursor(Statement::from(&describe))?;
+                Ok(cursor.get_table_desc(describe)?)
+            }
+            _ => Err(OdbcHelperError::unsupported_database(db, "show table keys")),
+        }
+    }
+
+    /// Build emulated postgres `pg_catalog` and `information_schema` of all tables in `schema`.
+    /// `database` is the catalog name shown to postgres clients.
+    pub fn pg_catalog(&self, database: &str, schema: &str) -> Result<PgCatalog, OdbcHelperError> {
+        let table_names: Vec<String> = self
+            .list_tables(schema)?
+            .items
+            .into_iter()
+            .map(|x| x.name)
+            .collect();
+        let desc: PgTableDesc = if table_names.is_empty() {
+            PgTableDesc::default()
+        } else {
+            (self.show_table(schema, table_names)?, &self.options).try_convert()?
+        };
+        Ok(PgCatalog::new(
+            database,
+            schema,
+            &desc,
+            self.options.case_sensitive,
+        ))
+    }
+
+    /// Query as postgres. Queries of emulated `pg_catalog` and `information_schema` are answered
+    /// by `catalog` built with [`Self::pg_catalog`], others are executed by the database.
+    pub fn pg_query<S: StatementInput>(
+        &self,
+        catalog: &PgCatalog,
+        stmt: S,
+    ) -> Result<PgQueryResult, OdbcHelperError> {
+        if let Some(result) = catalog.query(stmt.to_sql())? {
+            return Ok(result);
+        }
+        Ok(self.query(stmt)?.into())
+    }
+
+    /// Describe tables in `schema` as database neutral [`SchemaDesc`], empty `table_names` means
+    /// all tables. Only dameng and postgres databases are supported.
+    pub fn schema_desc(
+        &self,
+        schema: &str,
+        table_names: Vec<String>,
+    ) -> Result<SchemaDesc, OdbcHelperError> {
+        let table_names = if table_names.is_empty() {
+            self.list_tables(schema)?
+                .items
+                .into_iter()
+                .map(|x| x.name)
+                .collect()
+        } else {
+            table_names
+        };
+        if table_names.is_empty() {
+            return Ok(SchemaDesc::default());
+        }
+
+        let desc = self.show_table(schema, table_names)?;
+        (desc, &self.options).try_convert()
+    }
+
+    /// Quote schema and table name given by user, see [`OdbcDbConnection::quote_ident`].
+    pub fn quote_table(&self, schema: &str, table: &str) -> String {
+        format!("{}.{}", self.quote_ident(schema), self.quote_ident(table))
+    }
+
+    /// Quote identifier given by user, the name is folded the same way as
+    /// [`Options::case_sensitive`] so that it matches the names of `show_table` and generated ddl.
+    pub fn quote_ident(&self, name: &str) -> String {
+        match self.options.database {
+            SupportDatabase::Dameng if self.options.case_sensitive => quote_ident(name),
+            SupportDatabase::Dameng => quote_ident(&name.to_uppercase()),
+            SupportDatabase::Pg => pg_helper::ddl::quote_ident(name, self.options.case_sensitive),
+            SupportDatabase::Mysql => self.quote_column(name),
+        }
+    }
+
+    /// Quote column name reported by the database as is.
+    pub fn quote_column(&self, name: &str) -> String {
+        match self.options.database {
+            SupportDatabase::Mysql => format!("`{}`", name.replace('`', "``")),
+            _ => quote_ident(name),
+        }
+    }
+
+    /// Export result set of sql to parquet or arrow IPC, return the exported rows.
+    /// Arrow types are mapped from the odbc column types, see [`crate::export::arrow_type`].
+    pub fn export_query<S: StatementInput, W: Write + Send>(
+        &self,
+        stmt: S,
+        writer: W,
+        export_options: &ExportOptions,
+    ) -> Result<usize, OdbcHelperError> {
+        let cursor = self.query_cursor(stmt)?;
+        export_cursor(cursor, None, writer, &self.options, export_options)
+    }
+
+    /// Export all rows of table to parquet or arrow IPC, return the exported rows.
+    /// Dameng columns are described by `show_table`, so that the declared decimal precision is kept.
+    pub fn export_table<W: Write + Send>(
+        &self,
+        schema: &str,
+        table: &str,
+        writer: W,
+        export_options: &ExportOptions,
+    ) -> Result<usize, OdbcHelperError> {
+        match self.options.database {
+            SupportDatabase::Dameng => {
+                let desc: DmTableDesc = self
+                    .show_table(schema, vec![table.to_string()])?
+                    .try_convert()?;
+                let mut items = desc
+                    .data
+                    .into_values()
+                    .next()
+                    .ok_or_else(|| sql_error!("table {}.{} not found", schema, table))?;
+                items.sort_by_key(|x| x.col_index);
+
+                let columns: Vec<String> =
+                    items.iter().map(|x| self.quote_column(&x.name)).collect();
+                let fields = items.iter().map(|x| x.convert()).collect();
+                let sql = format!(
+                    "SELECT {} FROM {}",
+                    columns.join(", "),
+                    self.quote_table(schema, table)
+                );
+                let cursor = self.query_cursor(sql)?;
+                export_cursor(cursor, Some(fields), writer, &self.options, export_options)
+            }
+            _ => self.export_query(
+                format!("SELECT * FROM {}", self.quote_table(schema, table)),
+                writer,
+                export_options,
+            ),
+        }
+    }
+
+    /// Columns of table described by `show_table` in column order, see [`ImportColumn`].
+    pub fn import_columns(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ImportColumn>, OdbcHelperError> {
+        let desc = self.show_table(schema, vec![table.to_string()])?;
+        let columns = match self.options.database {
+            SupportDatabase::Dameng => {
+                let desc: DmTableDesc = desc.try_convert()?;
+                let mut items: Vec<_> = desc.data.into_values().flatten().collect();
+                items.sort_by_key(|x| x.col_index);
+                items
+                    .iter()
+                    .map(|x| x.try_convert())
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            _ => {
+                let desc = OdbcTableDesc::new(desc.0, desc.1)?;
+                let mut items: Vec<_> = desc.data.into_values().flatten().collect();
+                items.sort_by_key(|x| x.ordinal);
+                items.iter().map(|x| x.convert()).collect()
+            }
+        };
+        if columns.is_empty() {
+            return Err(sql_error!("table {}.{} not found", schema, table));
+        }
+        Ok(columns)
+    }
+
+    /// Import csv or parquet file into table, the file header names the columns. Values are
+    /// coerced against the column types and inserted with array parameters of
+    /// `ImportOptions::batch_size` rows. Rows which could not be coerced or inserted are written to
+    /// `rejects` with the reason, see [`import_rows`]. It must run in a transaction, the batches
+    /// are inserted in savepoints.
+    pub fn import_file<P: AsRef<Path>, W: Write>(
+        &self,
+        schema: &str,
+        table: &str,
+        path: P,
+        rejects: W,
+        import_options: &ImportOptions,
+    ) -> Result<ImportResult, OdbcHelperError> {
+        let file = ImportFile::open(path, import_options)?;
+        let table_columns = self.import_columns(schema, table)?;
+        let columns = file
+            .headers
+            .iter()
+            .map(|header| {
+                table_columns
+                    .iter()
+                    .find(|x| {
+                        if self.options.case_sensitive {
+                            x.name == *header
+                        } else {
+                            x.name.eq_ignore_ascii_case(header)
+                        }
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        sql_error!("column {} not found in table {}.{}", header, schema, table)
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if columns.is_empty() {
+            return Err(sql_error!("import file has no columns"));
+        }
+
+        let names: Vec<String> = columns.iter().map(|x| self.quote_column(&x.name)).collect();
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            self.quote_table(schema, table),
+            names.join(", "),
+            vec!["?"; names.len()].join(", ")
+        );
+        debug!("import sql:{}", sql);
+        let mut prepared = self.conn.prepare(&sql).map_err(|e| self.db_error(e))?;
+        import_rows(
+            &self.conn,
+            &mut prepared,
+            &self.options.database,
+            &columns,
+            file,
+            rejects,
+            import_options,
+        )
+    }
+
+    /// Decode odbc error with diagnostic record, see [`OdbcHelperError::from_odbc`].
+    fn db_error(&self, error: odbc_api::Error) -> OdbcHelperError {
+        OdbcHelperError::from_odbc(&self.options.database, error)
+    }
+
+    /// Translate postgres sql to the database dialect if `Options::translate_pg_sql` is enabled.
+    fn translate_sql(&self, sql: &str) -> Result<DamengSql, OdbcHelperError> {
+        match self.options.database {
+            SupportDatabase::Dameng if self.options.translate_pg_sql => {
+                pg_to_dameng_statement(sql, self.options.case_sensitive)
+            }
+            _ => Ok(DamengSql {
+                sql: sql.to_string(),
+                placeholders: vec![],
+            }),
+        }
+    }
+
     fn exec_result<S: Into<String>>(
         &self,
         sql: S,
         params: impl ParameterCollectionRef,
-    ) -> anyhow::Result<ExecResult> {
-        let mut stmt = self.conn.preallocate()?;
-        stmt.execute(&sql.into(), params)?;
-        let row_op = stmt.row_count()?;
+    ) -> Result<ExecResult, OdbcHelperError> {
+        let mut stmt = self.conn.preallocate().map_err(|e| self.db_error(e))?;
+        stmt.execute(&sql.into(), params)
+            .map_err(|e| self.db_error(e))?;
+        let row_op = stmt.row_count().map_err(|e| self.db_error(e))?;
         let result = row_op
             .map(|r| ExecResult { rows_affected: r })
             .unwrap_or_default();
@@ -173,30 +576,38 @@ impl<'a> OdbcDbConnection<'a> {
         &self,
         sql: &str,
         params: impl ParameterCollectionRef,
-    ) -> anyhow::Result<QueryResult> {
+    ) -> Result<QueryResult, OdbcHelperError> {
         let mut cursor = self
             .conn
-            .execute(sql, params)?
-            .ok_or_else(|| anyhow!("query error"))?;
+            .execute(sql, params)
+            .map_err(|e| self.db_error(e))?
+            .ok_or(OdbcHelperError::NoResultSet)?;
 
-        let mut query_result = Self::get_cursor_columns(&mut cursor)?;
+        let mut query_result = self.get_cursor_columns(&mut cursor)?;
         debug!("columns:{:?}", query_result.columns);
 
-        let descs = query_result.columns.iter().map(|c| {
-            <(&OdbcColumn, &Options) as TryConvert<BufferDescription>>::try_convert((
-                c,
-                &self.options,
-            ))
-            .unwrap()
-        });
+        let descs = query_result
+            .columns
+            .iter()
+            .map(|c| {
+                <(&OdbcColumn, &Options) as TryConvert<BufferDescription>>::try_convert((
+                    c,
+                    &self.options,
+                ))
+                .map_err(|e| conversion_error!("{}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         let row_set_buffer =
-            ColumnarAnyBuffer::try_from_description(self.options.max_batch_size, descs).unwrap();
+            ColumnarAnyBuffer::try_from_description(self.options.max_batch_size, descs.into_iter())
+                .map_err(|e| self.db_error(e))?;
 
-        let mut row_set_cursor = cursor.bind_buffer(row_set_buffer).unwrap();
+        let mut row_set_cursor = cursor
+            .bind_buffer(row_set_buffer)
+            .map_err(|e| self.db_error(e))?;
 
         let mut total_row = vec![];
-        while let Some(row_set) = row_set_cursor.fetch()? {
+        while let Some(row_set) = row_set_cursor.fetch().map_err(|e| self.db_error(e))? {
             for index in 0..query_result.columns.len() {
                 let column_view: AnySlice = row_set.column(index);
                 let column_types: Vec<OdbcColumnItem> = column_view.convert();
@@ -216,14 +627,22 @@ impl<'a> OdbcDbConnection<'a> {
         Ok(query_result)
     }
 
-    fn get_cursor_columns(cursor: &mut CursorImpl<StatementImpl>) -> anyhow::Result<QueryResult> {
+    fn get_cursor_columns(
+        &self,
+        cursor: &mut CursorImpl<StatementImpl>,
+    ) -> Result<QueryResult, OdbcHelperError> {
         let mut query_result = QueryResult::default();
-        for index in 0..cursor.num_result_cols()?.try_into()? {
+        let num_cols = cursor.num_result_cols().map_err(|e| self.db_error(e))?;
+        for index in 0..num_cols.max(0) as u16 {
             let mut column_description = ColumnDescription::default();
-            cursor.describe_col(index + 1, &mut column_description)?;
+            cursor
+                .describe_col(index + 1, &mut column_description)
+                .map_err(|e| self.db_error(e))?;
 
             let column = OdbcColumn::new(
-                column_description.name_to_string()?,
+                column_description
+                    .name_to_string()
+                    .map_err(|e| conversion_error!(source = e, "invalid column name"))?,
                 column_description.data_type,
                 column_description.could_be_nullable(),
             );
@@ -232,24 +651,111 @@ impl<'a> OdbcDbConnection<'a> {
         Ok(query_result)
     }
 
+    /// Execute sql with bound parameters and return the cursor, which could be streamed by
+    /// [`crate::StreamPrint`]. The sql is executed as is, without postgres translation.
+    pub fn query_cursor<S: StatementInput>(
+        &self,
+        stmt: S,
+    ) -> Result<CursorImpl<StatementImpl<'_>>, OdbcHelperError> {
+        let sql = stmt.to_sql().to_string();
+        let cursor = match stmt.values()? {
+            Either::Left(params) => self.conn.execute(&sql, &params[..]),
+            Either::Right(()) => self.conn.execute(&sql, ()),
+        }
+        .map_err(|e| self.db_error(e))?;
+        cursor.ok_or(OdbcHelperError::NoResultSet)
+    }
+
+    /// Execute catalog sql and fetch all rows as text
+    fn query_text_rows<S: StatementInput>(&self, stmt: S) -> Result<TextRows, OdbcHelperError> {
+        let cursor = self.query_cursor(stmt)?;
+        fetch_text_rows(cursor, self.options.max_batch_size)
+    }
+
+    /// List schema objects, return `(schema, name, remarks)`.
+    /// Dameng query system table `SYSOBJECTS`, other database use ODBC `SQLTables`.
+    fn list_objects(
+        &self,
+        schema: &str,
+        object_type: DmObjectType,
+        table_type: &str,
+    ) -> Result<Vec<(String, String, Option<String>)>, OdbcHelperError> {
+        let objects = match self.options.database {
+            SupportDatabase::Dameng => {
+                let stmt = Statement::new(
+                    list_objects_sql(object_type),
+                    vec![PgValueInput::Varchar(schema.to_string())],
+                );
+                self.query_text_rows(stmt)?
+                    .iter()
+                    .map(|row| (text_at(row, 0), text_at(row, 1), opt_text_at(row, 2)))
+                    .collect()
+            }
+            _ => {
+                let cursor = self
+                    .conn
+                    .tables("", schema, "%", table_type)
+                    .map_err(|e| self.db_error(e))?;
+                fetch_text_rows(cursor, self.options.max_batch_size)?
+                    .iter()
+                    .map(|row| {
+                        (
+                            text_at(row, sql_tables::TABLE_SCHEM),
+                            text_at(row, sql_tables::TABLE_NAME),
+                            opt_text_at(row, sql_tables::REMARKS),
+                        )
+                    })
+                    .collect()
+            }
+        };
+        Ok(objects)
+    }
+
     fn table_desc(
         &self,
         db_name: &str,
         table_names: Vec<String>,
-    ) -> anyhow::Result<TableDescResult> {
+    ) -> Result<TableDescResult, OdbcHelperError> {
         let db = &self.options.database;
         match db {
             SupportDatabase::Dameng => {
                 let describe =
                     CursorImpl::get_table_sql(table_names, db_name, self.options.case_sensitive);
-                let cursor = self
-                    .conn
-                    .execute(&describe.describe_sql, ())?
-                    .ok_or_else(|| anyhow!("query error"))?;
-                cursor.get_table_desc(describe)
+                let cursor = self.query_cursor(Statement::from(&describe))?;
+                Ok(cursor.get_table_desc(describe)?)
             }
             _ => {
-                bail!("current not support database:{:?}", db)
+                // Generic path based on ODBC catalog function `SQLColumns`
+                let mut desc = OdbcTableDesc::default();
+                for table_name in table_names.iter() {
+                    let cursor = self
+                        .conn
+                        .columns("", db_name, table_name, "%")
+                        .map_err(|e| self.db_error(e))?;
+                    let rows = fetch_text_rows(cursor, self.options.max_batch_size)?;
+                    let cursor = primary_keys(&self.conn, "", db_name, table_name)
+                        .map_err(|e| self.db_error(e))?;
+                    let keys = fetch_text_rows(cursor, self.options.max_batch_size)?;
+                    for row in rows {
+                        // The table name of `SQLColumns` is a search pattern, `_` match any
+                        // character. So filter out the tables which name is not equal.
+                        if !text_at(&row, sql_columns::TABLE_NAME).eq(table_name) {
+                            continue;
+                        }
+                        let mut item = OdbcTableItem::from_sql_columns(&row)?;
+                        item.primary_key = keys
+                            .iter()
+                            .find(|x| text_at(x, sql_primary_keys::COLUMN_NAME) == item.name)
+                            .and_then(|x| opt_text_at(x, sql_primary_keys::KEY_SEQ))
+                            .and_then(|x| x.parse().ok());
+                        desc.data
+                            .entry(item.table_name.to_owned())
+                            .or_default()
+                            .push(item);
+                    }
+                }
+                debug!("odbc table desc:{:?}", desc);
+                Ok(desc.into())
             }
         }
     }
@@ -1,12 +1,77 @@
+use crate::error::OdbcHelperError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub mod catalog;
+pub mod connection;
 pub mod database;
 pub mod execute;
 pub mod query;
 pub mod statement;
 pub mod table;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SupportDatabase {
     Dameng,
     Pg,
     Mysql,
 }
+
+impl SupportDatabase {
+    pub const ALL: [SupportDatabase; 3] = [Self::Dameng, Self::Pg, Self::Mysql];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dameng => "dameng",
+            Self::Pg => "pg",
+            Self::Mysql => "mysql",
+        }
+    }
+}
+
+impl Display for SupportDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Parse database kind ignoring case, `dm`, `postgres` and `postgresql` are also accepted.
+impl FromStr for SupportDatabase {
+    type Err = OdbcHelperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dameng" | "dm" => Ok(Self::Dameng),
+            "pg" | "postgres" | "postgresql" => Ok(Self::Pg),
+            "mysql" => Ok(Self::Mysql),
+            _ => Err(OdbcHelperError::UnsupportedDatabase {
+                database: s.to_string(),
+                operation: "parse",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_support_database_from_str() {
+        for database in SupportDatabase::ALL {
+            assert_eq!(
+                database.to_string().parse::<SupportDatabase>().unwrap(),
+                database
+            );
+        }
+        assert_eq!(
+            "DM".parse::<SupportDatabase>().unwrap(),
+            SupportDatabase::Dameng
+        );
+        assert_eq!(
+            "postgres".parse::<SupportDatabase>().unwrap(),
+            SupportDatabase::Pg
+        );
+        assert!("oracle".parse::<SupportDatabase>().is_err());
+    }
+}
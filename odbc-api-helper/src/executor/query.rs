@@ -1,6 +1,7 @@
 use crate::extension::odbc::{OdbcColumn, OdbcColumnItem};
+use odbc_common::data_type::alignment;
 use odbc_common::print_table::Print;
-use odbc_common::{StyledString, Table, TableTheme, TextStyle};
+use odbc_common::{PrintOptions, TableData};
 
 #[derive(Debug, Default)]
 pub struct QueryResult {
@@ -11,23 +12,27 @@ pub struct QueryResult {
 }
 
 impl Print for QueryResult {
-    fn convert_table(self) -> anyhow::Result<Table> {
-        let headers: Vec<StyledString> = self
+    fn table_data(self) -> anyhow::Result<TableData> {
+        self.table_data_with(&PrintOptions::default())
+    }
+
+    /// Values are rendered by column type, numbers are right aligned.
+    fn table_data_with(self, options: &PrintOptions) -> anyhow::Result<TableData> {
+        let headers = self.columns.iter().map(|x| x.name.to_string()).collect();
+        let column_types = self.columns.iter().map(|x| x.type_description()).collect();
+        let alignments = self
             .columns
             .iter()
-            .map(|x| StyledString::new(x.name.to_string(), TextStyle::default_header()))
+            .map(|x| alignment(&x.data_type))
             .collect();
 
         let rows = self
             .data
             .iter()
-            .map(|x| {
-                x.iter()
-                    .map(|y| y.to_string())
-                    .map(|y| StyledString::new(y, TextStyle::basic_left()))
-                    .collect::<Vec<_>>()
-            })
+            .map(|x| x.iter().map(|y| y.display(options.binary_format)).collect())
             .collect();
-        Ok(Table::new(headers, rows, TableTheme::rounded()))
+        Ok(TableData::new(headers, rows)
+            .with_column_types(column_types)
+            .with_alignments(alignments))
     }
 }
@@ -1,7 +1,12 @@
 use crate::error::OdbcHelperError;
+use crate::extension::pg::PgValueInput;
 use crate::TryConvert;
+use dameng_helper::TableSqlDescribe;
 use either::Either;
-use odbc_api::parameter::InputParameter;
+use odbc_api::handles::{CData, DataType, HasDataType};
+use odbc_api::parameter::{CElement, InputParameter};
+use odbc_api::sys::CDataType;
+use std::ffi::c_void;
 use std::fmt::Debug;
 
 pub(crate) type EitherBoxParams = Either<Vec<Box<dyn InputParameter>>, ()>;
@@ -45,6 +50,20 @@ where
     }
 }
 
+/// Dameng describe sql, all values are bound as varchar
+impl From<&TableSqlDescribe> for Statement<PgValueInput> {
+    fn from(describe: &TableSqlDescribe) -> Self {
+        Statement::new(
+            describe.describe_sql.as_str(),
+            describe
+                .values
+                .iter()
+                .map(|x| PgValueInput::Varchar(x.to_string()))
+                .collect(),
+        )
+    }
+}
+
 impl SqlValue for &str {
     fn to_value(self) -> Either<Box<dyn InputParameter>, ()> {
         Either::Right(())
@@ -131,9 +150,8 @@ impl<T: StatementInput> TryConvert<EitherBoxParams> for T {
                     .into_iter()
                     .map(|v| v.to_value())
                     .map(|x| {
-                        x.left().ok_or_else(|| {
-                            OdbcHelperError::SqlParamsError("value not include empty tuple".into())
-                        })
+                        x.left()
+                            .ok_or_else(|| params_error!("value not include empty tuple"))
                     })
                     .collect();
                 Ok(Either::Left(params?))
@@ -142,3 +160,55 @@ impl<T: StatementInput> TryConvert<EitherBoxParams> for T {
         }
     }
 }
+
+/// Borrowed parameter, the same parameter can be bound to several placeholders.
+pub(crate) struct ParamRef<'a>(&'a dyn InputParameter);
+
+unsafe impl CData for ParamRef<'_> {
+    fn cdata_type(&self) -> CDataType {
+        self.0.cdata_type()
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        self.0.indicator_ptr()
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.0.value_ptr()
+    }
+
+    fn buffer_length(&self) -> isize {
+        self.0.buffer_length()
+    }
+}
+
+impl HasDataType for ParamRef<'_> {
+    fn data_type(&self) -> DataType {
+        self.0.data_type()
+    }
+}
+
+unsafe impl CElement for ParamRef<'_> {}
+
+/// Arrange parameters in order of placeholders, `placeholders` is the 1-based parameter number of
+/// each `?`, E.g: `[1, 2, 1]` binds the first parameter twice.
+pub(crate) fn bind_by_placeholders<'a>(
+    params: &'a [Box<dyn InputParameter>],
+    placeholders: &[usize],
+) -> Result<Vec<ParamRef<'a>>, OdbcHelperError> {
+    placeholders
+        .iter()
+        .map(|n| {
+            n.checked_sub(1)
+                .and_then(|index| params.get(index))
+                .map(|x| ParamRef(x.as_ref()))
+                .ok_or_else(|| {
+                    params_error!(
+                        "placeholder ${} has no value, {} values given",
+                        n,
+                        params.len()
+                    )
+                })
+        })
+        .collect()
+}
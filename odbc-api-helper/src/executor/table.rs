@@ -1,4 +1,10 @@
-use odbc_common::{Print, StyledString, Table, TableTheme, TextStyle};
+use crate::error::OdbcHelperError;
+use crate::executor::catalog::{opt_text_at, text_at};
+use odbc_api::sys::SqlDataType;
+use odbc_api::DataType;
+use odbc_common::{Print, TableData};
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 pub type TableDescResult = (Vec<String>, Vec<Vec<String>>);
 
@@ -18,23 +24,231 @@ impl From<TableDescResult> for TableDescResultInner {
 }
 
 impl Print for TableDescResultInner {
-    fn convert_table(self) -> anyhow::Result<Table> {
-        let headers: Vec<StyledString> = self
-            .column_names
+    fn table_data(self) -> anyhow::Result<TableData> {
+        Ok(TableData::from((self.column_names, self.columns_desc)))
+    }
+}
+
+/// ODBC `SQLColumns` result set columns used by database-neutral table describe
+pub(crate) mod sql_columns {
+    pub const TABLE_NAME: usize = 2;
+    pub const COLUMN_NAME: usize = 3;
+    pub const DATA_TYPE: usize = 4;
+    pub const TYPE_NAME: usize = 5;
+    pub const COLUMN_SIZE: usize = 6;
+    pub const DECIMAL_DIGITS: usize = 8;
+    pub const NULLABLE: usize = 10;
+    pub const COLUMN_DEF: usize = 12;
+    pub const ORDINAL_POSITION: usize = 16;
+
+    /// `NULLABLE` column value `SQL_NO_NULLS`
+    pub const SQL_NO_NULLS: &str = "0";
+}
+
+/// Database-neutral table column describe, filled by ODBC catalog function `SQLColumns`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OdbcTableItem {
+    pub table_name: String,
+    pub name: String,
+    /// ODBC sql data type, E.g: `SQL_VARCHAR` is 12
+    pub data_type: i16,
+    /// Data source dependent data type name
+    pub type_name: String,
+    pub column_size: Option<usize>,
+    pub decimal_digits: Option<i16>,
+    pub nullable: bool,
+    pub default_val: Option<String>,
+    /// Column ordinal position, start with 1
+    pub ordinal: usize,
+    /// Column sequence number in primary key, start with 1. `None` is not a primary key column
+    pub primary_key: Option<usize>,
+}
+
+impl OdbcTableItem {
+    pub const HEADERS: [&'static str; 10] = [
+        "TABLE_NAME",
+        "COLUMN_NAME",
+        "DATA_TYPE",
+        "TYPE_NAME",
+        "COLUMN_SIZE",
+        "DECIMAL_DIGITS",
+        "NULLABLE",
+        "COLUMN_DEF",
+        "ORDINAL_POSITION",
+        "KEY_SEQ",
+    ];
+
+    /// Parse one row of `SQLColumns` result set fetched as text. Names are kept as reported by
+    /// driver, `primary_key` is filled from `SQLPrimaryKeys` afterwards.
+    pub(crate) fn from_sql_columns(row: &[Option<String>]) -> Result<Self, OdbcHelperError> {
+        Ok(Self {
+            table_name: text_at(row, sql_columns::TABLE_NAME),
+            name: text_at(row, sql_columns::COLUMN_NAME),
+            data_type: parse_metadata(&text_at(row, sql_columns::DATA_TYPE))?,
+            type_name: text_at(row, sql_columns::TYPE_NAME),
+            column_size: opt_text_at(row, sql_columns::COLUMN_SIZE)
+                .map(|x| parse_metadata(&x))
+                .transpose()?,
+            decimal_digits: opt_text_at(row, sql_columns::DECIMAL_DIGITS)
+                .map(|x| parse_metadata(&x))
+                .transpose()?,
+            nullable: text_at(row, sql_columns::NULLABLE) != sql_columns::SQL_NO_NULLS,
+            default_val: opt_text_at(row, sql_columns::COLUMN_DEF),
+            ordinal: parse_metadata(&text_at(row, sql_columns::ORDINAL_POSITION))?,
+            primary_key: None,
+        })
+    }
+
+    /// Get `odbc_api::DataType` from sql data type, column size and decimal digits
+    pub fn odbc_data_type(&self) -> DataType {
+        DataType::new(
+            SqlDataType(self.data_type),
+            self.column_size.unwrap_or_default(),
+            self.decimal_digits.unwrap_or_default(),
+        )
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<String> {
+        vec![
+            self.table_name.to_string(),
+            self.name.to_string(),
+            self.data_type.to_string(),
+            self.type_name.to_string(),
+            self.column_size.map(|x| x.to_string()).unwrap_or_default(),
+            self.decimal_digits
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+            self.nullable.to_string(),
+            self.default_val.clone().unwrap_or_default(),
+            self.ordinal.to_string(),
+            self.primary_key.map(|x| x.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+fn parse_metadata<T>(value: &str) -> Result<T, OdbcHelperError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value
+        .parse::<T>()
+        .map_err(|e| metadata_error!(source = e, "invalid column metadata:{}", value))
+}
+
+/// Database-neutral table describe, key is table name
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OdbcTableDesc {
+    pub data: BTreeMap<String, Vec<OdbcTableItem>>,
+}
+
+impl OdbcTableDesc {
+    pub fn new(headers: Vec<String>, data: Vec<Vec<String>>) -> Result<Self, OdbcHelperError> {
+        let index_of = |name: &str| -> Result<usize, OdbcHelperError> {
+            headers
+                .iter()
+                .position(|x| x == name)
+                .ok_or_else(|| metadata_error!("table describe header `{}` not found", name))
+        };
+        let indexes = OdbcTableItem::HEADERS
             .iter()
-            .map(|x| StyledString::new(x.to_string(), TextStyle::default_header()))
-            .collect();
+            .map(|x| index_of(x))
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let mut data_map: BTreeMap<String, Vec<OdbcTableItem>> = BTreeMap::new();
+        for row in data {
+            let val = |i: usize| row[indexes[i]].as_str();
+            let opt_val = |i: usize| Some(val(i)).filter(|x| !x.is_empty());
+
+            let item = OdbcTableItem {
+                table_name: val(0).to_string(),
+                name: val(1).to_string(),
+                data_type: parse_metadata(val(2))?,
+                type_name: val(3).to_string(),
+                column_size: opt_val(4).map(parse_metadata).transpose()?,
+                decimal_digits: opt_val(5).map(parse_metadata).transpose()?,
+                nullable: parse_metadata(val(6))?,
+                default_val: opt_val(7).map(|x| x.to_string()),
+                ordinal: parse_metadata(val(8))?,
+                primary_key: opt_val(9).map(parse_metadata).transpose()?,
+            };
+            data_map
+                .entry(item.table_name.to_owned())
+                .or_default()
+                .push(item);
+        }
+        Ok(Self { data: data_map })
+    }
+
+    /// Table names are kept as reported by driver, so without `case_sensitive` the key is
+    /// matched ignoring case when there is no exact match.
+    pub fn get_data(&self, key: String, case_sensitive: bool) -> Option<&Vec<OdbcTableItem>> {
+        self.data.get(&key).or_else(|| {
+            if case_sensitive {
+                return None;
+            }
+            self.data
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&key))
+                .map(|(_, items)| items)
+        })
+    }
+}
 
-        let rows = self
-            .columns_desc
+impl From<OdbcTableDesc> for TableDescResult {
+    fn from(desc: OdbcTableDesc) -> Self {
+        let headers = OdbcTableItem::HEADERS
             .iter()
-            .map(|x| {
-                x.iter()
-                    .map(|y| y.to_string())
-                    .map(|y| StyledString::new(y, TextStyle::basic_left()))
-                    .collect::<Vec<_>>()
-            })
+            .map(|x| x.to_string())
             .collect();
-        Ok(Table::new(headers, rows, TableTheme::rounded()))
+        let data = desc
+            .data
+            .values()
+            .flat_map(|items| items.iter().map(|x| x.to_vec()))
+            .collect();
+        (headers, data)
+    }
+}
+
+impl Print for OdbcTableDesc {
+    fn table_data(self) -> anyhow::Result<TableData> {
+        TableDescResultInner::from(TableDescResult::from(self)).table_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odbc_table_desc_convert() {
+        let row = |x: &[&str]| x.iter().map(|x| Some(x.to_string())).collect::<Vec<_>>();
+        let mut item = OdbcTableItem::from_sql_columns(&row(&[
+            "", "public", "t1", "price", "2", "numeric", "10", "12", "2", "10", "0", "", "0", "3",
+            "", "", "1", "NO",
+        ]))
+        .unwrap();
+        assert_eq!(item.table_name, "t1");
+        assert_eq!(item.name, "price");
+        assert_eq!(item.primary_key, None);
+        assert!(!item.nullable);
+        assert_eq!(item.default_val, Some("0".to_string()));
+        assert_eq!(
+            item.odbc_data_type(),
+            DataType::Numeric {
+                precision: 10,
+                scale: 2
+            }
+        );
+        item.default_val = None;
+        item.primary_key = Some(1);
+
+        let desc = OdbcTableDesc {
+            data: BTreeMap::from([("t1".to_string(), vec![item])]),
+        };
+        let result: TableDescResult = desc.clone().into();
+        assert_eq!(OdbcTableDesc::new(result.0, result.1).unwrap(), desc);
+        assert!(desc.get_data("T1".to_string(), false).is_some());
+        assert!(desc.get_data("T1".to_string(), true).is_none());
     }
 }
@@ -0,0 +1,682 @@
+use crate::error::OdbcHelperError;
+use crate::executor::database::Options;
+use crate::extension::odbc::OdbcColumn;
+use crate::Convert;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, Int8Array, RecordBatch, StringArray,
+    Time64MicrosecondArray, TimestampMicrosecondArray,
+};
+use arrow_ipc::writer::{FileWriter, IpcWriteOptions};
+use arrow_ipc::CompressionType;
+use arrow_schema::{DataType as ArrowDataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use dameng_helper::table::DmTableItem;
+use dameng_helper::DmDateType;
+use odbc_api::buffers::{AnySlice, BufferDesc, ColumnarAnyBuffer};
+use odbc_api::sys::{Date, Timestamp};
+use odbc_api::{ColumnDescription, Cursor, DataType, ResultSetMetadata};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Max precision of arrow `Decimal128`
+const MAX_DECIMAL_PRECISION: usize = 38;
+
+/// Max text length of time, E.g: `23:59:59.999999`
+const MAX_TIME_LEN: usize = 32;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Parquet,
+    /// Arrow IPC file format, also known as Feather V2
+    Ipc,
+}
+
+impl ExportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::Ipc => "arrow",
+        }
+    }
+
+    /// Format by file extension: `.parquet`, `.arrow`, `.ipc` or `.feather`
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|x| x.to_str())
+            .and_then(|x| x.parse().ok())
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = OdbcHelperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "parquet" => Ok(Self::Parquet),
+            "arrow" | "ipc" | "feather" => Ok(Self::Ipc),
+            _ => Err(export_error!("unknown export format:{}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportCompression {
+    Uncompressed,
+    /// Only supported by parquet
+    Snappy,
+    #[default]
+    Zstd,
+}
+
+impl ExportCompression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Uncompressed => "none",
+            Self::Snappy => "snappy",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+impl Display for ExportCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ExportCompression {
+    type Err = OdbcHelperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(Self::Uncompressed),
+            "snappy" => Ok(Self::Snappy),
+            "zstd" => Ok(Self::Zstd),
+            _ => Err(export_error!("unknown export compression:{}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub compression: ExportCompression,
+    /// Max rows of parquet row group
+    pub row_group_size: usize,
+}
+
+impl ExportOptions {
+    // Default row group size 1M rows, the same as parquet
+    pub const ROW_GROUP_SIZE: usize = 1024 * 1024;
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::default(),
+            compression: ExportCompression::default(),
+            row_group_size: Self::ROW_GROUP_SIZE,
+        }
+    }
+}
+
+/// Arrow type of odbc data type. Decimal over 38 digits, character and unknown types are `Utf8`.
+pub fn arrow_type(data_type: &DataType) -> ArrowDataType {
+    match *data_type {
+        DataType::Numeric { precision, scale } | DataType::Decimal { precision, scale } => {
+            decimal_type(precision, scale as usize)
+        }
+        DataType::Integer => ArrowDataType::Int32,
+        DataType::SmallInt => ArrowDataType::Int16,
+        DataType::TinyInt => ArrowDataType::Int8,
+        DataType::BigInt => ArrowDataType::Int64,
+        DataType::Real => ArrowDataType::Float32,
+        DataType::Float { precision } if precision <= 24 => ArrowDataType::Float32,
+        DataType::Float { .. } | DataType::Double => ArrowDataType::Float64,
+        DataType::Bit => ArrowDataType::Boolean,
+        DataType::Date => ArrowDataType::Date32,
+        DataType::Time { .. } => ArrowDataType::Time64(TimeUnit::Microsecond),
+        DataType::Timestamp { .. } => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+            ArrowDataType::Binary
+        }
+        DataType::Unknown
+        | DataType::Char { .. }
+        | DataType::WChar { .. }
+        | DataType::Varchar { .. }
+        | DataType::WVarchar { .. }
+        | DataType::LongVarchar { .. }
+        | DataType::Other { .. } => ArrowDataType::Utf8,
+    }
+}
+
+/// Arrow type of dameng column, time zone types and intervals are `Utf8`.
+pub fn dm_arrow_type(item: &DmTableItem) -> ArrowDataType {
+    match item.r#type {
+        // `LENGTH$` is precision, 0 means precision not specified and the default is 38
+        DmDateType::NUMERIC | DmDateType::NUMBER | DmDateType::DECIMAL => {
+            let precision = if item.length == 0 {
+                MAX_DECIMAL_PRECISION
+            } else {
+                item.length
+            };
+            decimal_type(precision, item.scale)
+        }
+        DmDateType::BIT | DmDateType::BOOL => ArrowDataType::Boolean,
+        DmDateType::INTEGER => ArrowDataType::Int32,
+        DmDateType::BIGINT => ArrowDataType::Int64,
+        DmDateType::TINYINT | DmDateType::BYTE => ArrowDataType::Int8,
+        DmDateType::SMALLINT => ArrowDataType::Int16,
+        DmDateType::REAL => ArrowDataType::Float32,
+        DmDateType::FLOAT | DmDateType::DOUBLE | DmDateType::DOUBLE_PRECISION => {
+            ArrowDataType::Float64
+        }
+        DmDateType::BINARY
+        | DmDateType::VARBINARY
+        | DmDateType::IMAGE
+        | DmDateType::BLOB
+        | DmDateType::BFILE => ArrowDataType::Binary,
+        DmDateType::DATE => ArrowDataType::Date32,
+        DmDateType::TIME => ArrowDataType::Time64(TimeUnit::Microsecond),
+        DmDateType::TIMESTAMP => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        _ => ArrowDataType::Utf8,
+    }
+}
+
+fn decimal_type(precision: usize, scale: usize) -> ArrowDataType {
+    if (1..=MAX_DECIMAL_PRECISION).contains(&precision) && scale <= precision {
+        ArrowDataType::Decimal128(precision as u8, scale as i8)
+    } else {
+        ArrowDataType::Utf8
+    }
+}
+
+impl Convert<Field> for &OdbcColumn {
+    fn convert(self) -> Field {
+        Field::new(&self.name, arrow_type(&self.data_type), self.nullable)
+    }
+}
+
+impl Convert<Field> for &DmTableItem {
+    fn convert(self) -> Field {
+        Field::new(&self.name, dm_arrow_type(self), self.nullable)
+    }
+}
+
+/// Parquet or arrow IPC file writer of record batches
+#[allow(missing_debug_implementations)]
+pub struct ExportWriter<W: Write + Send> {
+    inner: ExportWriterInner<W>,
+    rows: usize,
+}
+
+enum ExportWriterInner<W: Write + Send> {
+    Parquet(Box<ArrowWriter<W>>),
+    Ipc(FileWriter<W>),
+}
+
+impl<W: Write + Send> ExportWriter<W> {
+    pub fn try_new(
+        writer: W,
+        schema: SchemaRef,
+        options: &ExportOptions,
+    ) -> Result<Self, OdbcHelperError> {
+        let inner = match options.format {
+            ExportFormat::Parquet => {
+                let compression = match options.compression {
+                    ExportCompression::Uncompressed => Compression::UNCOMPRESSED,
+                    ExportCompression::Snappy => Compression::SNAPPY,
+                    ExportCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+                };
+                let properties = WriterProperties::builder()
+                    .set_compression(compression)
+                    .set_max_row_group_size(options.row_group_size.max(1))
+                    .build();
+                ExportWriterInner::Parquet(Box::new(ArrowWriter::try_new(
+                    writer,
+                    schema,
+                    Some(properties),
+                )?))
+            }
+            ExportFormat::Ipc => {
+                let compression = match options.compression {
+                    ExportCompression::Uncompressed => None,
+                    ExportCompression::Snappy => {
+                        return Err(export_error!("arrow ipc does not support snappy"))
+                    }
+                    ExportCompression::Zstd => Some(CompressionType::ZSTD),
+                };
+                let ipc_options = IpcWriteOptions::default().try_with_compression(compression)?;
+                ExportWriterInner::Ipc(FileWriter::try_new_with_options(
+                    writer,
+                    &schema,
+                    ipc_options,
+                )?)
+            }
+        };
+        Ok(Self { inner, rows: 0 })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<(), OdbcHelperError> {
+        match &mut self.inner {
+            ExportWriterInner::Parquet(writer) => writer.write(batch)?,
+            ExportWriterInner::Ipc(writer) => writer.write(batch)?,
+        }
+        self.rows += batch.num_rows();
+        Ok(())
+    }
+
+    /// Write file footer and return the written rows
+    pub fn finish(self) -> Result<usize, OdbcHelperError> {
+        match self.inner {
+            ExportWriterInner::Parquet(writer) => {
+                writer.close()?;
+            }
+            ExportWriterInner::Ipc(mut writer) => writer.finish()?,
+        }
+        Ok(self.rows)
+    }
+}
+
+/// Fetch all rows of cursor batch by batch, convert each batch to record batch and write it.
+/// `fields` override the arrow fields converted from cursor columns, E.g: fields converted from
+/// [`DmTableItem`] which have the declared decimal precision.
+/// Return the exported rows.
+pub fn export_cursor<C: Cursor, W: Write + Send>(
+    mut cursor: C,
+    fields: Option<Vec<Field>>,
+    writer: W,
+    options: &Options,
+    export_options: &ExportOptions,
+) -> Result<usize, OdbcHelperError> {
+    let columns = cursor_columns(&mut cursor)?;
+    let fields = match fields {
+        Some(fields) if fields.len() != columns.len() => {
+            return Err(export_error!(
+                "{} fields but cursor has {} columns",
+                fields.len(),
+                columns.len()
+            ))
+        }
+        Some(fields) => fields,
+        None => columns.iter().map(|x| x.convert()).collect(),
+    };
+    let schema = Arc::new(Schema::new(fields));
+    debug!("export schema:{:?}", schema);
+
+    let descs = columns
+        .iter()
+        .zip(schema.fields().iter())
+        .map(|(column, field)| buffer_desc(column, field.data_type(), options));
+    let buffer = ColumnarAnyBuffer::try_from_descs(options.max_batch_size, descs)?;
+    let mut row_set_cursor = cursor.bind_buffer(buffer)?;
+
+    let mut writer = ExportWriter::try_new(writer, schema.clone(), export_options)?;
+    // Long values are not truncated silently, increase `max_str_len` or `max_binary_len` instead
+    while let Some(row_set) = row_set_cursor
+        .fetch_with_truncation_check(true)
+        .map_err(truncation_error)?
+    {
+        let arrays = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| to_array(row_set.column(index), field))
+            .collect::<Result<Vec<_>, _>>()?;
+        writer.write(&RecordBatch::try_new(schema.clone(), arrays)?)?;
+    }
+    writer.finish()
+}
+
+fn cursor_columns<C: ResultSetMetadata>(
+    cursor: &mut C,
+) -> Result<Vec<OdbcColumn>, OdbcHelperError> {
+    let num_cols = cursor.num_result_cols()?;
+    let mut columns = vec![];
+    for index in 0..num_cols.max(0) as u16 {
+        let mut description = ColumnDescription::default();
+        cursor.describe_col(index + 1, &mut description)?;
+        columns.push(OdbcColumn::new(
+            description
+                .name_to_string()
+                .map_err(|e| conversion_error!(source = e, "invalid column name"))?,
+            description.data_type,
+            description.could_be_nullable(),
+        ));
+    }
+    Ok(columns)
+}
+
+/// Fetch buffer of column converted to `data_type`. Decimal and time are fetched as text, so
+/// that the precision is kept.
+fn buffer_desc(column: &OdbcColumn, data_type: &ArrowDataType, options: &Options) -> BufferDesc {
+    let limit = |len: Option<usize>, max: usize| match len {
+        Some(len) if len > 0 => len.min(max),
+        _ => max,
+    };
+    match data_type {
+        ArrowDataType::Boolean => BufferDesc::Bit { nullable: true },
+        ArrowDataType::Int8 => BufferDesc::I8 { nullable: true },
+        ArrowDataType::Int16 => BufferDesc::I16 { nullable: true },
+        ArrowDataType::Int32 => BufferDesc::I32 { nullable: true },
+        ArrowDataType::Int64 => BufferDesc::I64 { nullable: true },
+        ArrowDataType::Float32 => BufferDesc::F32 { nullable: true },
+        ArrowDataType::Float64 => BufferDesc::F64 { nullable: true },
+        ArrowDataType::Date32 => BufferDesc::Date { nullable: true },
+        ArrowDataType::Timestamp(..) => BufferDesc::Timestamp { nullable: true },
+        // sign, leading zero and decimal point
+        ArrowDataType::Decimal128(precision, _) => BufferDesc::Text {
+            max_str_len: *precision as usize + 3,
+        },
+        ArrowDataType::Time64(_) => BufferDesc::Text {
+            max_str_len: MAX_TIME_LEN,
+        },
+        ArrowDataType::Binary => BufferDesc::Binary {
+            length: limit(Some(column.data_type.column_size()), options.max_binary_len),
+        },
+        _ => match column.data_type {
+            DataType::WChar { .. } | DataType::WVarchar { .. } => BufferDesc::WText {
+                max_str_len: limit(column.data_type.utf16_len(), options.max_str_len),
+            },
+            _ => BufferDesc::Text {
+                max_str_len: limit(column.data_type.utf8_len(), options.max_str_len),
+            },
+        },
+    }
+}
+
+fn truncation_error(error: odbc_api::Error) -> OdbcHelperError {
+    match error {
+        odbc_api::Error::TooLargeValueForBuffer => export_error!(
+            "value is longer than fetch buffer, increase max_str_len or max_binary_len"
+        ),
+        e => e.into(),
+    }
+}
+
+fn to_array(slice: AnySlice, field: &Field) -> Result<ArrayRef, OdbcHelperError> {
+    let array: ArrayRef = match (field.data_type(), slice) {
+        (ArrowDataType::Boolean, AnySlice::NullableBit(x)) => {
+            Arc::new(BooleanArray::from_iter(x.map(|x| x.map(|x| x.as_bool()))))
+        }
+        (ArrowDataType::Int8, AnySlice::NullableI8(x)) => {
+            Arc::new(Int8Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Int16, AnySlice::NullableI16(x)) => {
+            Arc::new(Int16Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Int32, AnySlice::NullableI32(x)) => {
+            Arc::new(Int32Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Int64, AnySlice::NullableI64(x)) => {
+            Arc::new(Int64Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Float32, AnySlice::NullableF32(x)) => {
+            Arc::new(Float32Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Float64, AnySlice::NullableF64(x)) => {
+            Arc::new(Float64Array::from_iter(x.map(|x| x.copied())))
+        }
+        (ArrowDataType::Date32, AnySlice::NullableDate(x)) => Arc::new(Date32Array::from(
+            x.map(|x| x.map(date_to_days).transpose())
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        (ArrowDataType::Timestamp(..), AnySlice::NullableTimestamp(x)) => {
+            Arc::new(TimestampMicrosecondArray::from(
+                x.map(|x| x.map(timestamp_to_micros).transpose())
+                    .collect::<Result<Vec<_>, _>>()?,
+            ))
+        }
+        (ArrowDataType::Decimal128(precision, scale), AnySlice::Text(x)) => Arc::new(
+            Decimal128Array::from(
+                x.iter()
+                    .map(|x| {
+                        x.map(|x| parse_decimal(&String::from_utf8_lossy(x), *scale))
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .with_precision_and_scale(*precision, *scale)?,
+        ),
+        (ArrowDataType::Time64(_), AnySlice::Text(x)) => Arc::new(Time64MicrosecondArray::from(
+            x.iter()
+                .map(|x| {
+                    x.map(|x| parse_time_micros(&String::from_utf8_lossy(x)))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        (ArrowDataType::Binary, AnySlice::Binary(x)) => Arc::new(BinaryArray::from_iter(x.iter())),
+        (ArrowDataType::Utf8, AnySlice::Text(x)) => Arc::new(StringArray::from_iter(
+            x.iter().map(|x| x.map(String::from_utf8_lossy)),
+        )),
+        (ArrowDataType::Utf8, AnySlice::WText(x)) => Arc::new(StringArray::from_iter(
+            x.iter().map(|x| x.map(|x| x.to_string_lossy())),
+        )),
+        (data_type, _) => {
+            return Err(export_error!(
+                "unexpected fetch buffer of column {} with type {}",
+                field.name(),
+                data_type
+            ))
+        }
+    };
+    Ok(array)
+}
+
+fn date_to_days(date: &Date) -> Result<i32, OdbcHelperError> {
+    let date = NaiveDate::from_ymd_opt(date.year as i32, date.month as u32, date.day as u32)
+        .ok_or_else(|| conversion_error!("invalid date:{:?}", date))?;
+    Ok((date - NaiveDate::default()).num_days() as i32)
+}
+
+fn timestamp_to_micros(ts: &Timestamp) -> Result<i64, OdbcHelperError> {
+    let date_time = NaiveDate::from_ymd_opt(ts.year as i32, ts.month as u32, ts.day as u32)
+        .and_then(|x| {
+            x.and_hms_nano_opt(
+                ts.hour as u32,
+                ts.minute as u32,
+                ts.second as u32,
+                ts.fraction,
+            )
+        })
+        .ok_or_else(|| conversion_error!("invalid timestamp:{:?}", ts))?;
+    Ok(date_time.and_utc().timestamp_micros())
+}
+
+/// Microseconds since midnight of time text, E.g: `15:50:36.123`
+fn parse_time_micros(text: &str) -> Result<i64, OdbcHelperError> {
+    let time = NaiveTime::parse_from_str(text.trim(), "%H:%M:%S%.f")
+        .map_err(|e| conversion_error!(source = e, "invalid time:{}", text))?;
+    Ok(time.num_seconds_from_midnight() as i64 * 1_000_000 + time.nanosecond() as i64 / 1_000)
+}
+
+/// Parse decimal text to `i128` with `scale` digits of fraction, E.g: `-12.5` with scale 2 is
+/// `-1250`. Fraction digits beyond `scale` must be zeros, otherwise the precision would be lost.
+fn parse_decimal(text: &str, scale: i8) -> Result<i128, OdbcHelperError> {
+    let invalid = || conversion_error!("invalid decimal:{} with scale {}", text, scale);
+    let trimmed = text.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|x| x.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let scale = scale.max(0) as usize;
+    let fraction = match fraction.split_at_checked(scale) {
+        Some((kept, rest)) if rest.bytes().all(|x| x == b'0') => kept.to_string(),
+        Some(_) => return Err(invalid()),
+        None => format!("{:0<width$}", fraction, width = scale),
+    };
+    let value = format!("{}{}", integer, fraction);
+    let value: i128 = if value.is_empty() {
+        0
+    } else {
+        value.parse().map_err(|_| invalid())?
+    };
+    Ok(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use arrow_ipc::reader::FileReader;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_arrow_type() {
+        assert_eq!(
+            arrow_type(&DataType::Decimal {
+                precision: 10,
+                scale: 2
+            }),
+            ArrowDataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            arrow_type(&DataType::Numeric {
+                precision: 40,
+                scale: 2
+            }),
+            ArrowDataType::Utf8
+        );
+        assert_eq!(
+            arrow_type(&DataType::Float { precision: 53 }),
+            ArrowDataType::Float64
+        );
+        assert_eq!(
+            arrow_type(&DataType::Timestamp { precision: 6 }),
+            ArrowDataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+
+        let item = DmTableItem {
+            r#type: DmDateType::NUMBER,
+            length: 0,
+            scale: 4,
+            ..Default::default()
+        };
+        assert_eq!(dm_arrow_type(&item), ArrowDataType::Decimal128(38, 4));
+        let item = DmTableItem {
+            r#type: DmDateType::CLOB,
+            ..Default::default()
+        };
+        assert_eq!(dm_arrow_type(&item), ArrowDataType::Utf8);
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_decimal("-12.5", 2).unwrap(), -1250);
+        assert_eq!(parse_decimal("12.500", 2).unwrap(), 1250);
+        assert_eq!(parse_decimal(".5", 1).unwrap(), 5);
+        assert_eq!(parse_decimal("7", 0).unwrap(), 7);
+        assert_eq!(
+            parse_decimal("99999999999999999999999999999999999999", 0).unwrap(),
+            99999999999999999999999999999999999999
+        );
+        assert!(parse_decimal("12.345", 2).is_err());
+        assert!(parse_decimal("1e5", 0).is_err());
+        assert!(parse_decimal("-", 0).is_err());
+
+        assert_eq!(parse_time_micros("00:00:01.5").unwrap(), 1_500_000);
+        assert_eq!(parse_time_micros("23:59:59").unwrap(), 86_399_000_000);
+
+        let date = Date {
+            year: 1970,
+            month: 1,
+            day: 2,
+        };
+        assert_eq!(date_to_days(&date).unwrap(), 1);
+        let ts = Timestamp {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 1,
+            fraction: 1_000,
+        };
+        assert_eq!(timestamp_to_micros(&ts).unwrap(), 1_000_001);
+    }
+
+    #[test]
+    fn test_export_writer() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("ID", ArrowDataType::Int32, false),
+            Field::new("PRICE", ArrowDataType::Decimal128(10, 2), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(1250), None])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+            ],
+        )
+        .unwrap();
+
+        let mut buf = vec![];
+        let mut writer =
+            ExportWriter::try_new(&mut buf, schema.clone(), &ExportOptions::default()).unwrap();
+        writer.write(&batch).unwrap();
+        assert_eq!(writer.finish().unwrap(), 2);
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buf))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches, vec![batch.clone()]);
+
+        let options = ExportOptions {
+            format: ExportFormat::Ipc,
+            ..Default::default()
+        };
+        let mut buf = vec![];
+        let mut writer = ExportWriter::try_new(&mut buf, schema.clone(), &options).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        let reader = FileReader::try_new(Cursor::new(buf), None).unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches[0].column(1).null_count(), 1);
+        assert_eq!(batches, vec![batch]);
+
+        let options = ExportOptions {
+            format: ExportFormat::Ipc,
+            compression: ExportCompression::Snappy,
+            ..Default::default()
+        };
+        assert!(ExportWriter::try_new(vec![], schema, &options).is_err());
+
+        assert_eq!(
+            ExportFormat::from_path("t.feather"),
+            Some(ExportFormat::Ipc)
+        );
+        assert_eq!(ExportFormat::from_path("t.csv"), None);
+    }
+}
@@ -1,11 +1,12 @@
+use crate::error::OdbcHelperError;
 use crate::executor::table::TableDescResult;
 use crate::TryConvert;
 use dameng_helper::table::DmTableDesc;
 
 impl TryConvert<DmTableDesc> for TableDescResult {
-    type Error = anyhow::Error;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<DmTableDesc, Self::Error> {
-        DmTableDesc::new(self.0, self.1)
+        Ok(DmTableDesc::new(self.0, self.1)?)
     }
 }
@@ -1,10 +1,14 @@
 use crate::executor::database::Options;
+use crate::extension::util::{parse_to_data_time, parse_to_date, parse_to_time};
 use crate::{Convert, TryConvert};
 use bytes::BytesMut;
 use odbc_api::buffers::{AnySlice, BufferDescription, BufferKind};
 use odbc_api::sys::{Date, Time, Timestamp, NULL_DATA};
 use odbc_api::DataType;
+use odbc_common::data_type::type_description;
+use odbc_common::BinaryFormat;
 use std::cmp::min;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone)]
 pub struct OdbcColumn {
@@ -21,6 +25,11 @@ impl OdbcColumn {
             nullable,
         }
     }
+
+    /// Sql type name with nullability, E.g: `VARCHAR(255) NOT NULL`
+    pub fn type_description(&self) -> String {
+        type_description(&self.data_type, Some(self.nullable))
+    }
 }
 
 impl TryConvert<BufferDescription> for (&OdbcColumn, &Options) {
@@ -69,7 +78,7 @@ pub struct OdbcColumnItem {
     pub value: Option<BytesMut>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OdbcColumnType {
     Text,
     WText,
@@ -87,9 +96,68 @@ pub enum OdbcColumnType {
     Bit,
 }
 
-impl ToString for OdbcColumnItem {
-    fn to_string(&self) -> String {
-        format!("{:?}", self)
+impl OdbcColumnType {
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            OdbcColumnType::F64
+                | OdbcColumnType::F32
+                | OdbcColumnType::I8
+                | OdbcColumnType::I16
+                | OdbcColumnType::I32
+                | OdbcColumnType::I64
+                | OdbcColumnType::U8
+        )
+    }
+}
+
+impl OdbcColumnItem {
+    /// Display text of the value, `None` is `NULL` which is different from empty text.
+    ///
+    /// Date and time are ISO 8601 format as postgres outputs, E.g: `2022-08-24 15:50:36.123`,
+    /// binary is encoded as `binary_format`.
+    pub fn display(&self, binary_format: BinaryFormat) -> Option<String> {
+        let value = self.value.as_ref()?;
+        let text = || String::from_utf8_lossy(value).to_string();
+        let display = match self.odbc_type {
+            OdbcColumnType::Text
+            | OdbcColumnType::WText
+            | OdbcColumnType::F64
+            | OdbcColumnType::F32
+            | OdbcColumnType::I8
+            | OdbcColumnType::I16
+            | OdbcColumnType::I32
+            | OdbcColumnType::I64 => text(),
+            OdbcColumnType::U8 => value.first().map(|x| x.to_string()).unwrap_or_default(),
+            OdbcColumnType::Bit => match text().as_str() {
+                "1" | "true" => "true".to_string(),
+                "0" | "false" => "false".to_string(),
+                x => x.to_string(),
+            },
+            OdbcColumnType::Binary => binary_format.encode(value),
+            OdbcColumnType::Date => parse_to_date(value.clone())
+                .map(|x| x.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|_| text()),
+            // Keep the text with time zone offset as is, it is dropped by parsing.
+            OdbcColumnType::Time if !text().contains('+') => parse_to_time(value.clone())
+                .map(|x| x.format("%H:%M:%S%.f").to_string())
+                .unwrap_or_else(|_| text()),
+            OdbcColumnType::Timestamp if !text().contains('+') => parse_to_data_time(value.clone())
+                .map(|x| x.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+                .unwrap_or_else(|_| text()),
+            OdbcColumnType::Time | OdbcColumnType::Timestamp => text(),
+        };
+        Some(display)
+    }
+}
+
+/// `NULL` is displayed as `NULL`, binary is hex format.
+impl Display for OdbcColumnItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.display(BinaryFormat::Hex) {
+            Some(x) => write!(f, "{}", x),
+            None => write!(f, "NULL"),
+        }
     }
 }
 
@@ -610,3 +678,48 @@ impl TryConvert<time::PrimitiveDateTime> for Timestamp {
         Ok(time::PrimitiveDateTime::new(date, time))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odbc_column_item_display() {
+        let item = |odbc_type: OdbcColumnType, value: Option<&[u8]>| OdbcColumnItem {
+            odbc_type,
+            value: value.map(BytesMut::from),
+        };
+        let hex = BinaryFormat::Hex;
+        assert_eq!(item(OdbcColumnType::Text, None).display(hex), None);
+        assert_eq!(
+            item(OdbcColumnType::Text, Some(b"")).display(hex),
+            Some("".to_string())
+        );
+        assert_eq!(item(OdbcColumnType::Text, None).to_string(), "NULL");
+        assert_eq!(item(OdbcColumnType::I32, Some(b"-42")).to_string(), "-42");
+        assert_eq!(item(OdbcColumnType::U8, Some(&[200])).to_string(), "200");
+        assert_eq!(item(OdbcColumnType::Bit, Some(b"1")).to_string(), "true");
+        assert_eq!(
+            item(OdbcColumnType::Binary, Some(&[0x0a, 0xff])).to_string(),
+            "\\x0aff"
+        );
+        assert_eq!(
+            item(OdbcColumnType::Binary, Some(&[0x0a, 0xff])).display(BinaryFormat::Base64),
+            Some("Cv8=".to_string())
+        );
+        assert_eq!(
+            item(OdbcColumnType::Date, Some(b"2022-08-24")).to_string(),
+            "2022-08-24"
+        );
+        assert_eq!(
+            item(OdbcColumnType::Time, Some(b"15:50:36.0")).to_string(),
+            "15:50:36"
+        );
+        assert_eq!(
+            item(OdbcColumnType::Timestamp, Some(b"2022-08-24 15:50:36.123")).to_string(),
+            "2022-08-24 15:50:36.123"
+        );
+        assert!(OdbcColumnType::F64.is_numeric());
+        assert!(!OdbcColumnType::Text.is_numeric());
+    }
+}
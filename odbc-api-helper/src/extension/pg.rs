@@ -1,8 +1,10 @@
+use crate::error::OdbcHelperError;
 use crate::executor::database::Options;
 use crate::executor::query::QueryResult;
 use crate::executor::statement::SqlValue;
 use crate::extension::odbc::{OdbcColumn, OdbcColumnItem, OdbcColumnType};
 use crate::{Convert, TryConvert};
+use bytes::BytesMut;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use either::Either;
 use odbc_api::buffers::BufferKind;
@@ -10,16 +12,21 @@ use odbc_api::parameter::InputParameter;
 use odbc_api::Bit;
 use odbc_api::IntoParameter;
 use pg_helper::table::PgTableItem;
-use postgres_types::{Oid, Type as PgType};
+use postgres_types::{Kind, Oid, Type as PgType};
 use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
 
-use crate::executor::table::TableDescResult;
+use crate::executor::table::{OdbcTableDesc, OdbcTableItem, TableDescResult};
 use crate::executor::SupportDatabase;
 use crate::extension::util::{
-    parse_to_bool, parse_to_data_time, parse_to_date, parse_to_float4, parse_to_float8,
-    parse_to_i8, parse_to_int2, parse_to_int4, parse_to_int8, parse_to_string, parse_to_time,
+    parse_to_array, parse_to_bool, parse_to_data_time, parse_to_date, parse_to_float4,
+    parse_to_float8, parse_to_i8, parse_to_inet, parse_to_int2, parse_to_int4, parse_to_int8,
+    parse_to_interval, parse_to_json, parse_to_money, parse_to_string, parse_to_time,
+    parse_to_uuid,
 };
 use dameng_helper::table::DmTableDesc;
+use pg_helper::builtin_types;
 use pg_helper::table::PgTableDesc;
 
 #[derive(Debug, PartialEq)]
@@ -41,6 +48,82 @@ pub enum PgValueInput {
     Date(NaiveDate),
     Numeric(i32),
     Name(String),
+    Oid(Oid),
+    /// lowercase hyphenated uuid
+    Uuid(String),
+    Json(serde_json::Value),
+    Jsonb(serde_json::Value),
+    Interval(PgInterval),
+    /// one dimension array, element is text representation, `None` is NULL element
+    Array(Vec<Option<String>>),
+    /// address and netmask bits, also used by `cidr`
+    Inet(IpAddr, u8),
+    /// amount in cents
+    Money(i64),
+}
+
+/// Same fields as postgres internal interval representation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PgInterval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+/// Postgres `postgres` interval style, E.g: `1 year 2 mons 3 days 04:05:06.5`
+impl Display for PgInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // unit is singular when count is 1 or -1, E.g: `1 day`, `2 days`
+        let part = |count: i32, unit: &str| {
+            let plural = if count.abs() == 1 { "" } else { "s" };
+            format!("{} {}{}", count, unit, plural)
+        };
+        let mut parts = vec![];
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            parts.push(part(years, "year"));
+        }
+        if months != 0 {
+            parts.push(part(months, "mon"));
+        }
+        if self.days != 0 {
+            parts.push(part(self.days, "day"));
+        }
+        if self.microseconds != 0 || parts.is_empty() {
+            let sign = if self.microseconds < 0 { "-" } else { "" };
+            let total = self.microseconds.unsigned_abs();
+            let (hours, rest) = (total / 3_600_000_000, total % 3_600_000_000);
+            let (minutes, rest) = (rest / 60_000_000, rest % 60_000_000);
+            let (seconds, micros) = (rest / 1_000_000, rest % 1_000_000);
+            let mut time = format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds);
+            if micros != 0 {
+                time.push_str(format!(".{:06}", micros).trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Format one dimension array to postgres array literal, E.g: `{a,"b,c",NULL}`
+fn array_literal(items: &[Option<String>]) -> String {
+    let items = items
+        .iter()
+        .map(|x| match x {
+            None => "NULL".to_string(),
+            Some(x)
+                if x.is_empty()
+                    || x.eq_ignore_ascii_case("NULL")
+                    || x.contains(|c: char| {
+                        matches!(c, ',' | '{' | '}' | '"' | '\\') || c.is_whitespace()
+                    }) =>
+            {
+                format!("\"{}\"", x.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            Some(x) => x.clone(),
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", items.join(","))
 }
 
 impl SqlValue for PgValueInput {
@@ -59,11 +142,24 @@ impl SqlValue for PgValueInput {
             Self::Float8(i) => left_param!(i.into_parameter()),
             Self::Char(i) => left_param!(i.into_parameter()),
             Self::Varchar(i) | Self::Text(i) | Self::Name(i) => left_param!(i.into_parameter()),
+            Self::Oid(i) => left_param!(i64::from(i).into_parameter()),
             Self::Bool(i) => left_param!(Bit::from_bool(i).into_parameter()),
             Self::Bytea(bytes) => left_param!(bytes.into_parameter()),
             Self::Time(i) | Self::Timez(i) => left_param!(i.to_string().into_parameter()),
             Self::Timestamp(i) | Self::Timestampz(i) => left_param!(i.to_string().into_parameter()),
             Self::Date(i) => left_param!(i.to_string().into_parameter()),
+            Self::Uuid(i) => left_param!(i.into_parameter()),
+            Self::Json(i) | Self::Jsonb(i) => left_param!(i.to_string().into_parameter()),
+            Self::Interval(i) => left_param!(i.to_string().into_parameter()),
+            Self::Array(i) => left_param!(array_literal(&i).into_parameter()),
+            Self::Inet(addr, netmask) => {
+                left_param!(format!("{}/{}", addr, netmask).into_parameter())
+            }
+            Self::Money(i) => {
+                let sign = if i < 0 { "-" } else { "" };
+                let cents = i.unsigned_abs();
+                left_param!(format!("{}{}.{:02}", sign, cents / 100, cents % 100).into_parameter())
+            }
         }
     }
 }
@@ -197,8 +293,18 @@ pub fn oid_typlen<C: Convert<PgType>>(c: C) -> i16 {
     pg_helper::oid_typlen(pg_type)
 }
 
+pub fn oid_typalign<C: Convert<PgType>>(c: C) -> char {
+    let pg_type = c.convert();
+    pg_helper::oid_typalign(pg_type)
+}
+
+pub fn oid_typcategory<C: Convert<PgType>>(c: C) -> char {
+    let pg_type = c.convert();
+    pg_helper::oid_typcategory(pg_type)
+}
+
 impl TryConvert<PgTableDesc> for (TableDescResult, &Options) {
-    type Error = anyhow::Error;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<PgTableDesc, Self::Error> {
         let pg = match self.1.database {
@@ -208,12 +314,24 @@ impl TryConvert<PgTableDesc> for (TableDescResult, &Options) {
                 for (k, v) in dm.data.into_iter() {
                     let mut pg_item = Vec::new();
                     for dm in v {
-                        pg_item.push(dm.try_convert()?)
+                        pg_item.push((dm, &self.1.type_mapping).try_convert()?)
                     }
                     pg.insert(k.to_string(), pg_item);
                 }
                 PgTableDesc { data: pg }
             }
+            SupportDatabase::Pg => {
+                let odbc = OdbcTableDesc::new(self.0 .0, self.0 .1)?;
+                let mut pg = BTreeMap::new();
+                for (k, v) in odbc.data.into_iter() {
+                    let pg_item = v
+                        .iter()
+                        .map(|x| x.try_convert())
+                        .collect::<Result<Vec<PgTableItem>, _>>()?;
+                    pg.insert(k, pg_item);
+                }
+                PgTableDesc { data: pg }
+            }
             _ => PgTableDesc::default(),
         };
 
@@ -221,49 +339,113 @@ impl TryConvert<PgTableDesc> for (TableDescResult, &Options) {
     }
 }
 
-impl TryConvert<PgColumnItem> for (&OdbcColumnItem, &PgColumn) {
-    type Error = String;
-
-    fn try_convert(self) -> Result<PgColumnItem, Self::Error> {
-        let odbc_data = self.0.value.clone();
-        let pg_column = self.1;
-
-        let value = match pg_column.pg_type {
-            PgType::TEXT => odbc_data.map(|v| PgValueInput::Text(parse_to_string(v))),
-            PgType::VARCHAR => odbc_data.map(|v| PgValueInput::Varchar(parse_to_string(v))),
-            PgType::BYTEA => odbc_data.map(|v| PgValueInput::Bytea(v.to_vec())),
-            PgType::DATE => odbc_data.map(|v| PgValueInput::Date(parse_to_date(v).unwrap())),
-            PgType::TIME => odbc_data.map(|v| PgValueInput::Time(parse_to_time(v).unwrap())),
-            PgType::TIMETZ => odbc_data.map(|v| PgValueInput::Timez(parse_to_time(v).unwrap())),
-            PgType::TIMESTAMP => {
-                odbc_data.map(|v| PgValueInput::Timestamp(parse_to_data_time(v).unwrap()))
+/// Postgres column described by `SQLColumns` of the postgres ODBC driver, the type is looked up by
+/// driver `TYPE_NAME`.
+impl TryConvert<PgTableItem> for &OdbcTableItem {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<PgTableItem, Self::Error> {
+        let r#type = pg_type_from_driver_name(&self.type_name)
+            .ok_or_else(|| metadata_error!("unknown pg type name:{}", self.type_name))?;
+        let column_size = self.column_size.unwrap_or_default();
+        let decimal_digits = self.decimal_digits.unwrap_or_default().max(0) as usize;
+        let (length, scale) = match r#type {
+            PgType::NUMERIC => (column_size, decimal_digits),
+            PgType::CHAR | PgType::BPCHAR | PgType::VARCHAR | PgType::BIT | PgType::VARBIT => {
+                (column_size, 0)
             }
-            PgType::TIMESTAMPTZ => {
-                odbc_data.map(|v| PgValueInput::Timestampz(parse_to_data_time(v).unwrap()))
-            }
-            PgType::FLOAT8 => odbc_data.map(|v| PgValueInput::Float8(parse_to_float8(v).unwrap())),
-            PgType::FLOAT4 => odbc_data.map(|v| PgValueInput::Float4(parse_to_float4(v).unwrap())),
-            PgType::CHAR => odbc_data.map(|v| PgValueInput::Char(parse_to_i8(v).unwrap())),
-            PgType::INT2 => odbc_data.map(|v| PgValueInput::Int2(parse_to_int2(v).unwrap())),
-            PgType::INT4 => odbc_data.map(|v| PgValueInput::Int4(parse_to_int4(v).unwrap())),
-            PgType::NUMERIC => odbc_data.map(|v| PgValueInput::Numeric(parse_to_int4(v).unwrap())),
-            PgType::INT8 => odbc_data.map(|v| PgValueInput::Int8(parse_to_int8(v).unwrap())),
-            PgType::BOOL => odbc_data.map(|v| PgValueInput::Bool(parse_to_bool(v).unwrap())),
-            _ => {
-                error!(
-                    "There is no adaptation for this type, {}",
-                    pg_column.pg_type
-                );
-                odbc_data.map(|v| PgValueInput::Text(parse_to_string(v)))
+            // `DECIMAL_DIGITS` is fractional seconds precision
+            PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ => {
+                (0, decimal_digits)
             }
+            _ => (0, 0),
         };
+        Ok(PgTableItem {
+            name: self.name.to_string(),
+            table_id: 0,
+            col_index: self.ordinal.saturating_sub(1),
+            r#type,
+            length,
+            scale,
+            nullable: self.nullable,
+            default_val: self.default_val.clone(),
+            table_name: self.table_name.to_string(),
+            create_time: "".to_string(),
+        })
+    }
+}
+
+/// The driver reports column with sequence default as `serial`, array as `typname` of
+/// `pg_type`, E.g: `_int4`.
+fn pg_type_from_driver_name(name: &str) -> Option<PgType> {
+    let name = name.trim().to_lowercase();
+    let name = match name.as_str() {
+        "smallserial" | "serial2" => "int2",
+        "serial" | "serial4" => "int4",
+        "bigserial" | "serial8" => "int8",
+        x => x,
+    };
+    pg_helper::type_from_name(name).or_else(|| {
+        builtin_types()
+            .find(|(_, typname, _)| *typname == name)
+            .and_then(|(oid, ..)| PgType::from_oid(oid))
+    })
+}
+
+/// Parse odbc text value to pg value of `pg_type`, unadapted types are kept as text.
+fn parse_value(pg_type: &PgType, v: BytesMut) -> Result<PgValueInput, OdbcHelperError> {
+    let value = match *pg_type {
+        PgType::TEXT => PgValueInput::Text(parse_to_string(v)),
+        PgType::VARCHAR | PgType::BPCHAR => PgValueInput::Varchar(parse_to_string(v)),
+        PgType::BYTEA => PgValueInput::Bytea(v.to_vec()),
+        PgType::DATE => PgValueInput::Date(parse_to_date(v)?),
+        PgType::TIME => PgValueInput::Time(parse_to_time(v)?),
+        PgType::TIMETZ => PgValueInput::Timez(parse_to_time(v)?),
+        PgType::TIMESTAMP => PgValueInput::Timestamp(parse_to_data_time(v)?),
+        PgType::TIMESTAMPTZ => PgValueInput::Timestampz(parse_to_data_time(v)?),
+        PgType::FLOAT8 => PgValueInput::Float8(parse_to_float8(v)?),
+        PgType::FLOAT4 => PgValueInput::Float4(parse_to_float4(v)?),
+        PgType::CHAR => PgValueInput::Char(parse_to_i8(v)?),
+        PgType::INT2 => PgValueInput::Int2(parse_to_int2(v)?),
+        PgType::INT4 => PgValueInput::Int4(parse_to_int4(v)?),
+        PgType::NUMERIC => PgValueInput::Numeric(parse_to_int4(v)?),
+        PgType::INT8 => PgValueInput::Int8(parse_to_int8(v)?),
+        PgType::BOOL => PgValueInput::Bool(parse_to_bool(v)?),
+        PgType::UUID => PgValueInput::Uuid(parse_to_uuid(v)?),
+        PgType::JSON => PgValueInput::Json(parse_to_json(v)?),
+        PgType::JSONB => PgValueInput::Jsonb(parse_to_json(v)?),
+        PgType::INTERVAL => PgValueInput::Interval(parse_to_interval(v)?),
+        PgType::INET | PgType::CIDR => {
+            let (addr, netmask) = parse_to_inet(v)?;
+            PgValueInput::Inet(addr, netmask)
+        }
+        PgType::MONEY => PgValueInput::Money(parse_to_money(v)?),
+        ref t if matches!(t.kind(), Kind::Array(_)) => PgValueInput::Array(parse_to_array(v)?),
+        _ => {
+            error!("There is no adaptation for this type, {}", pg_type);
+            PgValueInput::Text(parse_to_string(v))
+        }
+    };
+    Ok(value)
+}
+
+impl TryConvert<PgColumnItem> for (&OdbcColumnItem, &PgColumn) {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<PgColumnItem, Self::Error> {
+        let value = self
+            .0
+            .value
+            .clone()
+            .map(|v| parse_value(&self.1.pg_type, v))
+            .transpose()?;
 
         Ok(PgColumnItem::new(value))
     }
 }
 
 impl TryConvert<PgQueryResult> for (QueryResult, &Vec<PgTableItem>, &Options) {
-    type Error = String;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<PgQueryResult, Self::Error> {
         let res = self.0;
@@ -285,7 +467,7 @@ impl TryConvert<PgQueryResult> for (QueryResult, &Vec<PgTableItem>, &Options) {
                     let mut row: Vec<PgColumnItem> = vec![];
                     for (index, odbc_item) in v.iter().enumerate() {
                         if let Some(col) = result.columns.get(index) {
-                            row.push((odbc_item, col).try_convert().unwrap());
+                            row.push((odbc_item, col).try_convert()?);
                         }
                     }
                     result.data.push(row);
@@ -297,7 +479,7 @@ impl TryConvert<PgQueryResult> for (QueryResult, &Vec<PgTableItem>, &Options) {
 }
 
 impl TryConvert<Vec<PgColumn>> for (&Vec<OdbcColumn>, &Vec<PgTableItem>, &Options) {
-    type Error = String;
+    type Error = OdbcHelperError;
 
     fn try_convert(self) -> Result<Vec<PgColumn>, Self::Error> {
         let odbc_columns = self.0;
@@ -368,6 +550,8 @@ mod tests {
             max_str_len: 1024,
             max_binary_len: 1024,
             case_sensitive: false,
+            type_mapping: Default::default(),
+            translate_pg_sql: false,
         };
         let result: PgQueryResult = (query_result, &vec![pg_table_item], &options)
             .try_convert()
@@ -385,4 +569,61 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_extended_value_convert() {
+        let convert = |pg_type: PgType, value: &str| {
+            let item = OdbcColumnItem {
+                odbc_type: OdbcColumnType::Text,
+                value: Some(value.into()),
+            };
+            let column = PgColumn {
+                name: "c1".to_string(),
+                oid: pg_type.oid(),
+                pg_type,
+                nullable: true,
+            };
+            let item: PgColumnItem = (&item, &column).try_convert().unwrap();
+            item.data.unwrap()
+        };
+
+        assert_eq!(
+            convert(PgType::UUID, "{A0EEBC99-9C0B4EF8-BB6D-6BB9BD380A11}"),
+            PgValueInput::Uuid("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string())
+        );
+        assert_eq!(
+            convert(PgType::JSONB, r#"{"a": [1, null]}"#),
+            PgValueInput::Jsonb(serde_json::json!({"a": [1, null]}))
+        );
+        assert_eq!(
+            convert(PgType::INET, "10.0.0.0/8"),
+            PgValueInput::Inet("10.0.0.0".parse().unwrap(), 8)
+        );
+        assert_eq!(
+            convert(PgType::MONEY, "($1,234.5)"),
+            PgValueInput::Money(-123450)
+        );
+        assert_eq!(
+            convert(PgType::TEXT_ARRAY, r#"{a,"b,c",NULL}"#),
+            PgValueInput::Array(vec![Some("a".into()), Some("b,c".into()), None])
+        );
+        assert_eq!(
+            array_literal(&[Some("a".into()), Some("b \"c\"".into()), None]),
+            r#"{a,"b \"c\"",NULL}"#
+        );
+
+        let interval = |value: &str| match convert(PgType::INTERVAL, value) {
+            PgValueInput::Interval(x) => x.to_string(),
+            x => panic!("unexpected value {:?}", x),
+        };
+        assert_eq!(interval("INTERVAL '1-2' YEAR TO MONTH"), "1 year 2 mons");
+        assert_eq!(interval("INTERVAL '2-1' YEAR TO MONTH"), "2 years 1 mon");
+        assert_eq!(interval("INTERVAL '-1' DAY"), "-1 day");
+        assert_eq!(
+            interval("INTERVAL '3 04:05:06.5' DAY TO SECOND"),
+            "3 days 04:05:06.5"
+        );
+        assert_eq!(interval("INTERVAL '-90' MINUTE"), "-01:30:00");
+        assert_eq!(interval("INTERVAL '0' DAY"), "00:00:00");
+    }
 }
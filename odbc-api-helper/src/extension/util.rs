@@ -1,59 +1,62 @@
+use crate::error::OdbcHelperError;
+use crate::extension::pg::PgInterval;
 use bytes::BytesMut;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::net::IpAddr;
+use std::num::ParseIntError;
+use std::str::FromStr;
 
-pub fn parse_to_bool(v: BytesMut) -> anyhow::Result<bool> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<bool>()?)
+pub fn parse_to_bool(v: BytesMut) -> Result<bool, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_i8(v: BytesMut) -> anyhow::Result<i8> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<i8>()?)
+pub fn parse_to_i8(v: BytesMut) -> Result<i8, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_int2(v: BytesMut) -> anyhow::Result<i16> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<i16>()?)
+pub fn parse_to_int2(v: BytesMut) -> Result<i16, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_int4(v: BytesMut) -> anyhow::Result<i32> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<i32>()?)
+pub fn parse_to_int4(v: BytesMut) -> Result<i32, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_int8(v: BytesMut) -> anyhow::Result<i64> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<i64>()?)
+pub fn parse_to_int8(v: BytesMut) -> Result<i64, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_float4(v: BytesMut) -> anyhow::Result<f32> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<f32>()?)
+pub fn parse_to_float4(v: BytesMut) -> Result<f32, OdbcHelperError> {
+    parse_text(v)
 }
 
-pub fn parse_to_float8(v: BytesMut) -> anyhow::Result<f64> {
-    Ok(String::from_utf8_lossy(v.as_ref())
-        .to_string()
-        .parse::<f64>()?)
+pub fn parse_to_float8(v: BytesMut) -> Result<f64, OdbcHelperError> {
+    parse_text(v)
+}
+
+fn parse_text<T>(v: BytesMut) -> Result<T, OdbcHelperError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    val.parse::<T>().map_err(|e| {
+        conversion_error!(source = e, "invalid {}:{}", std::any::type_name::<T>(), val)
+    })
 }
 
 pub fn parse_to_string(v: BytesMut) -> String {
     String::from_utf8_lossy(v.as_ref()).to_string()
 }
 
-pub fn parse_to_date(v: BytesMut) -> anyhow::Result<NaiveDate> {
+pub fn parse_to_date(v: BytesMut) -> Result<NaiveDate, OdbcHelperError> {
     let val = String::from_utf8_lossy(v.as_ref()).to_string();
-    let date = NaiveDate::parse_from_str(val.as_str(), "%Y-%m-%d")?;
+    let date = NaiveDate::parse_from_str(val.as_str(), "%Y-%m-%d")
+        .map_err(|e| conversion_error!(source = e, "invalid date:{}", val))?;
     Ok(date)
 }
 
-pub fn parse_to_time(v: BytesMut) -> anyhow::Result<NaiveTime> {
+pub fn parse_to_time(v: BytesMut) -> Result<NaiveTime, OdbcHelperError> {
     let val = String::from_utf8_lossy(v.as_ref()).to_string();
     let time = NaiveTime::parse_from_str(
         val.as_str(),
@@ -63,11 +66,11 @@ pub fn parse_to_time(v: BytesMut) -> anyhow::Result<NaiveTime> {
             "%H:%M:%S%.f"
         },
     )
-    .unwrap();
+    .map_err(|e| conversion_error!(source = e, "invalid time:{}", val))?;
     Ok(time)
 }
 
-pub fn parse_to_data_time(v: BytesMut) -> anyhow::Result<NaiveDateTime> {
+pub fn parse_to_data_time(v: BytesMut) -> Result<NaiveDateTime, OdbcHelperError> {
     let val = String::from_utf8_lossy(v.as_ref()).to_string();
     let date_time = NaiveDateTime::parse_from_str(
         val.as_str(),
@@ -76,6 +79,245 @@ pub fn parse_to_data_time(v: BytesMut) -> anyhow::Result<NaiveDateTime> {
         } else {
             "%Y-%m-%d %H:%M:%S%.f"
         },
-    )?;
+    )
+    .map_err(|e| conversion_error!(source = e, "invalid timestamp:{}", val))?;
     Ok(date_time)
 }
+
+/// Parse uuid with or without hyphens and braces, return lowercase hyphenated uuid.
+pub fn parse_to_uuid(v: BytesMut) -> Result<String, OdbcHelperError> {
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    let hex: String = val
+        .trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .chars()
+        .filter(|c| *c != '-')
+        .collect();
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(conversion_error!("invalid uuid:{}", val));
+    }
+    let hex = hex.to_lowercase();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+pub fn parse_to_json(v: BytesMut) -> Result<serde_json::Value, OdbcHelperError> {
+    serde_json::from_slice(v.as_ref()).map_err(|e| {
+        conversion_error!(
+            "invalid json:{}, {}",
+            String::from_utf8_lossy(v.as_ref()),
+            e
+        )
+    })
+}
+
+/// Parse sql standard interval, E.g: `INTERVAL '1-2' YEAR TO MONTH`, `3 04:05:06.5`, `-04:05`.
+/// Single number without time separator use the leading field of interval qualifier as unit.
+pub fn parse_to_interval(v: BytesMut) -> Result<PgInterval, OdbcHelperError> {
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    let err = || conversion_error!("invalid interval:{}", val);
+
+    let text = val.trim();
+    let text = match text.get(..8) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("INTERVAL") => text[8..].trim(),
+        _ => text,
+    };
+    let (value, qualifier) = match text.strip_prefix('\'') {
+        Some(x) => x.split_once('\'').ok_or_else(err)?,
+        None => (text, ""),
+    };
+    let leading_field = qualifier
+        .split_whitespace()
+        .next()
+        .map(|x| x.to_uppercase())
+        .unwrap_or_default();
+
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(x) => (true, x.trim()),
+        None => (false, value.trim_start_matches('+').trim()),
+    };
+
+    let mut interval = PgInterval::default();
+    if let Some((years, months)) = value.split_once('-') {
+        let years: i32 = years.trim().parse().map_err(|_| err())?;
+        let months: i32 = months.trim().parse().map_err(|_| err())?;
+        interval.months = years * 12 + months;
+    } else {
+        let (days, time) = match value.split_once(' ') {
+            Some((days, time)) => (Some(days), time.trim()),
+            None => (None, value),
+        };
+        if let Some(days) = days {
+            interval.days = days.parse().map_err(|_| err())?;
+        }
+        if time.contains(':') {
+            let parts = time.split(':').collect::<Vec<_>>();
+            let seconds = |x: &str| -> Result<i64, OdbcHelperError> {
+                let x: f64 = x.parse().map_err(|_| err())?;
+                Ok((x * 1_000_000f64).round() as i64)
+            };
+            let number = |x: &str| -> Result<i64, OdbcHelperError> { x.parse().map_err(|_| err()) };
+            interval.microseconds = match (parts.as_slice(), leading_field.as_str()) {
+                ([minutes, secs], "MINUTE") => number(minutes)? * 60_000_000 + seconds(secs)?,
+                ([hours, minutes], _) => {
+                    number(hours)? * 3_600_000_000 + number(minutes)? * 60_000_000
+                }
+                ([hours, minutes, secs], _) => {
+                    number(hours)? * 3_600_000_000 + number(minutes)? * 60_000_000 + seconds(secs)?
+                }
+                _ => return Err(err()),
+            };
+        } else if days.is_none() {
+            let number: f64 = time.parse().map_err(|_| err())?;
+            match leading_field.as_str() {
+                "YEAR" => interval.months = number as i32 * 12,
+                "MONTH" => interval.months = number as i32,
+                "DAY" => interval.days = number as i32,
+                "HOUR" => interval.microseconds = (number * 3_600_000_000f64) as i64,
+                "MINUTE" => interval.microseconds = (number * 60_000_000f64) as i64,
+                "SECOND" => interval.microseconds = (number * 1_000_000f64).round() as i64,
+                _ => return Err(err()),
+            }
+        } else if !time.is_empty() {
+            // `DAY TO HOUR` value, E.g: `3 04`
+            let hours: i64 = time.parse().map_err(|_| err())?;
+            interval.microseconds = hours * 3_600_000_000;
+        }
+    }
+
+    if negative {
+        interval.months = -interval.months;
+        interval.days = -interval.days;
+        interval.microseconds = -interval.microseconds;
+    }
+    Ok(interval)
+}
+
+/// Parse postgres array literal `{a,"b,c",NULL}` or json array `["a","b,c",null]`,
+/// only one dimension array is supported.
+pub fn parse_to_array(v: BytesMut) -> Result<Vec<Option<String>>, OdbcHelperError> {
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    let text = val.trim();
+    if text.starts_with('[') {
+        let items: Vec<serde_json::Value> = serde_json::from_str(text)
+            .map_err(|e| conversion_error!(source = e, "invalid array:{}", val))?;
+        return Ok(items
+            .into_iter()
+            .map(|x| match x {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(s),
+                x => Some(x.to_string()),
+            })
+            .collect());
+    }
+
+    let inner = text
+        .strip_prefix('{')
+        .and_then(|x| x.strip_suffix('}'))
+        .ok_or_else(|| conversion_error!("invalid array:{}", val))?;
+    let mut items = vec![];
+    if inner.trim().is_empty() {
+        return Ok(items);
+    }
+
+    let (mut item, mut quoted, mut in_quote, mut escape) = (String::new(), false, false, false);
+    for c in inner.chars() {
+        match c {
+            _ if escape => {
+                item.push(c);
+                escape = false;
+            }
+            '\\' => escape = true,
+            '"' => {
+                // whitespace before quoted element is ignored
+                if !quoted {
+                    item.clear();
+                }
+                in_quote = !in_quote;
+                quoted = true;
+            }
+            _ if quoted && !in_quote && c.is_whitespace() => {}
+            ',' if !in_quote => {
+                items.push(array_item(&item, quoted));
+                item.clear();
+                quoted = false;
+            }
+            _ => item.push(c),
+        }
+    }
+    if in_quote {
+        return Err(conversion_error!("invalid array:{}", val));
+    }
+    items.push(array_item(&item, quoted));
+    Ok(items)
+}
+
+fn array_item(item: &str, quoted: bool) -> Option<String> {
+    if quoted {
+        Some(item.to_string())
+    } else if item.trim().eq_ignore_ascii_case("NULL") {
+        None
+    } else {
+        Some(item.trim().to_string())
+    }
+}
+
+/// Parse ip address with optional netmask, E.g: `192.168.0.1`, `10.0.0.0/8`, `::1/128`.
+pub fn parse_to_inet(v: BytesMut) -> Result<(IpAddr, u8), OdbcHelperError> {
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    let (addr, netmask) = match val.trim().split_once('/') {
+        Some((addr, netmask)) => (addr, Some(netmask)),
+        None => (val.trim(), None),
+    };
+    let addr = IpAddr::from_str(addr)
+        .map_err(|e| conversion_error!(source = e, "invalid inet:{}", val))?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let netmask = match netmask {
+        Some(x) => x
+            .parse::<u8>()
+            .map_err(|e| conversion_error!(source = e, "invalid inet netmask:{}", val))?,
+        None => max,
+    };
+    if netmask > max {
+        return Err(conversion_error!("invalid inet netmask:{}", val));
+    }
+    Ok((addr, netmask))
+}
+
+/// Parse money to cents, E.g: `$1,234.56`, `-12.3`, `(5.00)`.
+pub fn parse_to_money(v: BytesMut) -> Result<i64, OdbcHelperError> {
+    let val = String::from_utf8_lossy(v.as_ref()).to_string();
+    let text = val.trim();
+    let (negative, text) = match text.strip_prefix('(').and_then(|x| x.strip_suffix(')')) {
+        Some(x) => (true, x),
+        None => (text.contains('-'), text),
+    };
+    let number: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    if number.is_empty() {
+        return Err(conversion_error!("invalid money:{}", val));
+    }
+    let (units, cents) = match number.split_once('.') {
+        Some((units, cents)) if cents.len() <= 2 => (units, format!("{:0<2}", cents)),
+        Some(_) => return Err(conversion_error!("invalid money:{}", val)),
+        None => (number.as_str(), "00".to_string()),
+    };
+    let err = |e: ParseIntError| conversion_error!(source = e, "invalid money:{}", val);
+    let units: i64 = if units.is_empty() {
+        0
+    } else {
+        units.parse().map_err(err)?
+    };
+    let cents = units * 100 + cents.parse::<i64>().map_err(err)?;
+    Ok(if negative { -cents } else { cents })
+}
@@ -0,0 +1,988 @@
+use crate::error::OdbcHelperError;
+use crate::executor::table::OdbcTableItem;
+use crate::executor::SupportDatabase;
+use crate::extension::util::{parse_to_json, parse_to_uuid};
+use crate::{Convert, TryConvert};
+use arrow_array::{Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::ArrowError;
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use dameng_helper::table::DmTableItem;
+use odbc_api::buffers::{AnySliceMut, BufferDesc};
+use odbc_api::handles::StatementImpl;
+use odbc_api::{Connection, DataType, Prepared};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::file::reader::ChunkReader;
+use pg_helper::{type_from_name, PgType};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"];
+
+const TIME_FORMATS: [&str; 2] = ["%H:%M:%S%.f", "%H:%M"];
+
+const TIMESTAMP_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Comma separated values with header
+    #[default]
+    Csv,
+    /// Tab separated values with header
+    Tsv,
+    Parquet,
+}
+
+impl ImportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    /// Format by file extension: `.csv`, `.tsv` or `.parquet`
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|x| x.to_str())
+            .and_then(|x| x.parse().ok())
+    }
+}
+
+impl Display for ImportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = OdbcHelperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(conversion_error!("unknown import format:{}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub format: ImportFormat,
+    /// Rows of one array insert
+    pub batch_size: usize,
+    /// Text of csv field read as NULL besides the empty field, E.g: `NULL`, `\N`
+    pub null_text: Option<String>,
+}
+
+impl ImportOptions {
+    // Default batch size 1K rows
+    pub const BATCH_SIZE: usize = 1024;
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            format: ImportFormat::default(),
+            batch_size: Self::BATCH_SIZE,
+            null_text: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportResult {
+    /// Inserted rows
+    pub rows: usize,
+    /// Rows written to the reject file
+    pub rejected: usize,
+}
+
+/// Target column of import, the type is described with postgres type whatever the database is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportColumn {
+    pub name: String,
+    pub r#type: PgType,
+    /// Max length of character and binary value, precision of numeric. `None` is unlimited.
+    pub length: Option<usize>,
+    pub scale: usize,
+    pub nullable: bool,
+    /// Character length is counted in bytes, which is the default of dameng `LENGTH_IN_CHAR=0`
+    pub length_in_bytes: bool,
+}
+
+impl TryConvert<ImportColumn> for &DmTableItem {
+    type Error = OdbcHelperError;
+
+    fn try_convert(self) -> Result<ImportColumn, Self::Error> {
+        Ok(ImportColumn {
+            name: self.name.to_string(),
+            r#type: self.r#type.clone().try_convert()?,
+            // 0 means the length or precision is not specified
+            length: (self.length > 0).then_some(self.length),
+            scale: self.scale,
+            nullable: self.nullable,
+            length_in_bytes: true,
+        })
+    }
+}
+
+/// Type name is looked up first, E.g: postgres `int4`, mysql `datetime` falls back to odbc type.
+impl Convert<ImportColumn> for &OdbcTableItem {
+    fn convert(self) -> ImportColumn {
+        let r#type =
+            type_from_name(&self.type_name).unwrap_or_else(|| odbc_pg_type(&self.odbc_data_type()));
+        // Column size of unbounded text and binary is a driver setting, not a limit of the column
+        let length = match r#type {
+            PgType::VARCHAR | PgType::BPCHAR | PgType::NUMERIC => {
+                self.column_size.filter(|x| *x > 0)
+            }
+            _ => None,
+        };
+        ImportColumn {
+            name: self.name.to_string(),
+            r#type,
+            length,
+            scale: self.decimal_digits.unwrap_or_default().max(0) as usize,
+            nullable: self.nullable,
+            length_in_bytes: false,
+        }
+    }
+}
+
+fn odbc_pg_type(data_type: &DataType) -> PgType {
+    match *data_type {
+        DataType::Numeric { .. } | DataType::Decimal { .. } => PgType::NUMERIC,
+        DataType::Integer => PgType::INT4,
+        DataType::SmallInt | DataType::TinyInt => PgType::INT2,
+        DataType::BigInt => PgType::INT8,
+        DataType::Real => PgType::FLOAT4,
+        DataType::Float { precision } if precision <= 24 => PgType::FLOAT4,
+        DataType::Float { .. } | DataType::Double => PgType::FLOAT8,
+        DataType::Bit => PgType::BOOL,
+        DataType::Date => PgType::DATE,
+        DataType::Time { .. } => PgType::TIME,
+        DataType::Timestamp { .. } => PgType::TIMESTAMP,
+        DataType::Char { .. } | DataType::WChar { .. } => PgType::BPCHAR,
+        DataType::Varchar { .. } | DataType::WVarchar { .. } => PgType::VARCHAR,
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+            PgType::BYTEA
+        }
+        DataType::LongVarchar { .. } | DataType::Unknown | DataType::Other { .. } => PgType::TEXT,
+    }
+}
+
+impl ImportColumn {
+    /// Parse value against the column type and return the value bound to insert parameter.
+    /// Numbers, booleans, dates and times are normalized, binary is decoded from hex.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use odbc_api_helper::import::ImportColumn;
+    /// use pg_helper::PgType;
+    ///
+    /// let column = ImportColumn {
+    ///     name: "CREATED".to_string(),
+    ///     r#type: PgType::TIMESTAMP,
+    ///     length: None,
+    ///     scale: 0,
+    ///     nullable: false,
+    ///     length_in_bytes: false,
+    /// };
+    /// let value = column.coerce(Some("2022/10/01 08:00:00")).unwrap();
+    /// assert_eq!(value, Some(b"2022-10-01 08:00:00".to_vec()));
+    /// assert!(column.coerce(None).is_err());
+    /// ```
+    pub fn coerce(&self, value: Option<&str>) -> Result<Option<Vec<u8>>, OdbcHelperError> {
+        let value = match value {
+            Some(value) => value,
+            None if self.nullable => return Ok(None),
+            None => return Err(conversion_error!("{}: NULL in NOT NULL column", self.name)),
+        };
+        let trimmed = value.trim();
+        let invalid = |kind: &str| conversion_error!("{}: invalid {}:{}", self.name, kind, value);
+
+        let coerced = match self.r#type {
+            PgType::BOOL => match trimmed.to_lowercase().as_str() {
+                "1" | "t" | "true" | "y" | "yes" | "on" => "1".to_string(),
+                "0" | "f" | "false" | "n" | "no" | "off" => "0".to_string(),
+                _ => return Err(invalid("bool")),
+            },
+            PgType::INT2 => parse_number::<i16>(trimmed).ok_or_else(|| invalid("int2"))?,
+            PgType::INT4 => parse_number::<i32>(trimmed).ok_or_else(|| invalid("int4"))?,
+            PgType::INT8 => parse_number::<i64>(trimmed).ok_or_else(|| invalid("int8"))?,
+            PgType::FLOAT4 | PgType::FLOAT8 => match trimmed.parse::<f64>() {
+                Ok(x) if x.is_finite() => trimmed.to_string(),
+                _ => return Err(invalid("float")),
+            },
+            PgType::NUMERIC => {
+                if !is_decimal(trimmed) {
+                    return Err(invalid("numeric"));
+                }
+                if let Some(precision) = self.length {
+                    let (integer, _) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+                    let digits = integer
+                        .trim_start_matches(['-', '+'])
+                        .trim_start_matches('0')
+                        .len();
+                    if digits + self.scale > precision {
+                        return Err(conversion_error!(
+                            "{}: {} overflows numeric({}, {})",
+                            self.name,
+                            value,
+                            precision,
+                            self.scale
+                        ));
+                    }
+                }
+                trimmed.to_string()
+            }
+            PgType::DATE => parse_date(trimmed)
+                .ok_or_else(|| invalid("date"))?
+                .format("%Y-%m-%d")
+                .to_string(),
+            PgType::TIME => parse_time(trimmed)
+                .ok_or_else(|| invalid("time"))?
+                .format("%H:%M:%S%.f")
+                .to_string(),
+            PgType::TIMESTAMP => parse_timestamp(trimmed)
+                .ok_or_else(|| invalid("timestamp"))?
+                .format("%Y-%m-%d %H:%M:%S%.f")
+                .to_string(),
+            PgType::UUID => parse_to_uuid(BytesMut::from(trimmed))
+                .map_err(|e| conversion_error!(source = e, "{}", self.name))?,
+            PgType::JSON | PgType::JSONB => {
+                parse_to_json(BytesMut::from(value))
+                    .map_err(|e| conversion_error!(source = e, "{}", self.name))?;
+                value.to_string()
+            }
+            PgType::BYTEA => {
+                let bytes = parse_hex(trimmed).ok_or_else(|| invalid("hex binary"))?;
+                self.check_length(bytes.len())?;
+                return Ok(Some(bytes));
+            }
+            PgType::VARCHAR | PgType::BPCHAR | PgType::TEXT | PgType::CHAR | PgType::NAME => {
+                let len = if self.length_in_bytes {
+                    value.len()
+                } else {
+                    value.chars().count()
+                };
+                self.check_length(len)?;
+                value.to_string()
+            }
+            _ => value.to_string(),
+        };
+        Ok(Some(coerced.into_bytes()))
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.r#type == PgType::BYTEA
+    }
+
+    fn check_length(&self, len: usize) -> Result<(), OdbcHelperError> {
+        match self.length {
+            Some(max) if len > max => Err(conversion_error!(
+                "{}: value length {} exceeds {}",
+                self.name,
+                len,
+                max
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_number<T: FromStr + ToString>(text: &str) -> Option<String> {
+    text.parse::<T>().ok().map(|x| x.to_string())
+}
+
+/// Plain decimal without exponent, E.g: `-12.50`, `.5`
+fn is_decimal(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    !(integer.is_empty() && fraction.is_empty())
+        && integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|x| x.is_ascii_digit())
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|x| NaiveDate::parse_from_str(text, x).ok())
+}
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    TIME_FORMATS
+        .iter()
+        .find_map(|x| NaiveTime::parse_from_str(text, x).ok())
+}
+
+/// Timestamp with offset is converted to UTC, date without time is midnight
+fn parse_timestamp(text: &str) -> Option<NaiveDateTime> {
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|x| NaiveDateTime::parse_from_str(text, x).ok())
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(text)
+                .ok()
+                .map(|x| x.naive_utc())
+        })
+        .or_else(|| parse_date(text).and_then(|x| x.and_hms_opt(0, 0, 0)))
+}
+
+/// Hex with optional `\x` or `0x` prefix
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let hex = ["\\x", "0x", "0X"]
+        .iter()
+        .find_map(|x| text.strip_prefix(x))
+        .unwrap_or(text);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRow {
+    /// Line number of csv, row number of parquet, start with 1
+    pub number: usize,
+    pub values: Vec<Option<String>>,
+    /// Reason if the row could not be read, E.g: invalid utf-8 or wrong number of fields
+    pub invalid: Option<String>,
+}
+
+type ImportRows = Box<dyn Iterator<Item = Result<ImportRow, OdbcHelperError>>>;
+
+/// Rows of csv or parquet file with the column names of header
+#[allow(missing_debug_implementations)]
+pub struct ImportFile {
+    pub headers: Vec<String>,
+    rows: ImportRows,
+}
+
+impl ImportFile {
+    pub fn open<P: AsRef<Path>>(path: P, options: &ImportOptions) -> Result<Self, OdbcHelperError> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| conversion_error!(source = e, "open import file {}", path.display()))?;
+        match options.format {
+            ImportFormat::Csv | ImportFormat::Tsv => Self::from_csv(file, options),
+            ImportFormat::Parquet => Self::from_parquet(file, options),
+        }
+    }
+
+    /// Read csv with header, empty field and `null_text` are NULL
+    pub fn from_csv<R: Read + 'static>(
+        reader: R,
+        options: &ImportOptions,
+    ) -> Result<Self, OdbcHelperError> {
+        let delimiter = match options.format {
+            ImportFormat::Tsv => b'\t',
+            _ => b',',
+        };
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_reader(reader);
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|x| x.trim().to_string())
+            .collect();
+        let fields = headers.len();
+        let null_text = options.null_text.clone();
+
+        let rows = reader.into_byte_records().map(move |record| {
+            let record = record?;
+            let number = record
+                .position()
+                .map(|x| x.line() as usize)
+                .unwrap_or_default();
+            let mut invalid = (record.len() != fields)
+                .then(|| format!("expected {} fields but got {}", fields, record.len()));
+            let values = record
+                .iter()
+                .map(|field| {
+                    let value = String::from_utf8(field.to_vec()).unwrap_or_else(|e| {
+                        invalid.get_or_insert_with(|| "invalid utf-8".to_string());
+                        String::from_utf8_lossy(e.as_bytes()).to_string()
+                    });
+                    let null = value.is_empty() || null_text.as_deref() == Some(value.as_str());
+                    (!null).then_some(value)
+                })
+                .collect();
+            Ok(ImportRow {
+                number,
+                values,
+                invalid,
+            })
+        });
+        Ok(Self {
+            headers,
+            rows: Box::new(rows),
+        })
+    }
+
+    /// Read parquet, values are formatted as text and coerced like csv fields
+    pub fn from_parquet<R: ChunkReader + 'static>(
+        reader: R,
+        options: &ImportOptions,
+    ) -> Result<Self, OdbcHelperError> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+        let headers = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|x| x.name().to_string())
+            .collect();
+        let batches = builder.with_batch_size(options.batch_size.max(1)).build()?;
+
+        let mut number = 0;
+        let rows = batches.flat_map(move |batch| {
+            let rows = batch_rows(batch, number);
+            number += rows.len();
+            rows
+        });
+        Ok(Self {
+            headers,
+            rows: Box::new(rows),
+        })
+    }
+}
+
+impl Iterator for ImportFile {
+    type Item = Result<ImportRow, OdbcHelperError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next()
+    }
+}
+
+fn batch_rows(
+    batch: Result<RecordBatch, ArrowError>,
+    first: usize,
+) -> Vec<Result<ImportRow, OdbcHelperError>> {
+    let batch = match batch {
+        Ok(batch) => batch,
+        Err(e) => return vec![Err(e.into())],
+    };
+    let options = FormatOptions::default();
+    let formatters = match batch
+        .columns()
+        .iter()
+        .map(|x| ArrayFormatter::try_new(x.as_ref(), &options))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(formatters) => formatters,
+        Err(e) => return vec![Err(e.into())],
+    };
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let values = batch
+                .columns()
+                .iter()
+                .zip(formatters.iter())
+                .map(|(array, formatter)| {
+                    (!array.is_null(row)).then(|| formatter.value(row).to_string())
+                })
+                .collect();
+            Ok(ImportRow {
+                number: first + row + 1,
+                values,
+                invalid: None,
+            })
+        })
+        .collect()
+}
+
+/// Savepoint of the batch being inserted, see [`import_rows`]
+const IMPORT_SAVEPOINT: &str = "odbc_import";
+
+/// Coerce rows of file against `columns` in the order of file headers, and insert them with array
+/// parameters of `prepared` batch by batch. Rows which could not be read or coerced are written to
+/// `rejects` as csv with the row number and reason, the header is written before the first one.
+///
+/// Each batch is inserted in a savepoint of `connection`, so it must run in a transaction. If the
+/// database rejects a batch, it is rolled back to the savepoint and retried row by row, the rows
+/// failed with database error are rejected with the error message. Other errors are returned,
+/// the caller should rollback.
+pub fn import_rows<W: Write>(
+    connection: &Connection<'_>,
+    prepared: &mut Prepared<StatementImpl<'_>>,
+    database: &SupportDatabase,
+    columns: &[ImportColumn],
+    file: ImportFile,
+    rejects: W,
+    options: &ImportOptions,
+) -> Result<ImportResult, OdbcHelperError> {
+    let mut rejects = Rejects::new(rejects, &file.headers);
+    let mut importer = BatchImporter {
+        connection,
+        prepared,
+        database,
+        columns,
+    };
+
+    let batch_size = options.batch_size.max(1);
+    let mut batch: Vec<BatchRow> = Vec::with_capacity(batch_size);
+    let mut result = ImportResult::default();
+    for row in file {
+        let row = row?;
+        match coerce_row(columns, &row) {
+            Ok(params) => batch.push(BatchRow {
+                number: row.number,
+                values: row.values,
+                params,
+            }),
+            Err(reason) => rejects.write(row.number, reason, &row.values)?,
+        }
+        if batch.len() == batch_size {
+            result.rows += importer.import(&batch, &mut rejects)?;
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        result.rows += importer.import(&batch, &mut rejects)?;
+    }
+    result.rejected = rejects.finish()?;
+    Ok(result)
+}
+
+/// Coerced row of batch with the file values kept for reject file
+struct BatchRow {
+    number: usize,
+    values: Vec<Option<String>>,
+    params: Vec<Option<Vec<u8>>>,
+}
+
+/// Csv writer of rejected rows, the header is written before the first one
+struct Rejects<W: Write> {
+    writer: csv::Writer<W>,
+    header: Vec<String>,
+    count: usize,
+}
+
+impl<W: Write> Rejects<W> {
+    fn new(writer: W, headers: &[String]) -> Self {
+        let mut header = vec!["ROW".to_string(), "REASON".to_string()];
+        header.extend(headers.iter().cloned());
+        Self {
+            writer: csv::Writer::from_writer(writer),
+            header,
+            count: 0,
+        }
+    }
+
+    fn write(
+        &mut self,
+        number: usize,
+        reason: String,
+        values: &[Option<String>],
+    ) -> Result<(), OdbcHelperError> {
+        debug!("reject row {}:{}", number, reason);
+        if self.count == 0 {
+            self.writer.write_record(&self.header)?;
+        }
+        let mut record = vec![number.to_string(), reason];
+        record.extend(values.iter().map(|x| x.clone().unwrap_or_default()));
+        self.writer.write_record(&record)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Flush and return the count of rejected rows
+    fn finish(mut self) -> Result<usize, OdbcHelperError> {
+        self.writer
+            .flush()
+            .map_err(|e| OdbcHelperError::CsvError(e.into()))?;
+        Ok(self.count)
+    }
+}
+
+struct BatchImporter<'a, 's> {
+    connection: &'a Connection<'a>,
+    prepared: &'a mut Prepared<StatementImpl<'s>>,
+    database: &'a SupportDatabase,
+    columns: &'a [ImportColumn],
+}
+
+impl BatchImporter<'_, '_> {
+    /// Insert batch in savepoint, retry row by row if the database rejects it
+    fn import<W: Write>(
+        &mut self,
+        batch: &[BatchRow],
+        rejects: &mut Rejects<W>,
+    ) -> Result<usize, OdbcHelperError> {
+        self.savepoint("SAVEPOINT")?;
+        match self.insert(batch) {
+            Ok(rows) => {
+                self.release_savepoint()?;
+                return Ok(rows);
+            }
+            Err(OdbcHelperError::DiagnosticError { error, .. }) => {
+                debug!("batch rejected by database:{}, retry row by row", error);
+                self.savepoint("ROLLBACK TO SAVEPOINT")?;
+            }
+            Err(e) => return Err(e),
+        }
+
+        let mut rows = 0;
+        for row in batch {
+            self.savepoint("SAVEPOINT")?;
+            match self.insert(std::slice::from_ref(row)) {
+                Ok(n) => {
+                    self.release_savepoint()?;
+                    rows += n;
+                }
+                Err(OdbcHelperError::DiagnosticError { error, .. }) => {
+                    self.savepoint("ROLLBACK TO SAVEPOINT")?;
+                    rejects.write(row.number, error.to_string(), &row.values)?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(rows)
+    }
+
+    fn savepoint(&self, command: &str) -> Result<(), OdbcHelperError> {
+        let sql = format!("{} {}", command, IMPORT_SAVEPOINT);
+        self.connection
+            .execute(&sql, ())
+            .map_err(|e| OdbcHelperError::from_odbc(self.database, e))?;
+        Ok(())
+    }
+
+    /// Dameng has no `RELEASE SAVEPOINT`, a savepoint with the same name replaces the old one
+    fn release_savepoint(&self) -> Result<(), OdbcHelperError> {
+        match self.database {
+            SupportDatabase::Dameng => Ok(()),
+            _ => self.savepoint("RELEASE SAVEPOINT"),
+        }
+    }
+
+    fn insert(&mut self, batch: &[BatchRow]) -> Result<usize, OdbcHelperError> {
+        insert_batch(self.prepared, self.database, self.columns, batch)
+    }
+}
+
+fn coerce_row(columns: &[ImportColumn], row: &ImportRow) -> Result<Vec<Option<Vec<u8>>>, String> {
+    if let Some(invalid) = &row.invalid {
+        return Err(invalid.to_string());
+    }
+    columns
+        .iter()
+        .zip(row.values.iter())
+        .map(|(column, value)| column.coerce(value.as_deref()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| error_reason(&e))
+}
+
+/// Error message followed by its sources, the reason of rejected row
+fn error_reason(error: &OdbcHelperError) -> String {
+    let mut reason = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        reason.push_str(": ");
+        reason.push_str(&e.to_string());
+        source = e.source();
+    }
+    reason
+}
+
+/// Bind text or binary buffers sized by the longest value of batch, and execute once
+fn insert_batch(
+    prepared: &mut Prepared<StatementImpl<'_>>,
+    database: &SupportDatabase,
+    columns: &[ImportColumn],
+    batch: &[BatchRow],
+) -> Result<usize, OdbcHelperError> {
+    let descs = columns.iter().enumerate().map(|(index, column)| {
+        let max_len = batch
+            .iter()
+            .filter_map(|row| row.params[index].as_ref().map(|x| x.len()))
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        if column.is_binary() {
+            BufferDesc::Binary { length: max_len }
+        } else {
+            BufferDesc::Text {
+                max_str_len: max_len,
+            }
+        }
+    });
+    let mut inserter = prepared
+        .column_inserter(batch.len(), descs)
+        .map_err(|e| OdbcHelperError::from_odbc(database, e))?;
+    inserter.set_num_rows(batch.len());
+    for index in 0..columns.len() {
+        let cells = batch
+            .iter()
+            .map(|row| row.params[index].as_deref())
+            .enumerate();
+        match inserter.column_mut(index) {
+            AnySliceMut::Text(mut column) => cells.for_each(|(row, x)| column.set_cell(row, x)),
+            AnySliceMut::Binary(mut column) => cells.for_each(|(row, x)| column.set_cell(row, x)),
+            _ => unreachable!("import parameters are bound as text or binary"),
+        }
+    }
+    inserter
+        .execute()
+        .map_err(|e| OdbcHelperError::from_odbc(database, e))?;
+    Ok(batch.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{ArrayRef, BinaryArray, Date32Array, Int32Array, StringArray};
+    use parquet::arrow::ArrowWriter;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    fn column(r#type: PgType, length: Option<usize>, scale: usize) -> ImportColumn {
+        ImportColumn {
+            name: "C1".to_string(),
+            r#type,
+            length,
+            scale,
+            nullable: true,
+            length_in_bytes: false,
+        }
+    }
+
+    fn coerce(column: &ImportColumn, value: &str) -> Result<String, OdbcHelperError> {
+        column
+            .coerce(Some(value))
+            .map(|x| String::from_utf8(x.unwrap()).unwrap())
+    }
+
+    #[test]
+    fn test_coerce() {
+        let bool_column = column(PgType::BOOL, None, 0);
+        assert_eq!(coerce(&bool_column, " Yes ").unwrap(), "1");
+        assert_eq!(coerce(&bool_column, "f").unwrap(), "0");
+        assert!(coerce(&bool_column, "2").is_err());
+
+        let int_column = column(PgType::INT2, None, 0);
+        assert_eq!(coerce(&int_column, " 12 ").unwrap(), "12");
+        assert!(coerce(&int_column, "40000").is_err());
+        assert!(coerce(&int_column, "1.5").is_err());
+        assert!(coerce(&column(PgType::FLOAT8, None, 0), "NaN").is_err());
+
+        let numeric_column = column(PgType::NUMERIC, Some(5), 2);
+        assert_eq!(coerce(&numeric_column, "-123.456").unwrap(), "-123.456");
+        assert_eq!(coerce(&numeric_column, "000123").unwrap(), "000123");
+        assert!(coerce(&numeric_column, "1234").is_err());
+        assert!(coerce(&numeric_column, "1e3").is_err());
+
+        let date_column = column(PgType::DATE, None, 0);
+        assert_eq!(coerce(&date_column, "20221001").unwrap(), "2022-10-01");
+        assert!(coerce(&date_column, "2022-02-30").is_err());
+        assert_eq!(
+            coerce(&column(PgType::TIME, None, 0), "08:30").unwrap(),
+            "08:30:00"
+        );
+        let timestamp_column = column(PgType::TIMESTAMP, None, 0);
+        assert_eq!(
+            coerce(&timestamp_column, "2022-10-01T08:00:00.5").unwrap(),
+            "2022-10-01 08:00:00.500"
+        );
+        assert_eq!(
+            coerce(&timestamp_column, "2022-10-01T08:00:00+08:00").unwrap(),
+            "2022-10-01 00:00:00"
+        );
+        assert_eq!(
+            coerce(&timestamp_column, "2022-10-01").unwrap(),
+            "2022-10-01 00:00:00"
+        );
+
+        let varchar_column = column(PgType::VARCHAR, Some(2), 0);
+        assert_eq!(coerce(&varchar_column, "达梦").unwrap(), "达梦");
+        let bytes_column = ImportColumn {
+            length_in_bytes: true,
+            ..varchar_column
+        };
+        assert!(coerce(&bytes_column, "达梦").is_err());
+
+        let binary_column = column(PgType::BYTEA, Some(2), 0);
+        assert_eq!(
+            binary_column.coerce(Some("\\x0aFF")).unwrap(),
+            Some(vec![10, 255])
+        );
+        assert!(binary_column.coerce(Some("0a0b0c")).is_err());
+        assert!(binary_column.coerce(Some("0g")).is_err());
+
+        let not_null = ImportColumn {
+            nullable: false,
+            ..column(PgType::TEXT, None, 0)
+        };
+        assert!(not_null.coerce(None).is_err());
+        assert_eq!(column(PgType::TEXT, None, 0).coerce(None).unwrap(), None);
+
+        let error = coerce(&column(PgType::UUID, None, 0), "x").unwrap_err();
+        assert!(std::error::Error::source(&error).is_some());
+        assert!(error_reason(&error).starts_with("type conversion error:C1: "));
+    }
+
+    #[test]
+    fn test_import_column() {
+        let item = DmTableItem {
+            name: "PRICE".to_string(),
+            r#type: dameng_helper::DmDateType::DECIMAL,
+            length: 10,
+            scale: 2,
+            ..Default::default()
+        };
+        let column: ImportColumn = (&item).try_convert().unwrap();
+        assert_eq!(column.r#type, PgType::NUMERIC);
+        assert_eq!(column.length, Some(10));
+        assert!(column.length_in_bytes);
+
+        let item = OdbcTableItem {
+            name: "CREATED".to_string(),
+            data_type: 93,
+            type_name: "datetime".to_string(),
+            column_size: Some(19),
+            ..Default::default()
+        };
+        let column: ImportColumn = (&item).convert();
+        assert_eq!(column.r#type, PgType::TIMESTAMP);
+        assert_eq!(column.length, None);
+
+        let item = OdbcTableItem {
+            name: "NAME".to_string(),
+            data_type: 12,
+            type_name: "varchar".to_string(),
+            column_size: Some(20),
+            ..Default::default()
+        };
+        assert_eq!((&item).convert().length, Some(20));
+    }
+
+    #[test]
+    fn test_import_file() {
+        let csv = "ID,NAME\n1,a\n2,\n3,b,c\n4,NULL\n";
+        let options = ImportOptions {
+            null_text: Some("NULL".to_string()),
+            ..Default::default()
+        };
+        let file = ImportFile::from_csv(Cursor::new(csv), &options).unwrap();
+        assert_eq!(file.headers, vec!["ID", "NAME"]);
+        let rows = file.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].number, 2);
+        assert_eq!(rows[1].values, vec![Some("2".to_string()), None]);
+        assert!(rows[2].invalid.is_some());
+        assert_eq!(rows[3].values[1], None);
+
+        let columns = vec![
+            ImportColumn {
+                name: "ID".to_string(),
+                nullable: false,
+                ..column(PgType::INT4, None, 0)
+            },
+            column(PgType::VARCHAR, Some(1), 0),
+        ];
+        assert!(coerce_row(&columns, &rows[0]).is_ok());
+        assert!(coerce_row(&columns, &rows[2]).is_err());
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "ID",
+                Arc::new(Int32Array::from(vec![Some(1), None])) as ArrayRef,
+            ),
+            (
+                "NAME",
+                Arc::new(StringArray::from(vec![Some("a"), Some("b")])) as ArrayRef,
+            ),
+            (
+                "BORN",
+                Arc::new(Date32Array::from(vec![Some(0), None])) as ArrayRef,
+            ),
+            (
+                "DATA",
+                Arc::new(BinaryArray::from(vec![Some(&[10u8, 255][..]), None])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let options = ImportOptions {
+            format: ImportFormat::Parquet,
+            batch_size: 1,
+            ..Default::default()
+        };
+        let file = ImportFile::from_parquet(bytes::Bytes::from(buf), &options).unwrap();
+        assert_eq!(file.headers, vec!["ID", "NAME", "BORN", "DATA"]);
+        let rows = file.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].number, 2);
+        assert_eq!(
+            rows[0].values,
+            vec![
+                Some("1".to_string()),
+                Some("a".to_string()),
+                Some("1970-01-01".to_string()),
+                Some("0aff".to_string())
+            ]
+        );
+        assert_eq!(rows[1].values[0], None);
+        assert_eq!(
+            column(PgType::BYTEA, None, 0)
+                .coerce(rows[0].values[3].as_deref())
+                .unwrap(),
+            Some(vec![10, 255])
+        );
+
+        assert_eq!(
+            ImportFormat::from_path("a.PARQUET"),
+            Some(ImportFormat::Parquet)
+        );
+        assert_eq!(ImportFormat::from_path("a.xlsx"), None);
+    }
+
+    #[test]
+    fn test_rejects() {
+        let mut buf = vec![];
+        let mut rejects = Rejects::new(&mut buf, &["ID".to_string(), "NAME".to_string()]);
+        rejects
+            .write(2, "invalid integer".to_string(), &[Some("x".into()), None])
+            .unwrap();
+        rejects
+            .write(
+                5,
+                "unique constraint".to_string(),
+                &[Some("1".into()), Some("a,b".into())],
+            )
+            .unwrap();
+        assert_eq!(rejects.finish().unwrap(), 2);
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "ROW,REASON,ID,NAME\n2,invalid integer,x,\n5,unique constraint,1,\"a,b\"\n"
+        );
+    }
+}
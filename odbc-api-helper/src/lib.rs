@@ -1,8 +1,5 @@
 #![deny(missing_debug_implementations)]
 
-#[macro_use]
-extern crate anyhow;
-
 #[macro_use]
 extern crate log;
 
@@ -11,12 +8,74 @@ pub extern crate pg_helper;
 
 pub use odbc_api;
 
+/// Build [`error::OdbcHelperError::SqlError`] with format arguments
+macro_rules! sql_error {
+    ($($arg:tt)*) => {
+        $crate::error::OdbcHelperError::SqlError(format!($($arg)*))
+    };
+}
+
+/// Build [`error::OdbcHelperError::TypeConversionError`] with format arguments, the underlying
+/// error is passed first as `source = e`.
+macro_rules! conversion_error {
+    (source = $source:expr, $($arg:tt)*) => {
+        $crate::error::OdbcHelperError::TypeConversionError {
+            message: format!($($arg)*),
+            source: Some($source.into()),
+        }
+    };
+    ($($arg:tt)*) => {
+        $crate::error::OdbcHelperError::TypeConversionError {
+            message: format!($($arg)*),
+            source: None,
+        }
+    };
+}
+
+/// Build [`error::OdbcHelperError::MetadataParseError`] like [`conversion_error`]
+macro_rules! metadata_error {
+    (source = $source:expr, $($arg:tt)*) => {
+        $crate::error::OdbcHelperError::MetadataParseError {
+            message: format!($($arg)*),
+            source: Some($source.into()),
+        }
+    };
+    ($($arg:tt)*) => {
+        $crate::error::OdbcHelperError::MetadataParseError {
+            message: format!($($arg)*),
+            source: None,
+        }
+    };
+}
+
+/// Build [`error::OdbcHelperError::SqlParamsError`] with format arguments
+macro_rules! params_error {
+    ($($arg:tt)*) => {
+        $crate::error::OdbcHelperError::SqlParamsError {
+            message: format!($($arg)*),
+            source: None,
+        }
+    };
+}
+
+/// Build [`error::OdbcHelperError::ExportError`] with format arguments
+macro_rules! export_error {
+    ($($arg:tt)*) => {
+        $crate::error::OdbcHelperError::ExportError(format!($($arg)*))
+    };
+}
+
 pub mod bridge;
+pub mod diff;
 pub mod error;
 pub mod executor;
+pub mod export;
 pub mod extension;
+pub mod import;
 
-pub use odbc_common::Print;
+pub use odbc_common::print_table::{BinaryFormat, Overflow, PrintOptions};
+pub use odbc_common::stream_print::{ColumnWidth, StreamPrint};
+pub use odbc_common::{OutputFormat, Print, TableData};
 
 pub trait Convert<T>: Sized {
     fn convert(self) -> T;
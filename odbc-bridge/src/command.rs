@@ -0,0 +1,460 @@
+use crate::profile::Profiles;
+use crate::repl::Repl;
+use crate::sql::{insert_sql, split_statements, TextValue};
+use crate::{Command, ConnectArgs, PrintArgs};
+use anyhow::{anyhow, Context};
+use odbc_api_helper::diff::{ColumnDiff, SchemaDiff, TableDiff};
+use odbc_api_helper::error::OdbcHelperError;
+use odbc_api_helper::executor::database::{ConnectionTrait, OdbcDbConnection, Options};
+use odbc_api_helper::executor::statement::Statement;
+use odbc_api_helper::executor::table::TableDescResultInner;
+use odbc_api_helper::executor::SupportDatabase;
+use odbc_api_helper::export::{ExportFormat, ExportOptions};
+use odbc_api_helper::import::{ImportFormat, ImportOptions};
+use odbc_api_helper::odbc_api::buffers::{AnySlice, AnySliceMut, BufferDesc, ColumnarAnyBuffer};
+use odbc_api_helper::odbc_api::{self, Cursor, DataType, Environment, ResultSetMetadata};
+use odbc_api_helper::{OutputFormat, Print, PrintOptions, StreamPrint, TableData};
+use simple_log::debug;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub fn run(command: Command) -> anyhow::Result<()> {
+    let env = Environment::new()?;
+    match command {
+        Command::Query {
+            connect,
+            print,
+            sql,
+            params,
+        } => {
+            let (connection, _) = connect.connect(&env)?;
+            let cursor = connection.query_cursor(Statement::new(sql, text_values(params)))?;
+            let mut stdout = io::stdout().lock();
+            cursor.stream_to(&mut stdout, print.print_options(OutputFormat::Table))?;
+            stdout.flush()?;
+        }
+        Command::Exec {
+            connect,
+            print,
+            sql,
+            params,
+        } => {
+            let (connection, _) = connect.connect(&env)?;
+            let result = connection.execute(Statement::new(sql, text_values(params)))?;
+            print_summary(
+                &print,
+                &["ROWS_AFFECTED"],
+                vec![vec![result.rows_affected.to_string()]],
+            )?;
+        }
+        Command::Describe {
+            connect,
+            print,
+            schema,
+            tables,
+        } => {
+            let (connection, default_schema) = connect.connect(&env)?;
+            let schema = schema.unwrap_or(default_schema);
+            let desc = connection.show_table(&schema, tables)?;
+            let mut stdout = io::stdout().lock();
+            TableDescResultInner::from(desc)
+                .write_to(&mut stdout, print.print_options(OutputFormat::Table))?;
+            stdout.flush()?;
+        }
+        Command::Export {
+            connect,
+            print,
+            schema,
+            table,
+            query,
+            compression,
+            row_group_size,
+            file,
+        } => {
+            let (connection, default_schema) = connect.connect(&env)?;
+            if let Some(format) = ExportFormat::from_path(&file) {
+                let export_options = ExportOptions {
+                    format,
+                    compression,
+                    row_group_size,
+                };
+                let writer = BufWriter::new(
+                    File::create(&file).with_context(|| format!("create export file {}", file))?,
+                );
+                let rows = match (query, table) {
+                    (Some(query), _) => connection.export_query(query, writer, &export_options)?,
+                    (None, Some(table)) => connection.export_table(
+                        schema.as_deref().unwrap_or(&default_schema),
+                        &table,
+                        writer,
+                        &export_options,
+                    )?,
+                    (None, None) => anyhow::bail!("either --table or --query is required"),
+                };
+                print_summary(
+                    &print,
+                    &["FILE", "ROWS"],
+                    vec![vec![file, rows.to_string()]],
+                )?;
+                return Ok(());
+            }
+
+            let sql = match (query, table) {
+                (Some(query), _) => query,
+                (None, Some(table)) => format!(
+                    "SELECT * FROM {}",
+                    connection.quote_table(schema.as_deref().unwrap_or(&default_schema), &table)
+                ),
+                (None, None) => anyhow::bail!("either --table or --query is required"),
+            };
+            let options = PrintOptions {
+                color: false,
+                ..print.print_options(file_format(&file))
+            };
+
+            let cursor = connection.query_cursor(sql)?;
+            let mut writer = BufWriter::new(
+                File::create(&file).with_context(|| format!("create export file {}", file))?,
+            );
+            let rows = cursor.stream_to(&mut writer, options)?;
+            writer.flush()?;
+            print_summary(
+                &print,
+                &["FILE", "ROWS"],
+                vec![vec![file, rows.to_string()]],
+            )?;
+        }
+        Command::Import {
+            connect,
+            print,
+            schema,
+            table,
+            batch_size,
+            null_value,
+            reject_file,
+            file,
+        } => {
+            let (connection, default_schema) = connect.connect(&env)?;
+            let schema = schema.unwrap_or(default_schema);
+            let import_options = ImportOptions {
+                format: ImportFormat::from_path(&file).unwrap_or_default(),
+                batch_size,
+                null_text: null_value,
+            };
+            let reject_file = reject_file.unwrap_or_else(|| format!("{}.rejects.csv", file));
+            let rejects = BufWriter::new(
+                File::create(&reject_file)
+                    .with_context(|| format!("create reject file {}", reject_file))?,
+            );
+            let result = transaction(&connection, || {
+                Ok(connection.import_file(&schema, &table, &file, rejects, &import_options)?)
+            })?;
+            let reject_file = if result.rejected == 0 {
+                std::fs::remove_file(&reject_file)?;
+                String::new()
+            } else {
+                reject_file
+            };
+            print_summary(
+                &print,
+                &["TABLE", "ROWS", "REJECTED", "REJECT_FILE"],
+                vec![vec![
+                    format!("{}.{}", schema, table),
+                    result.rows.to_string(),
+                    result.rejected.to_string(),
+                    reject_file,
+                ]],
+            )?;
+        }
+        Command::Migrate {
+            connect,
+            print,
+            schema,
+            tables,
+            target_profile,
+            target_database,
+            target_schema,
+            schema_only,
+        } => {
+            let (source, default_schema) = connect.connect(&env)?;
+            let (target, target_default_schema) =
+                connect.connect_profile(&env, &target_profile, target_database)?;
+            let schema = schema.unwrap_or(default_schema);
+            let target_schema = target_schema.unwrap_or(target_default_schema);
+
+            let source_desc = source.schema_desc(&schema, tables)?;
+            let target_desc = target.schema_desc(&target_schema, vec![])?;
+            let case_sensitive = source.options.case_sensitive;
+            let diff = additive_diff(target_desc.diff(&source_desc, case_sensitive));
+            let ddl = diff.to_alter_sql(
+                &target.options.database,
+                Some(&target_schema),
+                case_sensitive,
+            )?;
+            for sql in split_statements(&ddl).0 {
+                debug!("migrate ddl:{}", sql);
+                target.execute(sql)?;
+            }
+
+            let mut summary = vec![];
+            for (table, columns) in source_desc.data.iter() {
+                let action = diff
+                    .tables
+                    .iter()
+                    .find_map(|x| match x {
+                        TableDiff::Added { table_name, .. } if table_name == table => {
+                            Some("CREATE")
+                        }
+                        TableDiff::Changed { table_name, .. } if table_name == table => {
+                            Some("ALTER")
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                let rows = if schema_only {
+                    0
+                } else {
+                    let mut columns = columns.clone();
+                    columns.sort_by_key(|x| x.col_index);
+                    let columns: Vec<String> = columns.into_iter().map(|x| x.name).collect();
+                    copy_table(
+                        &source,
+                        &source.quote_table(&schema, table),
+                        &target,
+                        &target.quote_table(&target_schema, table),
+                        &columns,
+                    )?
+                };
+                summary.push(vec![
+                    table.to_string(),
+                    action.to_string(),
+                    rows.to_string(),
+                ]);
+            }
+            print_summary(&print, &["TABLE", "ACTION", "ROWS"], summary)?;
+        }
+        Command::Repl { connect, print } => {
+            let (connection, default_schema) = connect.connect(&env)?;
+            Repl::new(
+                connection,
+                default_schema,
+                print.print_options(OutputFormat::Table),
+            )
+            .run()?;
+        }
+    }
+    Ok(())
+}
+
+impl ConnectArgs {
+    /// Connect with profile and return the default schema of profile
+    fn connect<'e>(&self, env: &'e Environment) -> anyhow::Result<(OdbcDbConnection<'e>, String)> {
+        self.connect_profile(env, &self.profile, self.database)
+    }
+
+    /// Connect with another profile in the same profiles file
+    fn connect_profile<'e>(
+        &self,
+        env: &'e Environment,
+        name: &str,
+        database: Option<SupportDatabase>,
+    ) -> anyhow::Result<(OdbcDbConnection<'e>, String)> {
+        let profiles = Profiles::from_file(self.profiles.as_deref())?;
+        let profile = profiles.get(name)?;
+        debug!("profile {}:{:?}", name, profile);
+        let database = match database {
+            Some(database) => database,
+            None => profile.kind()?,
+        };
+
+        let config = profile.connection_config(database)?;
+        let connection =
+            OdbcDbConnection::connect_with_options(env, &config, profile.options(&config))?;
+        Ok((connection, profile.default_schema().to_string()))
+    }
+}
+
+fn text_values(params: Vec<String>) -> Vec<TextValue> {
+    params.into_iter().map(|x| TextValue(Some(x))).collect()
+}
+
+/// `schema.table` with the default schema if `schema` is not set
+/// Export format by file extension, default is csv
+fn file_format(file: &str) -> OutputFormat {
+    Path::new(file)
+        .extension()
+        .and_then(|x| x.to_str())
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(OutputFormat::Csv)
+}
+
+fn print_summary(
+    print: &PrintArgs,
+    headers: &[&str],
+    rows: Vec<Vec<String>>,
+) -> anyhow::Result<()> {
+    let headers = headers.iter().map(|x| x.to_string()).collect();
+    let mut stdout = io::stdout().lock();
+    print
+        .print_options(OutputFormat::Table)
+        .write(TableData::from((headers, rows)), &mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Copy rows as text from source table to target table in one transaction of target, each
+/// fetched batch is inserted with one execution of array parameters.
+/// `source_table` and `target_table` are quoted, `columns` are the names of source table.
+fn copy_table(
+    source: &OdbcDbConnection,
+    source_table: &str,
+    target: &OdbcDbConnection,
+    target_table: &str,
+    columns: &[String],
+) -> anyhow::Result<usize> {
+    let source_columns: Vec<String> = columns.iter().map(|x| source.quote_column(x)).collect();
+    let target_columns: Vec<String> = columns.iter().map(|x| target.quote_ident(x)).collect();
+    let select = format!("SELECT {} FROM {}", source_columns.join(", "), source_table);
+    let insert = insert_sql(target_table, &target_columns);
+    let mut cursor = source.query_cursor(select)?;
+    // Binary columns are fetched and bound as bytes, text buffers would copy the hex digits
+    // converted by driver
+    let mut descs = vec![];
+    for index in 1..=columns.len() as u16 {
+        descs.push(fetch_desc(cursor.col_data_type(index)?, &source.options));
+    }
+    let buffers = ColumnarAnyBuffer::try_from_descs(source.options.max_batch_size, descs)?;
+    let mut row_set_cursor = cursor.bind_buffer(buffers)?;
+    let target_error = |e| OdbcHelperError::from_odbc(&target.options.database, e);
+
+    transaction(target, || {
+        let mut prepared = target.conn.prepare(&insert).map_err(target_error)?;
+        let mut rows = 0;
+        while let Some(batch) = row_set_cursor
+            .fetch_with_truncation_check(true)
+            .map_err(|e| match e {
+                odbc_api::Error::TooLargeValueForBuffer => anyhow!(
+                    "value of {} is longer than max_str_len {} or max_binary_len {}",
+                    source_table,
+                    source.options.max_str_len,
+                    source.options.max_binary_len
+                ),
+                e => OdbcHelperError::from_odbc(&source.options.database, e).into(),
+            })?
+        {
+            let cells = |col_index| -> Vec<Option<&[u8]>> {
+                match batch.column(col_index) {
+                    AnySlice::Text(view) => view.iter().collect(),
+                    AnySlice::Binary(view) => view.iter().collect(),
+                    _ => unreachable!("copy columns are fetched as text or binary"),
+                }
+            };
+            let columns: Vec<_> = (0..batch.num_cols()).map(cells).collect();
+            let descs = columns.iter().enumerate().map(|(col_index, cells)| {
+                let max_len = cells
+                    .iter()
+                    .filter_map(|x| x.map(|x| x.len()))
+                    .max()
+                    .unwrap_or_default()
+                    .max(1);
+                match batch.column(col_index) {
+                    AnySlice::Binary(_) => BufferDesc::Binary { length: max_len },
+                    _ => BufferDesc::Text {
+                        max_str_len: max_len,
+                    },
+                }
+            });
+            let mut inserter = prepared
+                .column_inserter(batch.num_rows(), descs)
+                .map_err(target_error)?;
+            inserter.set_num_rows(batch.num_rows());
+            for (col_index, cells) in columns.iter().enumerate() {
+                let cells = cells.iter().copied().enumerate();
+                match inserter.column_mut(col_index) {
+                    AnySliceMut::Text(mut column) => {
+                        cells.for_each(|(row, x)| column.set_cell(row, x))
+                    }
+                    AnySliceMut::Binary(mut column) => {
+                        cells.for_each(|(row, x)| column.set_cell(row, x))
+                    }
+                    _ => unreachable!("copy parameters are bound as text or binary"),
+                }
+            }
+            inserter.execute().map_err(target_error).with_context(|| {
+                format!(
+                    "copy rows {}..{} of {}",
+                    rows + 1,
+                    rows + batch.num_rows(),
+                    source_table
+                )
+            })?;
+            rows += batch.num_rows();
+        }
+        Ok(rows)
+    })
+}
+
+/// Fetch buffer of copied column, binary is fetched as bytes and others as text
+fn fetch_desc(data_type: DataType, options: &Options) -> BufferDesc {
+    let limit = |len: Option<usize>, max: usize| match len {
+        Some(len) if len > 0 => len.min(max),
+        _ => max,
+    };
+    match data_type {
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+            BufferDesc::Binary {
+                length: limit(Some(data_type.column_size()), options.max_binary_len),
+            }
+        }
+        _ => BufferDesc::Text {
+            max_str_len: limit(data_type.utf8_len(), options.max_str_len),
+        },
+    }
+}
+
+/// Run `f` in transaction, rollback if it fails. Autocommit is restored even if commit or
+/// rollback fails, and the error of `f` is kept when rollback fails too.
+fn transaction<T>(
+    connection: &OdbcDbConnection,
+    f: impl FnOnce() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    connection.begin()?;
+    let result = match f() {
+        Ok(value) => connection.commit().map(|_| value).map_err(Into::into),
+        Err(e) => match connection.rollback() {
+            Ok(_) => Err(e),
+            Err(rollback) => Err(e.context(format!("rollback failed: {}", rollback))),
+        },
+    };
+    let finished = connection.finish();
+    let value = result?;
+    finished?;
+    Ok(value)
+}
+
+/// Keep added tables and columns of diff, migrate never drops or changes existing columns
+fn additive_diff(diff: SchemaDiff) -> SchemaDiff {
+    let tables = diff
+        .tables
+        .into_iter()
+        .filter_map(|table| match table {
+            TableDiff::Added { .. } => Some(table),
+            TableDiff::Dropped { .. } => None,
+            TableDiff::Changed {
+                table_name,
+                columns,
+            } => {
+                let columns: Vec<ColumnDiff> = columns
+                    .into_iter()
+                    .filter(|x| matches!(x, ColumnDiff::Added(_)))
+                    .collect();
+                (!columns.is_empty()).then_some(TableDiff::Changed {
+                    table_name,
+                    columns,
+                })
+            }
+        })
+        .collect();
+    SchemaDiff { tables }
+}
@@ -1,40 +1,227 @@
-use clap::Parser;
-use odbc_api_helper::executor::database::{OdbcDbConnection, Options};
+mod command;
+mod profile;
+mod repl;
+mod sql;
+
+use clap::{Parser, Subcommand};
 use odbc_api_helper::executor::SupportDatabase;
-use odbc_api_helper::odbc_api::Environment;
-use odbc_api_helper::Print;
-use serde::{Deserialize, Serialize};
-use std::fs;
+use odbc_api_helper::export::{ExportCompression, ExportOptions};
+use odbc_api_helper::import::ImportOptions;
+use odbc_api_helper::{BinaryFormat, ColumnWidth, OutputFormat, Overflow, PrintOptions};
+use std::path::PathBuf;
 
-/// Simple program to greet a person
+/// Command line client of dameng, postgres and mysql through odbc
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Name of the person to greet
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run sql and print the result set
+    Query {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Sql with `?` parameter markers
+        sql: String,
+        /// Parameter values bound as varchar in order
+        #[arg(short, long = "param")]
+        params: Vec<String>,
+    },
+    /// Run INSERT, UPDATE, DELETE or DDL sql and print affected rows
+    Exec {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Sql with `?` parameter markers
+        sql: String,
+        /// Parameter values bound as varchar in order
+        #[arg(short, long = "param")]
+        params: Vec<String>,
+    },
+    /// Describe columns of tables
+    Describe {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Schema of tables, default is the database of profile
+        #[arg(short, long)]
+        schema: Option<String>,
+        #[arg(required = true)]
+        tables: Vec<String>,
+    },
+    /// Export table or query result to file, the format is `--output` or the file extension.
+    /// `.parquet`, `.arrow`, `.ipc` and `.feather` files are written with arrow types.
+    Export {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Schema of table, default is the database of profile
+        #[arg(short, long)]
+        schema: Option<String>,
+        /// Table to export
+        #[arg(
+            short,
+            long,
+            required_unless_present = "query",
+            conflicts_with = "query"
+        )]
+        table: Option<String>,
+        /// Query to export
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Compression of parquet or arrow file: none, snappy or zstd
+        #[arg(long, default_value_t = ExportCompression::Zstd)]
+        compression: ExportCompression,
+        /// Max rows of parquet row group
+        #[arg(long, default_value_t = ExportOptions::ROW_GROUP_SIZE)]
+        row_group_size: usize,
+        file: String,
+    },
+    /// Import csv, tsv or parquet file with header into table, values are coerced to the column
+    /// types and rows which could not be coerced are written to the reject file
+    Import {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Schema of table, default is the database of profile
+        #[arg(short, long)]
+        schema: Option<String>,
+        /// Table to import into
+        #[arg(short, long)]
+        table: String,
+        /// Rows of one batch insert
+        #[arg(long, default_value_t = ImportOptions::BATCH_SIZE)]
+        batch_size: usize,
+        /// Csv field read as NULL besides the empty field, E.g: \N
+        #[arg(long)]
+        null_value: Option<String>,
+        /// Csv file of rejected rows with reason, default is `<file>.rejects.csv`
+        #[arg(long)]
+        reject_file: Option<String>,
+        file: String,
+    },
+    /// Copy tables with data to another database, missing tables and columns are created
+    Migrate {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+        /// Schema of source tables, default is the database of profile
+        #[arg(short, long)]
+        schema: Option<String>,
+        /// Tables to copy, default is all tables in schema
+        #[arg(short, long = "table")]
+        tables: Vec<String>,
+        /// Profile of target database in the same profiles file
+        #[arg(long)]
+        target_profile: String,
+        /// Target database kind: dameng, pg or mysql, default is the kind of target profile
+        #[arg(long)]
+        target_database: Option<SupportDatabase>,
+        /// Schema of target tables, default is the database of target profile
+        #[arg(long)]
+        target_schema: Option<String>,
+        /// Only create or alter tables, do not copy data
+        #[arg(long)]
+        schema_only: bool,
+    },
+    /// Start interactive sql shell
+    Repl {
+        #[command(flatten)]
+        connect: ConnectArgs,
+        #[command(flatten)]
+        print: PrintArgs,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct ConnectArgs {
+    /// Name of connection profile
+    #[arg(long, env = "ODBC_BRIDGE_PROFILE")]
+    profile: String,
+    /// Path of profiles file, toml or json, default is ~/.odbc_bridge_profiles.toml
+    #[arg(long, env = "ODBC_BRIDGE_PROFILES")]
+    profiles: Option<PathBuf>,
+    /// Database kind: dameng, pg or mysql, default is the kind of profile
+    #[arg(short, long)]
+    database: Option<SupportDatabase>,
+}
+
+#[derive(clap::Args, Debug)]
+struct PrintArgs {
+    /// Output format: table, csv, tsv, json, ndjson, markdown or html
+    #[arg(short, long)]
+    output: Option<OutputFormat>,
+    /// Max table width, default is the terminal width
     #[arg(short, long)]
-    path: String,
+    width: Option<usize>,
+    /// Fit wide cells into table width: wrap or truncate
+    #[arg(long, default_value_t = Overflow::Wrap)]
+    overflow: Overflow,
+    /// Truncate cells longer than the width
+    #[arg(long)]
+    max_column_width: Option<usize>,
+    /// Max printed rows
+    #[arg(long)]
+    max_rows: Option<usize>,
+    /// Display text of NULL
+    #[arg(long, default_value = "NULL")]
+    null: String,
+    /// Disable colour output
+    #[arg(long)]
+    no_color: bool,
+    /// Column widths of table: first-batch or metadata
+    #[arg(long, default_value_t = ColumnWidth::FirstBatch)]
+    column_width: ColumnWidth,
+    /// Max bytes of a fetched text cell
+    #[arg(long, default_value_t = PrintOptions::MAX_CELL_LEN)]
+    max_cell_len: usize,
+    /// Show column type and nullability in header
+    #[arg(long)]
+    show_types: bool,
+    /// Binary value encoding: hex or base64
+    #[arg(long, default_value_t = BinaryFormat::Hex)]
+    binary: BinaryFormat,
+    /// Display each row as a record of column name and value pairs
+    #[arg(short = 'x', long)]
+    expanded: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct EnvConfig {
-    connection: String,
-    database: String,
-    sql: String,
+impl PrintArgs {
+    /// Print options with `format` if `--output` is not set
+    fn print_options(&self, format: OutputFormat) -> PrintOptions {
+        PrintOptions {
+            format: self.output.unwrap_or(format),
+            width: self.width,
+            max_column_width: self.max_column_width,
+            overflow: self.overflow,
+            max_rows: self.max_rows,
+            null_text: self.null.clone(),
+            color: !self.no_color,
+            column_width: self.column_width,
+            max_cell_len: self.max_cell_len,
+            show_column_types: self.show_types,
+            binary_format: self.binary,
+            expanded: self.expanded,
+        }
+    }
 }
 
 fn main() {
     simple_log::quick!();
 
     let args = Args::parse();
-    let json = fs::read_to_string(args.path).unwrap();
-    let config: EnvConfig = serde_json::from_str(&json).unwrap();
-    println!("config:{:?}", config);
-    let env = Environment::new().unwrap();
-    let conn = env
-        .connect_with_connection_string(&config.connection)
-        .unwrap();
-
-    let connection = OdbcDbConnection::new(conn, Options::new(SupportDatabase::Dameng)).unwrap();
-    let cursor_impl = connection.conn.execute(&config.sql, ()).unwrap().unwrap();
-    cursor_impl.print_all_tables().unwrap()
+    if let Err(e) = command::run(args.command) {
+        eprintln!("ERROR: {:#}", e);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,248 @@
+use anyhow::{bail, Context};
+use odbc_api_helper::executor::connection::ConnectionConfig;
+use odbc_api_helper::executor::database::Options;
+use odbc_api_helper::executor::SupportDatabase;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default profiles file in home directory
+const PROFILES_FILE: &str = ".odbc_bridge_profiles.toml";
+
+/// Named connection profiles, loaded from toml:
+/// ```toml
+/// [dev]
+/// kind = "dameng"
+/// driver = "DM8"
+/// host = "127.0.0.1"
+/// port = 5236
+/// user = "SYSDBA"
+/// database = "SYSDBA"
+/// password_env = "DM_PASSWORD"
+/// login_timeout = 5
+/// max_batch_size = 256
+/// case_sensitive = false
+/// ```
+/// or json with the same structure. Plain text `password` is not accepted, the password is read
+/// from the environment variable `password_env` or the secrets file `password_file`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    /// key is profile name
+    #[serde(flatten)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Database kind: dameng, pg or mysql, default is dameng
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Odbc driver name, E.g: `DM8`, `PostgreSQL Unicode`
+    pub driver: String,
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    pub user: String,
+    /// Database name, also the default schema of dameng and mysql
+    pub database: String,
+    /// Default schema, default is `database`
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// Environment variable holding the password
+    #[serde(default)]
+    pub password_env: Option<String>,
+    /// File whose content is the password, surrounding whitespace is trimmed
+    #[serde(default)]
+    pub password_file: Option<PathBuf>,
+    /// Login timeout in seconds
+    #[serde(default)]
+    pub login_timeout: Option<u64>,
+    /// Client character set, E.g: dameng `PG_UTF8`, postgres `UTF8`
+    #[serde(default)]
+    pub charset: Option<String>,
+    #[serde(default)]
+    pub max_batch_size: Option<usize>,
+    #[serde(default)]
+    pub max_str_len: Option<usize>,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+impl Profiles {
+    pub fn from_toml(s: &str) -> anyhow::Result<Self> {
+        toml::from_str(s).context("parse profiles toml")
+    }
+
+    pub fn from_json(s: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(s).context("parse profiles json")
+    }
+
+    /// Load profiles file, file with `.json` extension is parsed as json, otherwise toml.
+    /// Default file is `~/.odbc_bridge_profiles.toml`.
+    pub fn from_file(path: Option<&Path>) -> anyhow::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => std::env::var_os("HOME")
+                .map(|x| PathBuf::from(x).join(PROFILES_FILE))
+                .context("HOME is not set, profiles file is required")?,
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("read profiles file {}", path.display()))?;
+        match path.extension().and_then(|x| x.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> anyhow::Result<&Profile> {
+        self.profiles.get(name).with_context(|| {
+            let names: Vec<&str> = self.profiles.keys().map(|x| x.as_str()).collect();
+            format!("profile {} not found in [{}]", name, names.join(", "))
+        })
+    }
+}
+
+impl Profile {
+    pub fn kind(&self) -> anyhow::Result<SupportDatabase> {
+        match &self.kind {
+            Some(kind) => Ok(kind.parse()?),
+            None => Ok(SupportDatabase::Dameng),
+        }
+    }
+
+    /// Resolve password from `password_env` or `password_file`, `None` if neither is set
+    pub fn password(&self) -> anyhow::Result<Option<String>> {
+        if let Some(name) = &self.password_env {
+            return match std::env::var(name) {
+                Ok(password) => Ok(Some(password)),
+                Err(e) => bail!("read password from environment variable {}: {}", name, e),
+            };
+        }
+        if let Some(path) = &self.password_file {
+            let password = std::fs::read_to_string(path)
+                .with_context(|| format!("read password file {}", path.display()))?;
+            return Ok(Some(password.trim().to_string()));
+        }
+        Ok(None)
+    }
+
+    pub fn default_schema(&self) -> &str {
+        self.schema.as_deref().unwrap_or(&self.database)
+    }
+
+    /// Connection config with resolved password
+    pub fn connection_config(&self, database: SupportDatabase) -> anyhow::Result<ConnectionConfig> {
+        let mut config = ConnectionConfig::new(database, &self.driver)
+            .with_server(&self.host)
+            .with_user(&self.user)
+            .with_schema(self.default_schema())
+            .with_case_sensitive(self.case_sensitive);
+        if database != SupportDatabase::Dameng {
+            config = config.with_catalog(&self.database);
+        }
+        config.port = self.port;
+        config.password = self.password()?;
+        config.login_timeout = self.login_timeout.map(Duration::from_secs);
+        config.charset = self.charset.clone();
+        Ok(config)
+    }
+
+    pub fn options(&self, config: &ConnectionConfig) -> Options {
+        let mut options = config.options();
+        if let Some(max_batch_size) = self.max_batch_size {
+            options.max_batch_size = max_batch_size;
+        }
+        if let Some(max_str_len) = self.max_str_len {
+            options.max_str_len = max_str_len;
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_from_toml_and_json() {
+        let toml = r#"
+[dev]
+driver = "DM8"
+host = "127.0.0.1"
+port = 5236
+user = "SYSDBA"
+database = "SYSDBA"
+password_env = "ODBC_BRIDGE_TEST_DEV_PASSWORD"
+max_batch_size = 256
+
+[pg]
+kind = "pg"
+driver = "PostgreSQL Unicode"
+host = "localhost"
+user = "postgres"
+database = "test"
+schema = "public"
+case_sensitive = true
+"#;
+        let profiles = Profiles::from_toml(toml).unwrap();
+        assert_eq!(profiles.profiles.len(), 2);
+        let json = serde_json::to_string(&profiles).unwrap();
+        assert_eq!(Profiles::from_json(&json).unwrap(), profiles);
+
+        let dev = profiles.get("dev").unwrap();
+        assert_eq!(dev.kind().unwrap(), SupportDatabase::Dameng);
+        std::env::set_var("ODBC_BRIDGE_TEST_DEV_PASSWORD", "SYSDBA001");
+        let config = dev.connection_config(SupportDatabase::Dameng).unwrap();
+        assert_eq!(
+            config.to_connection_string(),
+            "Driver={DM8};Server=127.0.0.1;TCP_PORT=5236;UID=SYSDBA;PWD=SYSDBA001;SCHEMA=SYSDBA;"
+        );
+        let options = dev.options(&config);
+        assert_eq!(options.max_batch_size, 256);
+        assert_eq!(options.max_str_len, Options::MAX_STR_LEN);
+        assert!(!options.case_sensitive);
+
+        let pg = profiles.get("pg").unwrap();
+        assert_eq!(pg.kind().unwrap(), SupportDatabase::Pg);
+        assert_eq!(pg.default_schema(), "public");
+        let config = pg.connection_config(SupportDatabase::Pg).unwrap();
+        assert!(pg.options(&config).case_sensitive);
+        assert_eq!(
+            config.to_connection_string(),
+            "Driver={PostgreSQL Unicode};Server=localhost;Database=test;UID=postgres;\
+             ConnSettings=SET search_path TO public;"
+        );
+
+        assert!(profiles.get("prod").is_err());
+    }
+
+    #[test]
+    fn test_profile_password() {
+        let plain = r#"
+[dev]
+driver = "DM8"
+host = "127.0.0.1"
+user = "SYSDBA"
+database = "SYSDBA"
+password = "SYSDBA001"
+"#;
+        assert!(Profiles::from_toml(plain).is_err());
+
+        let path = std::env::temp_dir().join("odbc_bridge_test_password");
+        std::fs::write(&path, "SYSDBA001\n").unwrap();
+        let profile = Profile {
+            password_file: Some(path),
+            ..Default::default()
+        };
+        assert_eq!(profile.password().unwrap(), Some("SYSDBA001".to_string()));
+
+        let profile = Profile {
+            password_env: Some("ODBC_BRIDGE_TEST_MISSING_PASSWORD".to_string()),
+            ..Default::default()
+        };
+        assert!(profile.password().is_err());
+        assert_eq!(Profile::default().password().unwrap(), None);
+    }
+}
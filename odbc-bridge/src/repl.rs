@@ -0,0 +1,206 @@
+use crate::sql::split_statements;
+use anyhow::bail;
+use odbc_api_helper::error::OdbcHelperError;
+use odbc_api_helper::executor::database::{ConnectionTrait, OdbcDbConnection};
+use odbc_api_helper::executor::table::TableDescResultInner;
+use odbc_api_helper::{Print, PrintOptions, StreamPrint};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// History file in home directory
+const HISTORY_FILE: &str = ".odbc_bridge_history";
+
+const HELP: &str = r"General
+  \q                 quit
+  \?                 show this help
+
+Informational
+  \dt [SCHEMA]       list tables, default is the configured database
+  \d [SCHEMA.]TABLE  describe table
+
+Formatting
+  \x [on|off]        toggle expanded display
+  \timing [on|off]   toggle timing of statements
+  \o [FILE]          send query results to file, or stdout if no file
+
+Statements are terminated by `;` and could span multiple lines.";
+
+/// Interactive sql shell in the style of `psql`
+pub struct Repl<'a> {
+    connection: OdbcDbConnection<'a>,
+    /// Default schema of `\dt` and `\d`
+    schema: String,
+    options: PrintOptions,
+    timing: bool,
+    output: Box<dyn Write>,
+    /// Query results are sent to file by `\o`
+    output_file: bool,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(connection: OdbcDbConnection<'a>, schema: String, options: PrintOptions) -> Self {
+        Self {
+            connection,
+            schema,
+            options,
+            timing: false,
+            output: Box::new(io::stdout()),
+            output_file: false,
+        }
+    }
+
+    /// Print options of current output, no color is written to file
+    fn print_options(&self) -> PrintOptions {
+        PrintOptions {
+            color: self.options.color && !self.output_file,
+            ..self.options.clone()
+        }
+    }
+
+    pub fn run(&mut self) -> anyhow::Result<()> {
+        let mut editor = DefaultEditor::new()?;
+        let history = history_path();
+        if let Some(path) = &history {
+            // History file does not exist on first run
+            let _ = editor.load_history(path);
+        }
+        println!("Type \"\\?\" for help.");
+
+        let mut buffer = String::new();
+        loop {
+            let prompt = if buffer.is_empty() { "dm=> " } else { "dm-> " };
+            match editor.readline(prompt) {
+                Ok(line) => {
+                    if buffer.is_empty() && line.trim_start().starts_with('\\') {
+                        editor.add_history_entry(line.as_str())?;
+                        match self.meta_command(line.trim()) {
+                            Ok(true) => break,
+                            Ok(false) => {}
+                            Err(e) => eprintln!("ERROR: {:#}", e),
+                        }
+                        continue;
+                    }
+                    if !buffer.is_empty() {
+                        buffer.push('\n');
+                    }
+                    buffer.push_str(&line);
+
+                    let (statements, end) = split_statements(&buffer);
+                    if statements.is_empty() {
+                        continue;
+                    }
+                    editor.add_history_entry(buffer[..end].trim())?;
+                    for sql in statements {
+                        if let Err(e) = self.execute(&sql) {
+                            eprintln!("ERROR: {:#}", e);
+                        }
+                    }
+                    buffer = buffer[end..].trim_start().to_string();
+                }
+                Err(ReadlineError::Interrupted) => buffer.clear(),
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if let Some(path) = &history {
+            editor.save_history(path)?;
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, sql: &str) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let database = &self.connection.options.database;
+        let db_error = |e| OdbcHelperError::from_odbc(database, e);
+
+        let options = self.print_options();
+        let mut stmt = self.connection.conn.preallocate().map_err(db_error)?;
+        let printed = match stmt.execute(sql, ()).map_err(db_error)? {
+            Some(cursor) => Some(cursor.stream_to(&mut self.output, options)?),
+            None => None,
+        };
+        match printed {
+            Some(rows) => writeln!(self.output, "({} rows)", rows)?,
+            None => {
+                let rows = stmt.row_count().map_err(db_error)?.unwrap_or_default();
+                writeln!(self.output, "{} rows affected", rows)?;
+            }
+        }
+        self.output.flush()?;
+
+        if self.timing {
+            println!("Time: {:.3} ms", start.elapsed().as_secs_f64() * 1000.0);
+        }
+        Ok(())
+    }
+
+    /// Return true if the shell should quit
+    fn meta_command(&mut self, line: &str) -> anyhow::Result<bool> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().unwrap_or_default();
+        let arg = parts.next();
+        match command {
+            "\\q" => return Ok(true),
+            "\\?" => println!("{}", HELP),
+            "\\dt" | "\\d" if command == "\\dt" || arg.is_none() => {
+                let schema = arg.unwrap_or(&self.schema);
+                let tables = self.connection.list_tables(schema)?;
+                let options = self.print_options();
+                tables.write_to(&mut self.output, options)?;
+            }
+            "\\d" => {
+                let name = arg.unwrap_or_default();
+                let (schema, table) = name.split_once('.').unwrap_or((&self.schema, name));
+                let desc = self
+                    .connection
+                    .show_table(schema, vec![table.to_string()])?;
+                let desc = TableDescResultInner::from(desc);
+                if desc.columns_desc.is_empty() {
+                    bail!("Did not find any relation named \"{}\"", name);
+                }
+                let options = self.print_options();
+                desc.write_to(&mut self.output, options)?;
+            }
+            "\\x" => {
+                self.options.expanded = toggle(self.options.expanded, arg)?;
+                println!(
+                    "Expanded display is {}.",
+                    if self.options.expanded { "on" } else { "off" }
+                );
+            }
+            "\\timing" => {
+                self.timing = toggle(self.timing, arg)?;
+                println!("Timing is {}.", if self.timing { "on" } else { "off" });
+            }
+            "\\o" => {
+                self.output.flush()?;
+                self.output = match arg {
+                    Some(path) => Box::new(File::create(path)?),
+                    None => Box::new(io::stdout()),
+                };
+                self.output_file = arg.is_some();
+            }
+            _ => bail!("invalid command {}, try \\? for help", command),
+        }
+        self.output.flush()?;
+        Ok(false)
+    }
+}
+
+fn toggle(value: bool, arg: Option<&str>) -> anyhow::Result<bool> {
+    match arg {
+        None => Ok(!value),
+        Some("on") => Ok(true),
+        Some("off") => Ok(false),
+        Some(x) => bail!("unrecognized value \"{}\", expect on or off", x),
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|x| PathBuf::from(x).join(HISTORY_FILE))
+}
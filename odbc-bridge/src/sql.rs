@@ -0,0 +1,101 @@
+use either::Either;
+use odbc_api_helper::executor::statement::SqlValue;
+use odbc_api_helper::odbc_api::parameter::InputParameter;
+use odbc_api_helper::odbc_api::IntoParameter;
+
+/// Parameter value bound as varchar, `None` is NULL
+#[derive(Debug)]
+pub struct TextValue(pub Option<String>);
+
+impl SqlValue for TextValue {
+    fn to_value(self) -> Either<Box<dyn InputParameter>, ()> {
+        Either::Left(Box::new(self.0.into_parameter()))
+    }
+}
+
+/// `INSERT` sql of `table` with a `?` parameter marker for each column
+pub fn insert_sql(table: &str, columns: &[String]) -> String {
+    format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        table,
+        columns.join(", "),
+        vec!["?"; columns.len()].join(", ")
+    )
+}
+
+/// Split complete statements terminated by `;`, which are returned without the terminator.
+/// `;` in quoted string, quoted identifier and comment is ignored.
+/// Return the statements and the byte offset of the incomplete rest.
+pub fn split_statements(text: &str) -> (Vec<String>, usize) {
+    let mut statements = vec![];
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut line_comment = false;
+    let mut block_comment = false;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|(_, x)| *x);
+        if line_comment {
+            line_comment = c != '\n';
+        } else if block_comment {
+            if c == '*' && next == Some('/') {
+                chars.next();
+                block_comment = false;
+            }
+        } else if let Some(q) = quote {
+            // Escaped quote is two quotes, it is the same as closing and opening again.
+            if c == q {
+                quote = None;
+            }
+        } else {
+            match c {
+                '\'' | '"' => quote = Some(c),
+                '-' if next == Some('-') => line_comment = true,
+                '/' if next == Some('*') => {
+                    chars.next();
+                    block_comment = true;
+                }
+                ';' => {
+                    let sql = text[start..i].trim();
+                    if !sql.is_empty() {
+                        statements.push(sql.to_string());
+                    }
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    (statements, start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(split_statements("SELECT 1"), (vec![], 0));
+        assert_eq!(
+            split_statements("SELECT 1;\nSELECT\n 2; SELECT"),
+            (vec!["SELECT 1".to_string(), "SELECT\n 2".to_string()], 20)
+        );
+        assert_eq!(
+            split_statements("SELECT 'a;''b', \"c;\" -- d;\n/* e; */ FROM T;"),
+            (
+                vec!["SELECT 'a;''b', \"c;\" -- d;\n/* e; */ FROM T".to_string()],
+                43
+            )
+        );
+        assert_eq!(split_statements("SELECT 'a;\n"), (vec![], 0));
+        assert_eq!(split_statements(";;"), (vec![], 2));
+    }
+
+    #[test]
+    fn test_insert_sql() {
+        assert_eq!(
+            insert_sql("S.T", &["ID".to_string(), "NAME".to_string()]),
+            "INSERT INTO S.T (ID, NAME) VALUES (?, ?)"
+        );
+    }
+}
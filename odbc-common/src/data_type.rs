@@ -0,0 +1,129 @@
+use crate::print_table::BinaryFormat;
+use nu_table::Alignment;
+use odbc_api::{ColumnDescription, DataType, Nullability, ResultSetMetadata};
+
+/// Sql type name of odbc data type, E.g: `VARCHAR(255)`, `NUMERIC(10,2)`
+pub fn type_name(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Unknown => "UNKNOWN".to_string(),
+        DataType::Char { length } => format!("CHAR({})", length),
+        DataType::WChar { length } => format!("NCHAR({})", length),
+        DataType::Numeric { precision, scale } => format!("NUMERIC({},{})", precision, scale),
+        DataType::Decimal { precision, scale } => format!("DECIMAL({},{})", precision, scale),
+        DataType::Integer => "INTEGER".to_string(),
+        DataType::SmallInt => "SMALLINT".to_string(),
+        DataType::Float { precision } => format!("FLOAT({})", precision),
+        DataType::Real => "REAL".to_string(),
+        DataType::Double => "DOUBLE".to_string(),
+        DataType::Varchar { length } => format!("VARCHAR({})", length),
+        DataType::WVarchar { length } => format!("NVARCHAR({})", length),
+        DataType::LongVarchar { .. } => "TEXT".to_string(),
+        DataType::LongVarbinary { .. } => "BLOB".to_string(),
+        DataType::Date => "DATE".to_string(),
+        DataType::Time { precision } => format!("TIME({})", precision),
+        DataType::Timestamp { precision } => format!("TIMESTAMP({})", precision),
+        DataType::BigInt => "BIGINT".to_string(),
+        DataType::TinyInt => "TINYINT".to_string(),
+        DataType::Bit => "BIT".to_string(),
+        DataType::Varbinary { length } => format!("VARBINARY({})", length),
+        DataType::Binary { length } => format!("BINARY({})", length),
+        DataType::Other { data_type, .. } => format!("OTHER({})", data_type.0),
+    }
+}
+
+/// Sql type name with nullability, E.g: `INTEGER NOT NULL`. `None` is unknown nullability.
+pub fn type_description(data_type: &DataType, nullable: Option<bool>) -> String {
+    match nullable {
+        Some(false) => format!("{} NOT NULL", type_name(data_type)),
+        _ => type_name(data_type),
+    }
+}
+
+pub fn is_numeric(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Numeric { .. }
+            | DataType::Decimal { .. }
+            | DataType::Integer
+            | DataType::SmallInt
+            | DataType::Float { .. }
+            | DataType::Real
+            | DataType::Double
+            | DataType::BigInt
+            | DataType::TinyInt
+    )
+}
+
+/// Numbers are right aligned, others are left aligned
+pub fn alignment(data_type: &DataType) -> Alignment {
+    if is_numeric(data_type) {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    }
+}
+
+/// Sql type descriptions, alignments and data types of result set columns
+pub fn describe_columns<C: ResultSetMetadata>(
+    cursor: &mut C,
+) -> anyhow::Result<(Vec<String>, Vec<Alignment>, Vec<DataType>)> {
+    let num_cols = cursor.num_result_cols()? as u16;
+    let mut column_types = Vec::with_capacity(num_cols as usize);
+    let mut alignments = Vec::with_capacity(num_cols as usize);
+    let mut data_types = Vec::with_capacity(num_cols as usize);
+    for column_number in 1..=num_cols {
+        let mut desc = ColumnDescription::default();
+        cursor.describe_col(column_number, &mut desc)?;
+        let nullable = match desc.nullability {
+            Nullability::Nullable => Some(true),
+            Nullability::NoNulls => Some(false),
+            Nullability::Unknown => None,
+        };
+        column_types.push(type_description(&desc.data_type, nullable));
+        alignments.push(alignment(&desc.data_type));
+        data_types.push(desc.data_type);
+    }
+    Ok((column_types, alignments, data_types))
+}
+
+/// Display text of value fetched as text, the same as typed values are displayed: `BIT` is
+/// `true` or `false`, binary converted to hex digits by driver is encoded as `binary_format`.
+/// ```
+/// use odbc_api::DataType;
+/// use odbc_common::data_type::display_text;
+/// use odbc_common::print_table::BinaryFormat;
+///
+/// let base64 = BinaryFormat::Base64;
+/// assert_eq!(display_text(&DataType::Bit, "1".to_string(), base64), "true");
+/// let binary = DataType::Varbinary { length: 2 };
+/// assert_eq!(display_text(&binary, "0AFF".to_string(), base64), "Cv8=");
+/// assert_eq!(display_text(&binary, "0AFF".to_string(), BinaryFormat::Hex), "\\x0aff");
+/// ```
+pub fn display_text(data_type: &DataType, text: String, binary_format: BinaryFormat) -> String {
+    match data_type {
+        DataType::Bit => match text.as_str() {
+            "1" => "true".to_string(),
+            "0" => "false".to_string(),
+            _ => text,
+        },
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+            match decode_hex(&text) {
+                Some(value) => binary_format.encode(&value),
+                None => text,
+            }
+        }
+        _ => text,
+    }
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    text.as_bytes()
+        .chunks(2)
+        .map(|x| {
+            std::str::from_utf8(x)
+                .ok()
+                .filter(|x| x.len() == 2)
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+        })
+        .collect()
+}
@@ -1,8 +1,13 @@
 #[macro_use]
 extern crate log;
 
+pub mod data_type;
+pub mod output_format;
 pub mod print_table;
+pub mod stream_print;
 pub use nu_protocol::*;
 pub use nu_table::*;
 
-pub use print_table::Print;
+pub use output_format::{OutputFormat, TableData};
+pub use print_table::{BinaryFormat, Overflow, Print, PrintOptions};
+pub use stream_print::StreamPrint;
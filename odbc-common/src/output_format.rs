@@ -0,0 +1,379 @@
+use crate::print_table::draw_table;
+use anyhow::bail;
+use nu_table::Alignment;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+
+/// Headers and rows of printable result, `None` cell is `NULL`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TableData {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+    /// Sql type of columns, E.g: `INTEGER NOT NULL`, empty if unknown.
+    pub column_types: Vec<String>,
+    /// Alignment of column cells, empty is left aligned.
+    pub alignments: Vec<Alignment>,
+}
+
+impl TableData {
+    pub fn new(headers: Vec<String>, rows: Vec<Vec<Option<String>>>) -> Self {
+        Self {
+            headers,
+            rows,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_column_types(mut self, column_types: Vec<String>) -> Self {
+        self.column_types = column_types;
+        self
+    }
+
+    pub fn with_alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.alignments = alignments;
+        self
+    }
+
+    pub fn alignment(&self, column: usize) -> Alignment {
+        self.alignments
+            .get(column)
+            .copied()
+            .unwrap_or(Alignment::Left)
+    }
+}
+
+impl From<(Vec<String>, Vec<Vec<String>>)> for TableData {
+    fn from(t: (Vec<String>, Vec<Vec<String>>)) -> Self {
+        Self::new(
+            t.0,
+            t.1.into_iter()
+                .map(|x| x.into_iter().map(Some).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Output format of [`crate::Print::write_format`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Box drawing table rendered by nu-table
+    #[default]
+    Table,
+    /// RFC 4180 csv, fields contain delimiter, quote or line break are quoted
+    Csv,
+    /// Tab separated values with the same quoting rule as csv
+    Tsv,
+    /// Json array of objects keyed by header
+    Json,
+    /// Newline delimited json, one object per line
+    Ndjson,
+    /// Github flavored markdown table
+    Markdown,
+    /// Html `<table>`
+    Html,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 7] = [
+        OutputFormat::Table,
+        OutputFormat::Csv,
+        OutputFormat::Tsv,
+        OutputFormat::Json,
+        OutputFormat::Ndjson,
+        OutputFormat::Markdown,
+        OutputFormat::Html,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Html => "html",
+        }
+    }
+
+    /// Write table data in the format. [`OutputFormat::Table`] is drawn without width limit.
+    pub fn write<W: Write>(&self, data: &TableData, writer: &mut W) -> anyhow::Result<()> {
+        if let OutputFormat::Table = self {
+            writeln!(writer, "{}", draw_table(&data.to_table())?)?;
+            return Ok(());
+        }
+        let mut format_writer =
+            FormatWriter::new(*self, data.headers.clone(), &data.alignments, writer)?;
+        format_writer.write_rows(&data.rows)?;
+        format_writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Write rows as soon as they are received, used to stream large result.
+/// [`OutputFormat::Table`] is not supported, see [`crate::stream_print::TableStreamer`].
+pub struct FormatWriter<W: Write> {
+    headers: Vec<String>,
+    rows: usize,
+    inner: FormatInner<W>,
+}
+
+enum FormatInner<W: Write> {
+    /// csv and tsv, `NULL` is written as empty field
+    Delimited(Box<csv::Writer<W>>),
+    Json(W),
+    Ndjson(W),
+    Markdown(W),
+    Html(W),
+}
+
+impl<W: Write> FormatWriter<W> {
+    /// Create writer and write the header, `alignments` is only used by markdown.
+    pub fn new(
+        format: OutputFormat,
+        headers: Vec<String>,
+        alignments: &[Alignment],
+        writer: W,
+    ) -> anyhow::Result<Self> {
+        let inner = match format {
+            OutputFormat::Table => bail!("table format could not be written row by row"),
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let mut csv_writer = csv::WriterBuilder::new()
+                    .delimiter(if format == OutputFormat::Csv {
+                        b','
+                    } else {
+                        b'\t'
+                    })
+                    .from_writer(writer);
+                csv_writer.write_record(&headers)?;
+                FormatInner::Delimited(Box::new(csv_writer))
+            }
+            OutputFormat::Json => {
+                let mut writer = writer;
+                writer.write_all(b"[")?;
+                FormatInner::Json(writer)
+            }
+            OutputFormat::Ndjson => FormatInner::Ndjson(writer),
+            OutputFormat::Markdown => {
+                let mut writer = writer;
+                let cells: Vec<String> = headers.iter().map(|x| escape_markdown(x)).collect();
+                writeln!(writer, "| {} |", cells.join(" | "))?;
+                let separator: String = (0..headers.len())
+                    .map(|i| match alignments.get(i) {
+                        Some(Alignment::Right) => " ---: |",
+                        Some(Alignment::Center) => " :---: |",
+                        _ => " --- |",
+                    })
+                    .collect();
+                writeln!(writer, "|{}", separator)?;
+                FormatInner::Markdown(writer)
+            }
+            OutputFormat::Html => {
+                let mut writer = writer;
+                writeln!(writer, "<table>")?;
+                writeln!(writer, "  <thead>")?;
+                writeln!(writer, "    <tr>")?;
+                for header in headers.iter() {
+                    writeln!(writer, "      <th>{}</th>", escape_html(header))?;
+                }
+                writeln!(writer, "    </tr>")?;
+                writeln!(writer, "  </thead>")?;
+                writeln!(writer, "  <tbody>")?;
+                FormatInner::Html(writer)
+            }
+        };
+        Ok(Self {
+            headers,
+            rows: 0,
+            inner,
+        })
+    }
+
+    pub fn write_rows(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<()> {
+        for row in rows.iter() {
+            match &mut self.inner {
+                FormatInner::Delimited(csv_writer) => {
+                    csv_writer
+                        .write_record(row.iter().map(|x| x.as_deref().unwrap_or_default()))?;
+                }
+                FormatInner::Json(writer) => {
+                    if self.rows > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    writer.write_all(b"\n  ")?;
+                    write_json_object(&self.headers, row, writer)?;
+                }
+                FormatInner::Ndjson(writer) => {
+                    write_json_object(&self.headers, row, writer)?;
+                    writer.write_all(b"\n")?;
+                }
+                FormatInner::Markdown(writer) => {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|x| x.as_deref().map(escape_markdown).unwrap_or_default())
+                        .collect();
+                    writeln!(writer, "| {} |", cells.join(" | "))?;
+                }
+                FormatInner::Html(writer) => {
+                    writeln!(writer, "    <tr>")?;
+                    for cell in row.iter() {
+                        writeln!(
+                            writer,
+                            "      <td>{}</td>",
+                            cell.as_deref().map(escape_html).unwrap_or_default()
+                        )?;
+                    }
+                    writeln!(writer, "    </tr>")?;
+                }
+            }
+            self.rows += 1;
+        }
+        Ok(())
+    }
+
+    /// Write the footer and flush, return the number of written rows
+    pub fn finish(self) -> anyhow::Result<usize> {
+        match self.inner {
+            FormatInner::Delimited(mut csv_writer) => csv_writer.flush()?,
+            FormatInner::Json(mut writer) => {
+                if self.rows > 0 {
+                    writer.write_all(b"\n")?;
+                }
+                writer.write_all(b"]\n")?;
+                writer.flush()?;
+            }
+            FormatInner::Ndjson(mut writer) | FormatInner::Markdown(mut writer) => {
+                writer.flush()?
+            }
+            FormatInner::Html(mut writer) => {
+                writeln!(writer, "  </tbody>")?;
+                writeln!(writer, "</table>")?;
+                writer.flush()?;
+            }
+        }
+        Ok(self.rows)
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match s.to_lowercase().as_str() {
+            "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "tsv" => OutputFormat::Tsv,
+            "json" => OutputFormat::Json,
+            "ndjson" | "jsonl" => OutputFormat::Ndjson,
+            "markdown" | "md" => OutputFormat::Markdown,
+            "html" => OutputFormat::Html,
+            _ => bail!(
+                "unknown output format:{}, expect one of {}",
+                s,
+                OutputFormat::ALL.map(|x| x.name()).join(", ")
+            ),
+        };
+        Ok(format)
+    }
+}
+
+/// Keys keep the header order, `NULL` is written as json `null`
+fn write_json_object<W: Write>(
+    headers: &[String],
+    row: &[Option<String>],
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    writer.write_all(b"{")?;
+    for (i, (header, cell)) in headers.iter().zip(row.iter()).enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut *writer, header)?;
+        writer.write_all(b":")?;
+        serde_json::to_writer(&mut *writer, cell)?;
+    }
+    writer.write_all(b"}")?;
+    Ok(())
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(format: OutputFormat, data: &TableData) -> String {
+        let mut buf = vec![];
+        format.write(data, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_output_format() {
+        let data = TableData::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Some("1".to_string()), Some("a,\"b\"|<c>".to_string())],
+                vec![Some("2".to_string()), None],
+            ],
+        );
+        assert_eq!(
+            write(OutputFormat::Csv, &data),
+            "id,name\n1,\"a,\"\"b\"\"|<c>\"\n2,\n"
+        );
+        assert_eq!(
+            write(OutputFormat::Tsv, &data),
+            "id\tname\n1\t\"a,\"\"b\"\"|<c>\"\n2\t\n"
+        );
+        assert_eq!(
+            write(OutputFormat::Ndjson, &data),
+            "{\"id\":\"1\",\"name\":\"a,\\\"b\\\"|<c>\"}\n{\"id\":\"2\",\"name\":null}\n"
+        );
+        assert_eq!(
+            write(OutputFormat::Json, &data),
+            "[\n  {\"id\":\"1\",\"name\":\"a,\\\"b\\\"|<c>\"},\n  {\"id\":\"2\",\"name\":null}\n]\n"
+        );
+        assert_eq!(write(OutputFormat::Json, &TableData::default()), "[]\n");
+        assert_eq!(
+            write(OutputFormat::Markdown, &data),
+            "| id | name |\n| --- | --- |\n| 1 | a,\"b\"\\|<c> |\n| 2 |  |\n"
+        );
+        let html = write(OutputFormat::Html, &data);
+        assert!(html.contains("<th>name</th>"));
+        assert!(html.contains("<td>a,&quot;b&quot;|&lt;c&gt;</td>"));
+        assert!(html.contains("<td></td>"));
+
+        assert_eq!(
+            "MD".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Markdown
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -1,10 +1,19 @@
+use crate::data_type::{describe_columns, display_text};
+use crate::output_format::{OutputFormat, TableData};
+use crate::stream_print::{ColumnWidth, ExpandedWriter};
 use anyhow::anyhow;
-use nu_protocol::Config;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use nu_protocol::{Config, TrimStrategy};
 use nu_table::Table;
-use nu_table::{Alignments, StyledString, TableTheme, TextStyle};
+use nu_table::{Alignment, Alignments, StyledString, TableTheme, TextStyle};
 use odbc_api::buffers::TextRowSet;
 use odbc_api::Cursor;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub trait Print: Sized {
     fn print_all_tables(self) -> anyhow::Result<()> {
@@ -13,22 +22,351 @@ pub trait Print: Sized {
         Ok(())
     }
 
-    fn convert_table(self) -> anyhow::Result<Table>;
+    /// Headers and rows of the result, all output formats are rendered from it.
+    fn table_data(self) -> anyhow::Result<TableData>;
+
+    /// Table data rendered with print options, E.g: [`PrintOptions::binary_format`].
+    /// Default is [`Print::table_data`].
+    fn table_data_with(self, _options: &PrintOptions) -> anyhow::Result<TableData> {
+        self.table_data()
+    }
+
+    fn convert_table(self) -> anyhow::Result<Table> {
+        Ok(self.table_data()?.to_table())
+    }
 
     fn table_string(self) -> anyhow::Result<String> {
-        let table = self.convert_table()?;
-        let cfg = Config::default();
-        let styles = HashMap::default();
-        let alignments = Alignments::default();
+        draw_table(&self.convert_table()?)
+    }
+
+    /// Write the result to `writer` in the output format. E.g:
+    /// ```
+    /// use odbc_common::output_format::{OutputFormat, TableData};
+    /// use odbc_common::Print;
+    ///
+    /// let data = TableData::from((
+    ///     vec!["id".to_string(), "name".to_string()],
+    ///     vec![vec!["1".to_string(), "hallo, world".to_string()]],
+    /// ));
+    /// let mut csv = vec![];
+    /// data.write_format(OutputFormat::Csv, &mut csv).unwrap();
+    /// assert_eq!(String::from_utf8(csv).unwrap(), "id,name\n1,\"hallo, world\"\n");
+    /// ```
+    fn write_format<W: Write>(self, format: OutputFormat, writer: &mut W) -> anyhow::Result<()> {
+        match format {
+            OutputFormat::Table => writeln!(writer, "{}", self.table_string()?)?,
+            _ => format.write(&self.table_data()?, writer)?,
+        }
+        Ok(())
+    }
+
+    /// Write the result to `writer` with the print options, E.g: print to stdout
+    /// ```no_run
+    /// use odbc_common::print_table::{Overflow, PrintOptions};
+    /// use odbc_common::{Print, TableData};
+    ///
+    /// let data = TableData::from((vec!["id".to_string()], vec![vec!["1".to_string()]]));
+    /// let options = PrintOptions {
+    ///     max_rows: Some(100),
+    ///     overflow: Overflow::Truncate,
+    ///     ..Default::default()
+    /// };
+    /// data.write_to(&mut std::io::stdout().lock(), options).unwrap();
+    /// ```
+    fn write_to<W: Write>(self, writer: &mut W, options: PrintOptions) -> anyhow::Result<()> {
+        options.write(self.table_data_with(&options)?, writer)
+    }
+}
+
+/// How to fit table cells into terminal width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Wrap cell content to multiple lines, keep word boundaries if possible
+    #[default]
+    Wrap,
+    /// Cut cell content and append `…`
+    Truncate,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overflow::Wrap => write!(f, "wrap"),
+            Overflow::Truncate => write!(f, "truncate"),
+        }
+    }
+}
+
+impl FromStr for Overflow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wrap" => Ok(Overflow::Wrap),
+            "truncate" => Ok(Overflow::Truncate),
+            _ => Err(anyhow!("unknown overflow:{}, expect wrap or truncate", s)),
+        }
+    }
+}
+
+/// Text encoding of binary value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryFormat {
+    /// Postgres bytea hex format, E.g: `\x0aff`
+    #[default]
+    Hex,
+    Base64,
+}
+
+impl Display for BinaryFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryFormat::Hex => write!(f, "hex"),
+            BinaryFormat::Base64 => write!(f, "base64"),
+        }
+    }
+}
+
+impl BinaryFormat {
+    /// Encode binary value as text.
+    /// ```
+    /// use odbc_common::print_table::BinaryFormat;
+    ///
+    /// assert_eq!(BinaryFormat::Hex.encode(&[0x0a, 0xff]), "\\x0aff");
+    /// assert_eq!(BinaryFormat::Base64.encode(&[0x0a, 0xff]), "Cv8=");
+    /// ```
+    pub fn encode(&self, value: &[u8]) -> String {
+        match self {
+            BinaryFormat::Hex => {
+                let hex: String = value.iter().map(|x| format!("{:02x}", x)).collect();
+                format!("\\x{}", hex)
+            }
+            BinaryFormat::Base64 => STANDARD.encode(value),
+        }
+    }
+}
+
+impl FromStr for BinaryFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hex" => Ok(BinaryFormat::Hex),
+            "base64" => Ok(BinaryFormat::Base64),
+            _ => Err(anyhow!("unknown binary format:{}, expect hex or base64", s)),
+        }
+    }
+}
+
+/// Options of [`Print::write_to`]
+#[derive(Debug, Clone)]
+pub struct PrintOptions {
+    pub format: OutputFormat,
+    /// Max table width, `None` is the terminal width of stdout, unlimited if stdout is not a terminal.
+    pub width: Option<usize>,
+    /// Cells wider than the width are cut and end with `…`, `None` is unlimited.
+    /// Only human readable formats: table, markdown and html are truncated.
+    pub max_column_width: Option<usize>,
+    pub overflow: Overflow,
+    /// Max printed rows, `None` is unlimited.
+    pub max_rows: Option<usize>,
+    /// Text of `NULL` cell in table, markdown and html.
+    /// csv/tsv write empty field and json write `null` to keep them machine readable.
+    pub null_text: String,
+    /// Colourize table header, colour is only output when stdout is a terminal.
+    pub color: bool,
+    /// Where the column widths of streaming table come from, see [`crate::stream_print::StreamPrint`].
+    pub column_width: ColumnWidth,
+    /// Max bytes of a text cell fetched from cursor, longer text is cut by the driver.
+    pub max_cell_len: usize,
+    /// Show sql type and nullability under column name in table, markdown and html.
+    pub show_column_types: bool,
+    pub binary_format: BinaryFormat,
+    /// Print each column of a record in its own line in table format, as `psql` `\x`
+    pub expanded: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            format: OutputFormat::Table,
+            width: None,
+            max_column_width: None,
+            overflow: Overflow::Wrap,
+            max_rows: None,
+            null_text: "NULL".to_string(),
+            color: true,
+            column_width: ColumnWidth::FirstBatch,
+            max_cell_len: Self::MAX_CELL_LEN,
+            show_column_types: false,
+            binary_format: BinaryFormat::Hex,
+            expanded: false,
+        }
+    }
+}
+
+impl PrintOptions {
+    pub const ELLIPSIS: char = '…';
+    // Default Max text cell length 4K
+    pub const MAX_CELL_LEN: usize = 4096;
+
+    pub fn terminal_width(&self) -> usize {
+        self.width.unwrap_or_else(|| {
+            terminal_size::terminal_size()
+                .map(|(w, _)| w.0 as usize)
+                .unwrap_or(usize::MAX)
+        })
+    }
+
+    fn human_readable(&self) -> bool {
+        matches!(
+            self.format,
+            OutputFormat::Table | OutputFormat::Markdown | OutputFormat::Html
+        )
+    }
 
-        let p = table
-            .draw_table(&cfg, &styles, alignments, usize::MAX)
-            .ok_or_else(|| anyhow!("convert table to string error"))?;
-        Ok(p)
+    /// Append column types to headers of human readable formats if
+    /// [`PrintOptions::show_column_types`] is set.
+    pub fn prepare_headers(&self, headers: &mut [String], column_types: &[String]) {
+        if self.show_column_types && self.human_readable() {
+            for (header, column_type) in headers.iter_mut().zip(column_types.iter()) {
+                header.push('\n');
+                header.push_str(column_type);
+            }
+        }
+    }
+
+    /// Fill `NULL` text and truncate cells of human readable formats: table, markdown and html.
+    pub fn prepare_rows(&self, rows: &mut [Vec<Option<String>>]) {
+        if self.human_readable() {
+            for cell in rows.iter_mut().flatten() {
+                let text = cell.get_or_insert_with(|| self.null_text.clone());
+                if let Some(width) = self.max_column_width {
+                    *text = truncate_cell(text, width);
+                }
+            }
+        }
     }
+
+    pub fn write<W: Write>(&self, mut data: TableData, writer: &mut W) -> anyhow::Result<()> {
+        let total_rows = data.rows.len();
+        if let Some(max_rows) = self.max_rows {
+            data.rows.truncate(max_rows);
+        }
+        self.prepare_headers(&mut data.headers, &data.column_types);
+        self.prepare_rows(&mut data.rows);
+
+        match self.format {
+            OutputFormat::Table if self.expanded => {
+                let mut expanded = ExpandedWriter::new(&mut *writer, data.headers.clone());
+                expanded.write_rows(&data.rows)?;
+                expanded.finish()?;
+                if data.rows.len() < total_rows {
+                    writeln!(writer, "({} of {} rows)", data.rows.len(), total_rows)?;
+                }
+            }
+            OutputFormat::Table => {
+                let cfg = Config {
+                    use_ansi_coloring: self.color,
+                    trim_strategy: match self.overflow {
+                        Overflow::Wrap => TrimStrategy::Wrap {
+                            try_to_keep_words: true,
+                        },
+                        Overflow::Truncate => TrimStrategy::Truncate {
+                            suffix: Some(Self::ELLIPSIS.to_string()),
+                        },
+                    },
+                    ..Default::default()
+                };
+                let table = data.to_table();
+                // Table is drawn without width limit if even one column could not fit the width.
+                let p = match draw_table_with(&table, &cfg, self.terminal_width()) {
+                    Ok(p) => p,
+                    Err(_) => draw_table_with(&table, &cfg, usize::MAX)?,
+                };
+                writeln!(writer, "{}", p)?;
+                if data.rows.len() < total_rows {
+                    writeln!(writer, "({} of {} rows)", data.rows.len(), total_rows)?;
+                }
+            }
+            format => format.write(&data, writer)?,
+        }
+        Ok(())
+    }
+}
+
+/// Cut the text to `width` display width, end with `…` if truncated.
+/// Wide chars(E.g: CJK) take two columns.
+pub fn truncate_cell(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if truncated_width + w + 1 > width {
+            break;
+        }
+        truncated_width += w;
+        truncated.push(c);
+    }
+    if width > 0 {
+        truncated.push(PrintOptions::ELLIPSIS);
+    }
+    truncated
 }
 
-const BATCH_SIZE: usize = 128;
+impl Print for TableData {
+    fn table_data(self) -> anyhow::Result<TableData> {
+        Ok(self)
+    }
+}
+
+impl TableData {
+    pub fn to_table(&self) -> Table {
+        let headers: Vec<StyledString> = self
+            .headers
+            .iter()
+            .map(|x| StyledString::new(x.to_string(), TextStyle::default_header()))
+            .collect();
+
+        let rows = self
+            .rows
+            .iter()
+            .map(|x| {
+                x.iter()
+                    .enumerate()
+                    .map(|(i, y)| {
+                        let style = match self.alignment(i) {
+                            Alignment::Right => TextStyle::basic_right(),
+                            Alignment::Center => TextStyle::basic_center(),
+                            Alignment::Left => TextStyle::basic_left(),
+                        };
+                        StyledString::new(y.clone().unwrap_or_default(), style)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Table::new(headers, rows, TableTheme::rounded())
+    }
+}
+
+pub(crate) fn draw_table(table: &Table) -> anyhow::Result<String> {
+    draw_table_with(table, &Config::default(), usize::MAX)
+}
+
+fn draw_table_with(table: &Table, cfg: &Config, width: usize) -> anyhow::Result<String> {
+    let styles = HashMap::default();
+    let alignments = Alignments::default();
+
+    let p = table
+        .draw_table(cfg, &styles, alignments, width)
+        .ok_or_else(|| anyhow!("convert table to string error"))?;
+    Ok(p)
+}
+
+pub(crate) const BATCH_SIZE: usize = 128;
 
 /// Print Cursor output to table.E.g:
 /// ```bash
@@ -44,17 +382,19 @@ impl<T> Print for T
 where
     T: Cursor,
 {
-    fn convert_table(mut self) -> anyhow::Result<Table> {
-        let headers: Vec<StyledString> = self
-            .column_names()?
-            .collect::<Result<Vec<String>, _>>()?
-            .into_iter()
-            .map(|x| StyledString::new(x, TextStyle::default_header()))
-            .collect();
+    fn table_data(self) -> anyhow::Result<TableData> {
+        self.table_data_with(&PrintOptions::default())
+    }
+
+    /// BIT and binary values are displayed as typed values, see [`display_text`]
+    fn table_data_with(mut self, options: &PrintOptions) -> anyhow::Result<TableData> {
+        let headers = self.column_names()?.collect::<Result<Vec<String>, _>>()?;
+        let (column_types, alignments, data_types) = describe_columns(&mut self)?;
 
         // Use schema in cursor to initialize a text buffer large enough to hold the largest
         // possible strings for each column up to an upper limit of 4KiB.
-        let mut buffers = TextRowSet::for_cursor(BATCH_SIZE, &mut self, Some(4096))?;
+        let mut buffers =
+            TextRowSet::for_cursor(BATCH_SIZE, &mut self, Some(PrintOptions::MAX_CELL_LEN))?;
         // Bind the buffer to the cursor. It is now being filled with every call to fetch.
         let mut row_set_cursor = self.bind_buffer(&mut buffers)?;
         let mut rows = vec![];
@@ -64,16 +404,88 @@ where
             for row_index in 0..batch.num_rows() {
                 // Within a row iterate over every column
                 let row_data = (0..batch.num_cols())
-                    .map(|col_index| batch.at(col_index, row_index).unwrap_or(&[]))
-                    .into_iter()
-                    .map(|x| String::from_utf8_lossy(x).to_string())
-                    .map(|x| StyledString::new(x, TextStyle::basic_left()))
+                    .map(|col_index| {
+                        batch.at(col_index, row_index).map(|x| {
+                            let text = String::from_utf8_lossy(x).to_string();
+                            display_text(&data_types[col_index], text, options.binary_format)
+                        })
+                    })
                     .collect();
                 rows.push(row_data);
             }
         }
-        let table = Table::new(headers, rows, TableTheme::rounded());
 
-        Ok(table)
+        Ok(TableData::new(headers, rows)
+            .with_column_types(column_types)
+            .with_alignments(alignments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_to() {
+        let data = TableData::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Some("1".to_string()), Some("hallo world".to_string())],
+                vec![Some("2".to_string()), None],
+                vec![Some("3".to_string()), None],
+            ],
+        );
+        let write = |options: PrintOptions| {
+            let mut buf = vec![];
+            data.clone().write_to(&mut buf, options).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+
+        let markdown = write(PrintOptions {
+            format: OutputFormat::Markdown,
+            max_column_width: Some(5),
+            max_rows: Some(2),
+            null_text: "<null>".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            markdown,
+            "| id | name |\n| --- | --- |\n| 1 | hall… |\n| 2 | <nul… |\n"
+        );
+
+        let csv = write(PrintOptions {
+            format: OutputFormat::Csv,
+            max_column_width: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(csv, "id,name\n1,hallo world\n2,\n3,\n");
+
+        let table = write(PrintOptions {
+            width: Some(20),
+            max_rows: Some(1),
+            color: false,
+            ..Default::default()
+        });
+        assert!(table.lines().all(|x| x.chars().count() <= 20));
+        assert!(table.ends_with("(1 of 3 rows)\n"));
+
+        let typed = TableData::new(vec!["id".to_string()], vec![vec![Some("1".to_string())]])
+            .with_column_types(vec!["INTEGER NOT NULL".to_string()])
+            .with_alignments(vec![Alignment::Right]);
+        let mut buf = vec![];
+        let options = PrintOptions {
+            format: OutputFormat::Markdown,
+            show_column_types: true,
+            ..Default::default()
+        };
+        typed.write_to(&mut buf, options).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "| id<br>INTEGER NOT NULL |\n| ---: |\n| 1 |\n"
+        );
+
+        assert_eq!(truncate_cell("hallo", 5), "hallo");
+        assert_eq!(truncate_cell("hallo", 1), "…");
+        assert_eq!(truncate_cell("hallo", 0), "");
     }
 }
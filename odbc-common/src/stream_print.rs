@@ -0,0 +1,485 @@
+use crate::data_type::{describe_columns, display_text};
+use crate::output_format::{FormatWriter, OutputFormat};
+use crate::print_table::{truncate_cell, PrintOptions, BATCH_SIZE};
+use anyhow::anyhow;
+use nu_table::Alignment;
+use odbc_api::buffers::TextRowSet;
+use odbc_api::Cursor;
+use std::fmt::{Display, Formatter};
+use std::io::{IsTerminal, Write};
+use std::str::FromStr;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns are never narrowed below the width to fit terminal, E.g: `a…`
+const MIN_COLUMN_WIDTH: usize = 3;
+
+/// Where the column widths of streaming table come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnWidth {
+    /// Widest cell of header and the first batch, later longer cells are truncated.
+    #[default]
+    FirstBatch,
+    /// Column display size of result set metadata, rows are printed as soon as fetched.
+    Metadata,
+}
+
+impl Display for ColumnWidth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnWidth::FirstBatch => write!(f, "first-batch"),
+            ColumnWidth::Metadata => write!(f, "metadata"),
+        }
+    }
+}
+
+impl FromStr for ColumnWidth {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first-batch" => Ok(ColumnWidth::FirstBatch),
+            "metadata" => Ok(ColumnWidth::Metadata),
+            _ => Err(anyhow!(
+                "unknown column width:{}, expect first-batch or metadata",
+                s
+            )),
+        }
+    }
+}
+
+/// Print rows batch by batch without buffering the whole result. E.g:
+/// ```no_run
+/// use odbc_api::Environment;
+/// use odbc_common::print_table::PrintOptions;
+/// use odbc_common::stream_print::StreamPrint;
+///
+/// let env = Environment::new().unwrap();
+/// let conn = env.connect_with_connection_string("DSN=dm").unwrap();
+/// let cursor = conn.execute("SELECT * FROM T1", ()).unwrap().unwrap();
+/// let options = PrintOptions {
+///     max_rows: Some(1000),
+///     ..Default::default()
+/// };
+/// let rows = cursor.stream_to(&mut std::io::stdout().lock(), options).unwrap();
+/// ```
+pub trait StreamPrint {
+    /// Return the number of printed rows
+    fn stream_to<W: Write>(self, writer: &mut W, options: PrintOptions) -> anyhow::Result<usize>;
+}
+
+impl<T> StreamPrint for T
+where
+    T: Cursor,
+{
+    fn stream_to<W: Write>(
+        mut self,
+        writer: &mut W,
+        options: PrintOptions,
+    ) -> anyhow::Result<usize> {
+        let mut headers = self.column_names()?.collect::<Result<Vec<String>, _>>()?;
+        let (column_types, alignments, data_types) = describe_columns(&mut self)?;
+        options.prepare_headers(&mut headers, &column_types);
+        let widths = match options.column_width {
+            ColumnWidth::FirstBatch => None,
+            ColumnWidth::Metadata => Some(
+                (1..=headers.len() as u16)
+                    .map(|i| {
+                        self.col_display_size(i)
+                            .map(|x| (x.max(0) as usize).min(options.max_cell_len))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+
+        let mut buffers =
+            TextRowSet::for_cursor(BATCH_SIZE, &mut self, Some(options.max_cell_len))?;
+        let mut row_set_cursor = self.bind_buffer(&mut buffers)?;
+        let mut sink = match options.format {
+            OutputFormat::Table if options.expanded => {
+                RowSink::Expanded(ExpandedWriter::new(&mut *writer, headers))
+            }
+            OutputFormat::Table => RowSink::Table(
+                TableStreamer::new(&mut *writer, headers, widths, &options)
+                    .with_alignments(alignments),
+            ),
+            format => RowSink::Format(FormatWriter::new(
+                format,
+                headers,
+                &alignments,
+                &mut *writer,
+            )?),
+        };
+
+        let max_rows = options.max_rows.unwrap_or(usize::MAX);
+        let mut printed = 0;
+        let mut omitted = false;
+        while let Some(batch) = row_set_cursor.fetch()? {
+            if printed == max_rows {
+                omitted = true;
+                break;
+            }
+            let num_rows = batch.num_rows().min(max_rows - printed);
+            let mut rows: Vec<Vec<Option<String>>> = (0..num_rows)
+                .map(|row_index| {
+                    (0..batch.num_cols())
+                        .map(|col_index| {
+                            batch.at(col_index, row_index).map(|x| {
+                                let text = String::from_utf8_lossy(x).to_string();
+                                display_text(&data_types[col_index], text, options.binary_format)
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+            options.prepare_rows(&mut rows);
+            match &mut sink {
+                RowSink::Table(table) => table.write_rows(&rows)?,
+                RowSink::Expanded(expanded) => expanded.write_rows(&rows)?,
+                RowSink::Format(format_writer) => format_writer.write_rows(&rows)?,
+            }
+            printed += num_rows;
+            if num_rows < batch.num_rows() {
+                omitted = true;
+                break;
+            }
+        }
+
+        match sink {
+            RowSink::Table(table) => {
+                let writer = table.finish()?;
+                if omitted {
+                    writeln!(writer, "({} rows printed, more rows omitted)", printed)?;
+                }
+            }
+            RowSink::Expanded(expanded) => {
+                let writer = expanded.finish()?;
+                if omitted {
+                    writeln!(writer, "({} rows printed, more rows omitted)", printed)?;
+                }
+            }
+            RowSink::Format(format_writer) => {
+                format_writer.finish()?;
+            }
+        }
+        Ok(printed)
+    }
+}
+
+enum RowSink<W: Write> {
+    Table(TableStreamer<W>),
+    Expanded(ExpandedWriter<W>),
+    Format(FormatWriter<W>),
+}
+
+/// Draw rounded box table row by row. Column widths are fixed once the header is drawn,
+/// longer cells are truncated with `…`.
+/// ```
+/// use odbc_common::print_table::PrintOptions;
+/// use odbc_common::stream_print::TableStreamer;
+///
+/// let mut buf = vec![];
+/// let options = PrintOptions {
+///     color: false,
+///     ..Default::default()
+/// };
+/// let mut table = TableStreamer::new(&mut buf, vec!["id".to_string()], None, &options);
+/// table.write_rows(&[vec![Some("1".to_string())]]).unwrap();
+/// table.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "╭────╮\n│ id │\n├────┤\n│ 1  │\n╰────╯\n"
+/// );
+/// ```
+pub struct TableStreamer<W: Write> {
+    writer: W,
+    headers: Vec<String>,
+    /// Fixed column widths, computed from the first batch if `None`
+    widths: Option<Vec<usize>>,
+    header_drawn: bool,
+    /// Alignment of column cells, empty is left aligned.
+    alignments: Vec<Alignment>,
+    max_width: usize,
+    max_column_width: Option<usize>,
+    color: bool,
+}
+
+impl<W: Write> TableStreamer<W> {
+    /// `widths` are fixed column widths, computed from the first batch if `None`.
+    pub fn new(
+        writer: W,
+        headers: Vec<String>,
+        widths: Option<Vec<usize>>,
+        options: &PrintOptions,
+    ) -> Self {
+        let mut table = Self {
+            writer,
+            headers,
+            widths: None,
+            header_drawn: false,
+            alignments: vec![],
+            max_width: options.terminal_width(),
+            max_column_width: options.max_column_width,
+            color: options.color && std::io::stdout().is_terminal(),
+        };
+        if let Some(widths) = widths {
+            table.widths = Some(table.fit_widths(widths));
+        }
+        table
+    }
+
+    pub fn with_alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.alignments = alignments;
+        self
+    }
+
+    pub fn write_rows(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<()> {
+        let widths = self.draw_header(rows)?;
+        for row in rows.iter() {
+            let cells = row.iter().map(|x| x.as_deref().unwrap_or_default());
+            self.write_line(&widths, cells, false)?;
+        }
+        Ok(())
+    }
+
+    /// Draw the bottom border and flush, the header is drawn if there is no row.
+    pub fn finish(mut self) -> anyhow::Result<W> {
+        let widths = self.draw_header(&[])?;
+        self.write_border(&widths, '╰', '┴', '╯')?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Draw the header once and return column widths
+    fn draw_header(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<Vec<usize>> {
+        if self.header_drawn {
+            return Ok(self.widths.clone().unwrap_or_default());
+        }
+        let widths = match self.widths.take() {
+            Some(widths) => widths,
+            None => {
+                let widths = self.headers.iter().map(|x| cell_text(x).width()).collect();
+                let widths = rows.iter().fold(widths, |widths: Vec<usize>, row| {
+                    widths
+                        .into_iter()
+                        .zip(row.iter())
+                        .map(|(w, x)| w.max(x.as_deref().map_or(0, |x| cell_text(x).width())))
+                        .collect()
+                });
+                self.fit_widths(widths)
+            }
+        };
+        self.write_border(&widths, '╭', '┬', '╮')?;
+        let headers = std::mem::take(&mut self.headers);
+        self.write_line(&widths, headers.iter().map(|x| x.as_str()), true)?;
+        self.headers = headers;
+        self.write_border(&widths, '├', '┼', '┤')?;
+        self.header_drawn = true;
+        self.widths = Some(widths.clone());
+        Ok(widths)
+    }
+
+    /// Cap column widths with `max_column_width`, then narrow the widest column until the table
+    /// fits `max_width`.
+    fn fit_widths(&self, widths: Vec<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = widths
+            .into_iter()
+            .map(|x| x.clamp(1, self.max_column_width.unwrap_or(usize::MAX).max(1)))
+            .collect();
+        let border = 3 * widths.len() + 1;
+        while widths.iter().sum::<usize>() + border > self.max_width {
+            match widths.iter_mut().max() {
+                Some(w) if *w > MIN_COLUMN_WIDTH => *w -= 1,
+                _ => break,
+            }
+        }
+        widths
+    }
+
+    fn write_border(
+        &mut self,
+        widths: &[usize],
+        left: char,
+        middle: char,
+        right: char,
+    ) -> anyhow::Result<()> {
+        let line = widths
+            .iter()
+            .map(|w| "─".repeat(w + 2))
+            .collect::<Vec<_>>()
+            .join(&middle.to_string());
+        writeln!(self.writer, "{}{}{}", left, line, right)?;
+        Ok(())
+    }
+
+    fn write_line<'a>(
+        &mut self,
+        widths: &[usize],
+        cells: impl Iterator<Item = &'a str>,
+        header: bool,
+    ) -> anyhow::Result<()> {
+        let mut line = String::from("│");
+        for (i, (width, cell)) in widths.iter().zip(cells).enumerate() {
+            let text = truncate_cell(&cell_text(cell), *width);
+            let padding = " ".repeat(width - text.width().min(*width));
+            if header && self.color {
+                line.push_str(&format!(" \x1b[1;32m{}\x1b[0m{} │", text, padding));
+            } else if !header && self.alignments.get(i) == Some(&Alignment::Right) {
+                line.push_str(&format!(" {}{} │", padding, text));
+            } else {
+                line.push_str(&format!(" {}{} │", text, padding));
+            }
+        }
+        writeln!(self.writer, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Expanded display as `psql` `\x`, each column of a record is printed in its own line. E.g:
+/// ```
+/// use odbc_common::stream_print::ExpandedWriter;
+///
+/// let mut buf = vec![];
+/// let mut expanded = ExpandedWriter::new(&mut buf, vec!["id".to_string(), "name".to_string()]);
+/// expanded
+///     .write_rows(&[vec![Some("1".to_string()), Some("hallo".to_string())]])
+///     .unwrap();
+/// expanded.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "-[ RECORD 1 ]\nid   | 1\nname | hallo\n"
+/// );
+/// ```
+pub struct ExpandedWriter<W: Write> {
+    writer: W,
+    headers: Vec<String>,
+    header_width: usize,
+    rows: usize,
+}
+
+impl<W: Write> ExpandedWriter<W> {
+    pub fn new(writer: W, headers: Vec<String>) -> Self {
+        let header_width = headers
+            .iter()
+            .map(|x| cell_text(x).width())
+            .max()
+            .unwrap_or(0);
+        Self {
+            writer,
+            headers,
+            header_width,
+            rows: 0,
+        }
+    }
+
+    /// `NULL` cell is printed as empty, use [`PrintOptions::prepare_rows`] to fill the text.
+    pub fn write_rows(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<()> {
+        for row in rows.iter() {
+            self.rows += 1;
+            let title = format!("-[ RECORD {} ]", self.rows);
+            let value_width = row
+                .iter()
+                .flat_map(|x| x.as_deref().unwrap_or_default().lines())
+                .map(|x| x.width())
+                .max()
+                .unwrap_or(0);
+            let dashes = (self.header_width + 3 + value_width).saturating_sub(title.width());
+            writeln!(self.writer, "{}{}", title, "-".repeat(dashes))?;
+            for (header, cell) in self.headers.iter().zip(row.iter()) {
+                let header = cell_text(header);
+                let padding = " ".repeat(self.header_width - header.width());
+                let mut lines = cell.as_deref().unwrap_or_default().lines();
+                writeln!(
+                    self.writer,
+                    "{}{} | {}",
+                    header,
+                    padding,
+                    lines.next().unwrap_or_default()
+                )?;
+                for line in lines {
+                    writeln!(self.writer, "{} | {}", " ".repeat(self.header_width), line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush and return the writer, `(0 rows)` is printed if there is no record.
+    pub fn finish(mut self) -> anyhow::Result<W> {
+        if self.rows == 0 {
+            writeln!(self.writer, "(0 rows)")?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Table row is single line, control chars are replaced with space.
+fn cell_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_streamer() {
+        let options = PrintOptions {
+            width: Some(24),
+            color: false,
+            ..Default::default()
+        };
+        let mut buf = vec![];
+        let mut table = TableStreamer::new(
+            &mut buf,
+            vec!["id".to_string(), "name".to_string()],
+            None,
+            &options,
+        );
+        table
+            .write_rows(&[
+                vec![Some("1".to_string()), Some("hallo".to_string())],
+                vec![Some("2".to_string()), Some("NULL".to_string())],
+            ])
+            .unwrap();
+        table
+            .write_rows(&[vec![
+                Some("3".to_string()),
+                Some("hallo\nworld".to_string()),
+            ]])
+            .unwrap();
+        table.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "╭────┬───────╮\n\
+             │ id │ name  │\n\
+             ├────┼───────┤\n\
+             │ 1  │ hallo │\n\
+             │ 2  │ NULL  │\n\
+             │ 3  │ hall… │\n\
+             ╰────┴───────╯\n"
+        );
+
+        let options = PrintOptions {
+            width: Some(16),
+            color: false,
+            ..Default::default()
+        };
+        let table = TableStreamer::new(
+            vec![],
+            vec!["id".to_string(), "name".to_string()],
+            Some(vec![10, 20]),
+            &options,
+        );
+        let output = String::from_utf8(table.finish().unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "╭───────┬──────╮\n\
+             │ id    │ name │\n\
+             ├───────┼──────┤\n\
+             ╰───────┴──────╯\n"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/parser.rs"]
+mod parser;
+
+fn main() {
+    println!("cargo:rerun-if-changed=pg_type.dat");
+    println!("cargo:rerun-if-changed=src/parser.rs");
+
+    let content = fs::read_to_string("pg_type.dat").expect("read pg_type.dat error");
+    let rows = parser::parse(&content).expect("parse pg_type.dat error");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("pg_type.rs");
+    fs::write(out, parser::generate(&rows)).expect("write pg_type.rs error");
+}
@@ -0,0 +1,289 @@
+use crate::table::{PgTableDesc, PgTableItem};
+use crate::PgType;
+
+/// Postgres reserved keywords, must be quoted when used as identifier.
+/// See: <https://www.postgresql.org/docs/current/sql-keywords-appendix.html>
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "similar",
+    "some",
+    "symmetric",
+    "system_user",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// Quote postgres identifier.
+///
+/// When `case_sensitive` is true, the identifier is always quoted to keep the original case.
+/// Otherwise it is folded to lowercase, and only quoted when it is a reserved keyword or
+/// contains special characters.
+///
+/// # Example
+///
+/// ```rust
+/// use pg_helper::ddl::quote_ident;
+///
+/// assert_eq!(quote_ident("UserName", true), r#""UserName""#);
+/// assert_eq!(quote_ident("USER_NAME", false), "user_name");
+/// assert_eq!(quote_ident("USER", false), r#""user""#);
+/// assert_eq!(quote_ident("A-B", false), r#""a-b""#);
+/// ```
+pub fn quote_ident(name: &str, case_sensitive: bool) -> String {
+    let quote = |x: &str| format!("\"{}\"", x.replace('"', "\"\""));
+    if case_sensitive {
+        return quote(name);
+    }
+
+    let name = name.to_lowercase();
+    let simple = name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_lowercase() || c == '_')
+        .unwrap_or_default()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$');
+    if simple && !RESERVED_KEYWORDS.contains(&name.as_str()) {
+        name
+    } else {
+        quote(&name)
+    }
+}
+
+fn quote_table(schema: Option<&str>, table_name: &str, case_sensitive: bool) -> String {
+    match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_ident(schema, case_sensitive),
+            quote_ident(table_name, case_sensitive)
+        ),
+        None => quote_ident(table_name, case_sensitive),
+    }
+}
+
+impl PgTableItem {
+    /// Get column type sql with type modifiers. E.g: `varchar(100)`, `numeric(10,2)`, `timestamp(3)`
+    pub fn type_sql(&self) -> String {
+        match self.r#type {
+            PgType::VARCHAR | PgType::BIT | PgType::VARBIT if self.length > 0 => {
+                format!("{}({})", self.r#type.name(), self.length)
+            }
+            PgType::BPCHAR if self.length > 0 => format!("char({})", self.length),
+            // single byte internal type, differs from `char(n)` which is `bpchar`
+            PgType::CHAR => "\"char\"".to_string(),
+            PgType::NUMERIC if self.length > 0 => match self.scale {
+                0 => format!("numeric({})", self.length),
+                scale => format!("numeric({},{})", self.length, scale),
+            },
+            // `scale` is fractional seconds precision, omitted when unknown or the default 6
+            PgType::TIME | PgType::TIMETZ | PgType::TIMESTAMP | PgType::TIMESTAMPTZ
+                if (1..6).contains(&self.scale) =>
+            {
+                format!("{}({})", self.r#type.name(), self.scale)
+            }
+            // Unknown type could not be used in table, keep the value as text
+            PgType::UNKNOWN => PgType::TEXT.name().to_string(),
+            _ => self.r#type.name().to_string(),
+        }
+    }
+
+    /// Get column definition sql. E.g: `user_name varchar(100) DEFAULT 'x' NOT NULL`
+    pub fn column_sql(&self, case_sensitive: bool) -> String {
+        let mut sql = format!(
+            "{} {}",
+            quote_ident(&self.name, case_sensitive),
+            self.type_sql()
+        );
+        if let Some(default_val) = self.default_val.as_ref().filter(|x| !x.is_empty()) {
+            sql.push_str(" DEFAULT ");
+            sql.push_str(default_val);
+        }
+        if !self.nullable {
+            sql.push_str(" NOT NULL");
+        }
+        sql
+    }
+}
+
+impl PgTableDesc {
+    /// Generate postgres `CREATE TABLE` sql of all tables.
+    /// The identifiers are quoted according to `case_sensitive`, see [`quote_ident`].
+    pub fn to_create_table_sql(&self, schema: Option<&str>, case_sensitive: bool) -> String {
+        self.data
+            .iter()
+            .map(|(table_name, items)| {
+                let mut items = items.iter().collect::<Vec<_>>();
+                items.sort_by_key(|x| x.col_index);
+                let columns = items
+                    .into_iter()
+                    .map(|x| x.column_sql(case_sensitive))
+                    .collect::<Vec<_>>();
+                format!(
+                    "CREATE TABLE {} (\n    {}\n);",
+                    quote_table(schema, table_name, case_sensitive),
+                    columns.join(",\n    ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_pg_create_table_sql() {
+        let item = |name: &str, col_index, r#type, length, scale, nullable, default_val: &str| {
+            PgTableItem {
+                name: name.to_string(),
+                table_id: 1058,
+                col_index,
+                r#type,
+                length,
+                scale,
+                nullable,
+                default_val: Some(default_val.to_string()),
+                table_name: "T1".to_string(),
+                create_time: "".to_string(),
+            }
+        };
+        let desc = PgTableDesc {
+            data: BTreeMap::from([(
+                "T1".to_string(),
+                vec![
+                    item("ID", 0, PgType::INT4, 4, 0, false, ""),
+                    item("PRICE", 1, PgType::NUMERIC, 10, 2, true, "0"),
+                    item("USER", 2, PgType::VARCHAR, 100, 0, false, "'foo'"),
+                    item(
+                        "CREATED",
+                        3,
+                        PgType::TIMESTAMP,
+                        8,
+                        6,
+                        true,
+                        "LOCALTIMESTAMP",
+                    ),
+                    item("AMOUNT", 4, PgType::NUMERIC, 0, 0, true, ""),
+                    item("CODE", 5, PgType::BPCHAR, 2, 0, true, ""),
+                    item("FLAG", 6, PgType::CHAR, 1, 0, true, ""),
+                    item("UPDATED", 7, PgType::TIMESTAMPTZ, 8, 3, true, ""),
+                    item("OPENED", 8, PgType::TIME, 8, 0, true, ""),
+                ],
+            )]),
+        };
+
+        let expect = r#"CREATE TABLE public.t1 (
+    id int4 NOT NULL,
+    price numeric(10,2) DEFAULT 0,
+    "user" varchar(100) DEFAULT 'foo' NOT NULL,
+    created timestamp DEFAULT LOCALTIMESTAMP,
+    amount numeric,
+    code char(2),
+    flag "char",
+    updated timestamptz(3),
+    opened time
+);"#;
+        assert_eq!(desc.to_create_table_sql(Some("public"), false), expect);
+
+        let sql = desc.to_create_table_sql(None, true);
+        assert!(sql.starts_with("CREATE TABLE \"T1\" (\n    \"ID\" int4 NOT NULL,"));
+    }
+}
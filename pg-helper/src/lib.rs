@@ -1,32 +1,67 @@
 #![deny(missing_debug_implementations)]
 #![allow(dead_code)]
 
-mod parser;
+pub mod ddl;
 pub mod table;
+pub mod type_info;
 
 pub use postgres_types::Type as PgType;
+pub use type_info::{builtin_types, type_info, type_info_by_oid, PgTypeInfo};
 
 pub fn oid_typlen(pg_type: PgType) -> i16 {
-    match pg_type {
-        PgType::BOOL => 1,
-        PgType::BYTEA => -1,
-        PgType::CHAR => 1,
-        PgType::INT8 => 8,
-        PgType::INT2 => 2,
-        PgType::INT2_VECTOR => -1,
-        PgType::INT4 => 4,
-        PgType::TEXT => -1,
-        PgType::FLOAT4 => 4,
-        PgType::FLOAT8 => 8,
-        PgType::VARCHAR => -1,
-        PgType::DATE => 4,
-        PgType::TIME => 8,
-        PgType::TIMESTAMP => 8,
-        PgType::TIMESTAMPTZ => 8,
-        PgType::TIMETZ => 12,
-        PgType::BIT => -1,
-        PgType::JSONB => -1,
-        PgType::NAME => 64,
-        _ => panic!("unknown pg_type:{}", pg_type),
-    }
+    type_info(&pg_type).typlen
+}
+
+pub fn oid_typalign(pg_type: PgType) -> char {
+    type_info(&pg_type).typalign
+}
+
+pub fn oid_typcategory(pg_type: PgType) -> char {
+    type_info(&pg_type).typcategory
+}
+
+/// Get pg type by type name or common alias, E.g: `int4`, `integer`, `timestamptz`.
+///
+/// # Example
+///
+/// ```rust
+/// use pg_helper::{type_from_name, PgType};
+///
+/// assert_eq!(type_from_name("INTEGER"), Some(PgType::INT4));
+/// assert_eq!(type_from_name("varchar"), Some(PgType::VARCHAR));
+/// assert_eq!(type_from_name("foo"), None);
+/// ```
+pub fn type_from_name(name: &str) -> Option<PgType> {
+    let pg_type = match &*name.trim().to_lowercase() {
+        "bool" | "boolean" => PgType::BOOL,
+        "bytea" => PgType::BYTEA,
+        "char" => PgType::CHAR,
+        "bpchar" | "character" => PgType::BPCHAR,
+        "name" => PgType::NAME,
+        "int8" | "bigint" => PgType::INT8,
+        "int2" | "smallint" => PgType::INT2,
+        "int4" | "int" | "integer" => PgType::INT4,
+        "text" => PgType::TEXT,
+        "json" => PgType::JSON,
+        "jsonb" => PgType::JSONB,
+        "float4" | "real" => PgType::FLOAT4,
+        "float8" | "double precision" => PgType::FLOAT8,
+        "varchar" | "character varying" => PgType::VARCHAR,
+        "date" => PgType::DATE,
+        "time" | "time without time zone" => PgType::TIME,
+        "timetz" | "time with time zone" => PgType::TIMETZ,
+        "timestamp" | "timestamp without time zone" => PgType::TIMESTAMP,
+        "timestamptz" | "timestamp with time zone" => PgType::TIMESTAMPTZ,
+        "interval" => PgType::INTERVAL,
+        "bit" => PgType::BIT,
+        "varbit" | "bit varying" => PgType::VARBIT,
+        "numeric" | "decimal" => PgType::NUMERIC,
+        "uuid" => PgType::UUID,
+        "inet" => PgType::INET,
+        "cidr" => PgType::CIDR,
+        "money" => PgType::MONEY,
+        "unknown" => PgType::UNKNOWN,
+        _ => return None,
+    };
+    Some(pg_type)
 }
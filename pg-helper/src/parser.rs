@@ -1,3 +1,254 @@
-// fn parser() {
-//     // https://github.com/sfackler/rust-postgres/blob/fc10985f9fdf0903893109bc951fb5891539bf97/codegen/src/pg_type.dat
-// }
+//! Parser of postgres `pg_type.dat`, used by `build.rs` to generate the builtin type table.
+//!
+//! The format is described in <https://www.postgresql.org/docs/current/system-catalog-initial-data.html>.
+//! Source: <https://github.com/postgres/postgres/blob/REL_15_STABLE/src/include/catalog/pg_type.dat>
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// One row of `pg_type` with the columns needed by type info, references are resolved to oid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgTypeRow {
+    pub oid: u32,
+    pub typname: String,
+    pub typlen: i16,
+    pub typbyval: bool,
+    pub typtype: char,
+    pub typalign: char,
+    pub typcategory: char,
+    pub typdelim: char,
+    pub typelem: u32,
+    pub typarray: u32,
+}
+
+/// Parse `pg_type.dat` content, array types declared by `array_type_oid` are generated the same
+/// way as `genbki.pl` does. Rows are sorted by oid.
+pub fn parse(content: &str) -> Result<Vec<PgTypeRow>, String> {
+    let entries = parse_entries(content)?;
+
+    let mut name_oid = BTreeMap::new();
+    for entry in entries.iter() {
+        name_oid.insert(entry.get("typname")?.to_string(), entry.oid()?);
+        if let Some(array_oid) = entry.values.get("array_type_oid") {
+            name_oid.insert(format!("_{}", entry.get("typname")?), parse_oid(array_oid)?);
+        }
+    }
+    let lookup = |name: &str| -> Result<u32, String> {
+        name_oid
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("unknown type name:{}", name))
+    };
+
+    let mut rows = vec![];
+    for entry in entries.iter() {
+        let typarray = match (
+            entry.values.get("typarray"),
+            entry.values.get("array_type_oid"),
+        ) {
+            (Some(name), _) => lookup(name)?,
+            (None, Some(oid)) => parse_oid(oid)?,
+            (None, None) => 0,
+        };
+        let row = PgTypeRow {
+            oid: entry.oid()?,
+            typname: entry.get("typname")?.to_string(),
+            typlen: parse_typlen(entry.get("typlen")?)?,
+            typbyval: parse_typbyval(entry.get("typbyval")?)?,
+            typtype: entry
+                .values
+                .get("typtype")
+                .map_or(Ok('b'), |x| parse_char(x))?,
+            typalign: parse_typalign(entry.get("typalign")?)?,
+            typcategory: parse_char(entry.get("typcategory")?)?,
+            typdelim: entry
+                .values
+                .get("typdelim")
+                .map_or(Ok(','), |x| parse_char(x))?,
+            typelem: entry.values.get("typelem").map_or(Ok(0), |x| lookup(x))?,
+            typarray,
+        };
+
+        if let Some(array_oid) = entry.values.get("array_type_oid") {
+            rows.push(PgTypeRow {
+                oid: parse_oid(array_oid)?,
+                typname: format!("_{}", row.typname),
+                typlen: -1,
+                typbyval: false,
+                typtype: 'b',
+                typalign: if row.typalign == 'd' { 'd' } else { 'i' },
+                typcategory: 'A',
+                typdelim: row.typdelim,
+                typelem: row.oid,
+                typarray: 0,
+            });
+        }
+        rows.push(row);
+    }
+
+    rows.sort_by_key(|x| x.oid);
+    if let Some(x) = rows.windows(2).find(|x| x[0].oid == x[1].oid) {
+        return Err(format!("duplicate oid:{}", x[0].oid));
+    }
+    Ok(rows)
+}
+
+/// Generate rust source of `PG_TYPE_INFO`, a `(oid, typname, PgTypeInfo)` slice sorted by oid.
+pub fn generate(rows: &[PgTypeRow]) -> String {
+    let mut code = String::new();
+    writeln!(
+        code,
+        "// Generated from `pg_type.dat` by `build.rs`, do not edit."
+    )
+    .unwrap();
+    writeln!(code).unwrap();
+    writeln!(
+        code,
+        "pub(crate) static PG_TYPE_INFO: &[(Oid, &str, PgTypeInfo)] = &["
+    )
+    .unwrap();
+    for row in rows {
+        writeln!(
+            code,
+            "    ({}, {:?}, PgTypeInfo {{ typlen: {}, typbyval: {}, typtype: {:?}, typalign: {:?}, typcategory: {:?}, typdelim: {:?}, typelem: {}, typarray: {} }}),",
+            row.oid,
+            row.typname,
+            row.typlen,
+            row.typbyval,
+            row.typtype,
+            row.typalign,
+            row.typcategory,
+            row.typdelim,
+            row.typelem,
+            row.typarray
+        )
+        .unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    values: BTreeMap<String, String>,
+}
+
+impl Entry {
+    fn get(&self, key: &str) -> Result<&str, String> {
+        self.values.get(key).map(|x| x.as_str()).ok_or_else(|| {
+            format!(
+                "missing {} of type {}",
+                key,
+                self.values.get("oid").map_or("", |x| x.as_str())
+            )
+        })
+    }
+
+    fn oid(&self) -> Result<u32, String> {
+        parse_oid(self.get("oid")?)
+    }
+}
+
+/// Parse entries like `{ oid => '16', typname => 'bool' },`, `#` starts a line comment.
+fn parse_entries(content: &str) -> Result<Vec<Entry>, String> {
+    let content = content
+        .lines()
+        .filter(|x| !x.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut entries = vec![];
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        let mut entry = Entry::default();
+        loop {
+            let key = chars
+                .by_ref()
+                .skip_while(|c| c.is_whitespace() || *c == ',')
+                .take_while(|c| !c.is_whitespace())
+                .collect::<String>();
+            if key == "}" {
+                break;
+            }
+            if key.is_empty() {
+                return Err("unexpected end of pg_type.dat".to_string());
+            }
+            let arrow = chars
+                .by_ref()
+                .skip_while(|c| c.is_whitespace())
+                .take(2)
+                .collect::<String>();
+            if arrow != "=>" || chars.by_ref().find(|c| !c.is_whitespace()) != Some('\'') {
+                return Err(format!("invalid value of key:{}", key));
+            }
+
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => value.extend(chars.next()),
+                    Some('\'') => break,
+                    Some(c) => value.push(c),
+                    None => return Err(format!("unterminated value of key:{}", key)),
+                }
+            }
+            entry.values.insert(key, value);
+
+            // the closing brace may follow the value without whitespace
+            while let Some(c) = chars.peek() {
+                match c {
+                    ',' => {
+                        chars.next();
+                    }
+                    c if c.is_whitespace() => {
+                        chars.next();
+                    }
+                    _ => break,
+                }
+            }
+            if chars.peek() == Some(&'}') {
+                chars.next();
+                break;
+            }
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_oid(s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("invalid oid:{}", s))
+}
+
+/// Symbols are resolved as 64 bit platform.
+fn parse_typlen(s: &str) -> Result<i16, String> {
+    match s {
+        "NAMEDATALEN" => Ok(64),
+        "SIZEOF_POINTER" => Ok(8),
+        _ => s.parse().map_err(|_| format!("invalid typlen:{}", s)),
+    }
+}
+
+fn parse_typbyval(s: &str) -> Result<bool, String> {
+    match s {
+        "t" | "FLOAT8PASSBYVAL" => Ok(true),
+        "f" => Ok(false),
+        _ => Err(format!("invalid typbyval:{}", s)),
+    }
+}
+
+fn parse_typalign(s: &str) -> Result<char, String> {
+    match s {
+        "ALIGNOF_POINTER" => Ok('d'),
+        _ => parse_char(s),
+    }
+}
+
+fn parse_char(s: &str) -> Result<char, String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("invalid char value:{}", s)),
+    }
+}
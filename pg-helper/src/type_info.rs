@@ -0,0 +1,162 @@
+use crate::PgType;
+use postgres_types::{Kind, Oid};
+
+// `PG_TYPE_INFO`, generated from `pg_type.dat` by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/pg_type.rs"));
+
+/// Storage attributes of postgres type in system catalog `pg_type`.
+/// See: <https://www.postgresql.org/docs/current/catalog-pg-type.html>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgTypeInfo {
+    /// For a fixed-size type, the number of bytes. -1 is varlena type, -2 is null-terminated
+    /// C string.
+    pub typlen: i16,
+    /// Whether values are passed by value rather than by reference
+    pub typbyval: bool,
+    /// `b` base, `c` composite, `d` domain, `e` enum, `p` pseudo, `r` range, `m` multirange
+    pub typtype: char,
+    /// Alignment required when storing a value: `c` char, `s` short, `i` int, `d` double
+    pub typalign: char,
+    /// Arbitrary classification used by the parser, E.g: `N` numeric, `S` string, `A` array
+    pub typcategory: char,
+    /// Character that separates two values of this type when parsing array input
+    pub typdelim: char,
+    /// Element type oid of array type, otherwise 0
+    pub typelem: Oid,
+    /// Array type oid which has this type as element, otherwise 0
+    pub typarray: Oid,
+}
+
+impl PgTypeInfo {
+    const fn new(typlen: i16, typalign: char, typcategory: char) -> Self {
+        Self {
+            typlen,
+            typbyval: false,
+            typtype: 'b',
+            typalign,
+            typcategory,
+            typdelim: ',',
+            typelem: 0,
+            typarray: 0,
+        }
+    }
+}
+
+/// Get the `pg_type` attributes of postgres type.
+/// Builtin types are looked up by oid, array, range, enum, composite and domain types which are
+/// not builtin are resolved by type kind.
+///
+/// # Example
+///
+/// ```rust
+/// use pg_helper::{type_info, PgType};
+///
+/// assert_eq!(type_info(&PgType::INT8).typlen, 8);
+/// assert_eq!(type_info(&PgType::UUID).typalign, 'c');
+/// assert_eq!(type_info(&PgType::FLOAT8_ARRAY).typalign, 'd');
+/// assert_eq!(type_info(&PgType::TEXT_ARRAY).typelem, PgType::TEXT.oid());
+/// assert_eq!(type_info(&PgType::BOX).typdelim, ';');
+/// ```
+pub fn type_info(pg_type: &PgType) -> PgTypeInfo {
+    type_info_by_oid(pg_type.oid()).unwrap_or_else(|| type_info_by_kind(pg_type))
+}
+
+/// Get the `pg_type` attributes of builtin type, `None` if oid is not builtin.
+pub fn type_info_by_oid(oid: Oid) -> Option<PgTypeInfo> {
+    PG_TYPE_INFO
+        .binary_search_by_key(&oid, |(oid, ..)| *oid)
+        .ok()
+        .map(|index| PG_TYPE_INFO[index].2)
+}
+
+/// All builtin types of `pg_type` as `(oid, typname, info)`, sorted by oid.
+pub fn builtin_types() -> impl Iterator<Item = (Oid, &'static str, PgTypeInfo)> {
+    PG_TYPE_INFO.iter().copied()
+}
+
+fn type_info_by_kind(pg_type: &PgType) -> PgTypeInfo {
+    match pg_type.kind() {
+        // Array alignment is `d` when element alignment is `d`, otherwise `i`
+        Kind::Array(element) => {
+            let element_info = type_info(element);
+            let typalign = match element_info.typalign {
+                'd' => 'd',
+                _ => 'i',
+            };
+            PgTypeInfo {
+                typdelim: element_info.typdelim,
+                typelem: element.oid(),
+                ..PgTypeInfo::new(-1, typalign, 'A')
+            }
+        }
+        Kind::Range(_) => PgTypeInfo {
+            typtype: 'r',
+            ..PgTypeInfo::new(-1, 'd', 'R')
+        },
+        Kind::Enum(_) => PgTypeInfo {
+            typbyval: true,
+            typtype: 'e',
+            ..PgTypeInfo::new(4, 'i', 'E')
+        },
+        Kind::Composite(_) => PgTypeInfo {
+            typtype: 'c',
+            ..PgTypeInfo::new(-1, 'd', 'C')
+        },
+        Kind::Domain(base) => PgTypeInfo {
+            typtype: 'd',
+            typelem: 0,
+            typarray: 0,
+            ..type_info(base)
+        },
+        Kind::Pseudo => PgTypeInfo {
+            typtype: 'p',
+            ..PgTypeInfo::new(4, 'i', 'P')
+        },
+        // user defined base type
+        _ => PgTypeInfo::new(-1, 'i', 'U'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pg_type_info() {
+        assert!(PG_TYPE_INFO.windows(2).all(|x| x[0].0 < x[1].0));
+        for (oid, _, info) in PG_TYPE_INFO.iter() {
+            if info.typcategory == 'A' && info.typelem != 0 {
+                let element = type_info_by_oid(info.typelem).unwrap();
+                assert_eq!(info.typdelim, element.typdelim, "oid {}", oid);
+            }
+        }
+
+        let bool = type_info(&PgType::BOOL);
+        assert_eq!((bool.typlen, bool.typbyval, bool.typalign), (1, true, 'c'));
+        assert_eq!(bool.typarray, PgType::BOOL_ARRAY.oid());
+        assert_eq!(type_info(&PgType::NAME).typlen, 64);
+        assert_eq!(type_info(&PgType::NAME).typelem, PgType::CHAR.oid());
+        assert!(type_info(&PgType::INT8).typbyval);
+        assert_eq!(type_info(&PgType::INTERNAL).typlen, 8);
+        assert_eq!(type_info(&PgType::INT4_RANGE).typtype, 'r');
+        assert_eq!(
+            builtin_types().find(|(_, name, _)| *name == "_int4"),
+            Some((
+                PgType::INT4_ARRAY.oid(),
+                "_int4",
+                type_info(&PgType::INT4_ARRAY)
+            ))
+        );
+        assert_eq!(type_info_by_oid(16384), None);
+
+        let custom_array = PgType::new(
+            "_custom".to_string(),
+            16385,
+            Kind::Array(PgType::BOX),
+            "public".to_string(),
+        );
+        let info = type_info(&custom_array);
+        assert_eq!((info.typalign, info.typdelim), ('d', ';'));
+        assert_eq!(info.typelem, PgType::BOX.oid());
+    }
+}